@@ -116,6 +116,10 @@ func ValidateAuth(c *fiber.Ctx) error {
 		tokenString = c.Cookies("access_token")
 	}
 
+	if tokenString == "" && queryTokenAuthEnabled() {
+		tokenString = c.Query("access_token")
+	}
+
 	if tokenString == "" {
 		return c.Status(fiber.StatusUnauthorized).JSON(ErrorResponse{
 			Status: "unauthorized",
@@ -178,6 +182,20 @@ func ValidateAuth(c *fiber.Ctx) error {
 	return nil
 }
 
+// queryTokenAuthEnabled reports whether ValidateAuth may fall back to an
+// ?access_token= query parameter when neither the Authorization header nor
+// the access_token cookie is present. It exists for clients that can only
+// issue a bare GET (an <img src>, a link opened from a widget).
+//
+// Off unless AUTH_QUERY_TOKEN_ENABLED=true. A token in the URL ends up in
+// browser history, in the logs of any proxy or CDN in front of us, and in
+// the Referer header of whatever the response links to — and a JWT is a
+// bearer credential until it expires. The proxy strips the parameter
+// before forwarding (stripAuthQuery) so channel services never log it.
+func queryTokenAuthEnabled() bool {
+	return os.Getenv("AUTH_QUERY_TOKEN_ENABLED") == "true"
+}
+
 // LogtoAuth is the Fiber middleware that validates the Logto JWT and advances
 // to the next handler. For inline auth checks (e.g. in the dynamic proxy),
 // use ValidateAuth instead.
@@ -31,6 +31,7 @@ const (
 	DBMaxConnIdleTime = 30 * time.Minute
 	DBMaxRetries      = 5
 	DBRetryDelay      = 2 * time.Second
+	DBMaxRetryDelay   = 30 * time.Second
 )
 
 // =============================================================================
@@ -107,6 +108,9 @@ const (
 	DashboardCacheTTL = 30 * time.Second
 	HealthCacheTTL    = 10 * time.Second
 	HealthCacheKey    = "cache:health"
+
+	HealthDashboardCacheTTL = 5 * time.Second
+	HealthDashboardCacheKey = "cache:health:dashboard"
 )
 
 // =============================================================================
@@ -4,6 +4,7 @@ import (
 	"context"
 	"log"
 	"os"
+	"strconv"
 	"strings"
 	"time"
 
@@ -46,23 +47,31 @@ func ConnectDB() {
 	// pending connection dials — the default is effectively unbounded.
 	config.ConnConfig.ConnectTimeout = 5 * time.Second
 
+	// Orchestrated startups often bring the API up before Postgres is
+	// accepting connections, so wait for it instead of crash-looping.
 	var pool *pgxpool.Pool
-	retries := DBMaxRetries
-	for i := 0; i < retries; i++ {
+	attempts := envInt("DB_CONNECT_RETRIES", DBMaxRetries)
+	baseDelay := time.Duration(envInt("DB_CONNECT_RETRY_DELAY_SECS", int(DBRetryDelay/time.Second))) * time.Second
+	for attempt := 1; attempt <= attempts; attempt++ {
 		pool, err = pgxpool.NewWithConfig(context.Background(), config)
 		if err == nil {
 			err = pool.Ping(context.Background())
 			if err == nil {
 				break
 			}
+			pool.Close()
+		}
+		if attempt == attempts {
+			break
 		}
 
-		log.Printf("[Database] Failed to connect, retrying in 2 seconds... (%d attempts left)", retries-i-1)
-		time.Sleep(DBRetryDelay)
+		delay := retryBackoff(baseDelay, attempt)
+		log.Printf("[Database] Connection attempt %d/%d failed: %v; retrying in %s", attempt, attempts, err, delay)
+		time.Sleep(delay)
 	}
 
 	if err != nil {
-		log.Fatalf("Unable to connect to database after retries")
+		log.Fatalf("Unable to connect to database after %d attempts: %v", attempts, err)
 	}
 
 	DBPool = pool
@@ -136,3 +145,28 @@ func StartWebhookEventsPruner(ctx context.Context) {
 	}()
 	log.Println("[Database] Webhook events pruner started (6h interval)")
 }
+
+// retryBackoff is the wait after the given failed attempt (1-based): base,
+// doubled for each further attempt, capped at DBMaxRetryDelay.
+func retryBackoff(base time.Duration, attempt int) time.Duration {
+	delay := base
+	for i := 1; i < attempt && delay < DBMaxRetryDelay; i++ {
+		delay *= 2
+	}
+	return min(delay, DBMaxRetryDelay)
+}
+
+// envInt reads a positive integer from the environment, falling back to
+// def (with a log line) when it is unset or invalid.
+func envInt(name string, def int) int {
+	raw := strings.TrimSpace(os.Getenv(name))
+	if raw == "" {
+		return def
+	}
+	v, err := strconv.Atoi(raw)
+	if err != nil || v <= 0 {
+		log.Printf("[Database] %s=%q is invalid, defaulting to %d", name, raw, def)
+		return def
+	}
+	return v
+}
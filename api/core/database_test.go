@@ -0,0 +1,33 @@
+package core
+
+import (
+	"testing"
+	"time"
+)
+
+func TestRetryBackoff(t *testing.T) {
+	base := 2 * time.Second
+	want := []time.Duration{2 * time.Second, 4 * time.Second, 8 * time.Second, 16 * time.Second, 30 * time.Second, 30 * time.Second}
+	for i, w := range want {
+		if got := retryBackoff(base, i+1); got != w {
+			t.Errorf("retryBackoff(%s, %d) = %s, want %s", base, i+1, got, w)
+		}
+	}
+}
+
+func TestEnvInt(t *testing.T) {
+	t.Setenv("DB_CONNECT_RETRIES", "")
+	if got := envInt("DB_CONNECT_RETRIES", 5); got != 5 {
+		t.Errorf("unset: got %d, want 5", got)
+	}
+	t.Setenv("DB_CONNECT_RETRIES", " 12 ")
+	if got := envInt("DB_CONNECT_RETRIES", 5); got != 12 {
+		t.Errorf("12: got %d, want 12", got)
+	}
+	for _, raw := range []string{"0", "-3", "ten"} {
+		t.Setenv("DB_CONNECT_RETRIES", raw)
+		if got := envInt("DB_CONNECT_RETRIES", 5); got != 5 {
+			t.Errorf("%q: got %d, want the default 5", raw, got)
+		}
+	}
+}
@@ -0,0 +1,165 @@
+package core
+
+import (
+	"context"
+	"encoding/json"
+	"io"
+	"log"
+	"net/http"
+	"os"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/gofiber/fiber/v2"
+)
+
+// HealthTarget is one service probed by /health/dashboard.
+type HealthTarget struct {
+	Name string
+	URL  string
+}
+
+// ServiceHealth is one service's entry in the dashboard. URLs and error
+// details stay in the logs; the route is public like /health.
+type ServiceHealth struct {
+	Status         string `json:"status"`
+	HTTPStatus     int    `json:"http_status,omitempty"`
+	ResponseTimeMs int64  `json:"response_time_ms"`
+}
+
+// HealthDashboard is the /health/dashboard response: every configured
+// service's probe result plus one overall status.
+type HealthDashboard struct {
+	Status    string                   `json:"status"`
+	CheckedAt time.Time                `json:"checked_at"`
+	Services  map[string]ServiceHealth `json:"services"`
+}
+
+// parseHealthTargets reads HEALTH_DASHBOARD_SERVICES: comma-separated
+// name=url pairs, e.g. "finance-service=http://finance-service:3001/health".
+// Malformed entries are logged and skipped.
+func parseHealthTargets(raw string) []HealthTarget {
+	var targets []HealthTarget
+	for _, entry := range strings.Split(raw, ",") {
+		entry = strings.TrimSpace(entry)
+		if entry == "" {
+			continue
+		}
+		name, rawURL, ok := strings.Cut(entry, "=")
+		name, targetURL := strings.TrimSpace(name), ValidateURL(rawURL, "")
+		if !ok || name == "" || targetURL == "" {
+			log.Printf("[HealthDashboard] Ignoring HEALTH_DASHBOARD_SERVICES entry %q; want name=url", entry)
+			continue
+		}
+		targets = append(targets, HealthTarget{Name: name, URL: targetURL})
+	}
+	return targets
+}
+
+// healthDashboardTargets is every registered health_checker channel's
+// /internal/health plus the services listed in HEALTH_DASHBOARD_SERVICES.
+// An env entry with a channel's name replaces that channel's default URL.
+func healthDashboardTargets() []HealthTarget {
+	byName := make(map[string]string)
+	for _, ch := range GetAllChannels() {
+		if ch.HasCapability("health_checker") {
+			byName[ch.Name] = ch.InternalURL + "/internal/health"
+		}
+	}
+	for _, t := range parseHealthTargets(os.Getenv("HEALTH_DASHBOARD_SERVICES")) {
+		byName[t.Name] = t.URL
+	}
+
+	targets := make([]HealthTarget, 0, len(byName))
+	for name, u := range byName {
+		targets = append(targets, HealthTarget{Name: name, URL: u})
+	}
+	sort.Slice(targets, func(i, j int) bool { return targets[i].Name < targets[j].Name })
+	return targets
+}
+
+// probeHealthTargets checks every target concurrently. A 200 is "healthy",
+// any other response "degraded", and no response at all "down". The
+// overall status is "healthy" only when every service is.
+func probeHealthTargets(ctx context.Context, client *http.Client, targets []HealthTarget) HealthDashboard {
+	res := HealthDashboard{
+		Status:    "healthy",
+		CheckedAt: time.Now().UTC(),
+		Services:  make(map[string]ServiceHealth, len(targets)),
+	}
+
+	var mu sync.Mutex
+	var wg sync.WaitGroup
+	for _, t := range targets {
+		wg.Add(1)
+		go func(t HealthTarget) {
+			defer wg.Done()
+			sh := probeHealthTarget(ctx, client, t)
+			mu.Lock()
+			defer mu.Unlock()
+			res.Services[t.Name] = sh
+			if sh.Status != "healthy" {
+				res.Status = "degraded"
+			}
+		}(t)
+	}
+	wg.Wait()
+	return res
+}
+
+func probeHealthTarget(ctx context.Context, client *http.Client, t HealthTarget) ServiceHealth {
+	start := time.Now()
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, t.URL, nil)
+	if err != nil {
+		log.Printf("[HealthDashboard] %s: bad URL: %v", t.Name, err)
+		return ServiceHealth{Status: "down"}
+	}
+	resp, err := client.Do(req)
+	elapsed := time.Since(start).Milliseconds()
+	if err != nil {
+		log.Printf("[HealthDashboard] %s unreachable: %v", t.Name, err)
+		return ServiceHealth{Status: "down", ResponseTimeMs: elapsed}
+	}
+	io.Copy(io.Discard, resp.Body)
+	resp.Body.Close()
+
+	status := "healthy"
+	if resp.StatusCode != http.StatusOK {
+		status = "degraded"
+	}
+	return ServiceHealth{Status: status, HTTPStatus: resp.StatusCode, ResponseTimeMs: elapsed}
+}
+
+// healthDashboard returns every service's health in one response, for ops
+// rather than probes: unlike /health it always answers 200 and reports the
+// overall state in the body. Results are cached in Redis for
+// HealthDashboardCacheTTL whatever their status, so a dashboard refreshing
+// every few seconds doesn't multiply probe traffic.
+func (s *Server) healthDashboard(c *fiber.Ctx) error {
+	if val, err := Rdb.Get(context.Background(), HealthDashboardCacheKey).Bytes(); err == nil {
+		c.Set("X-Cache", "HIT")
+		c.Set("Content-Type", "application/json")
+		return c.Send(val)
+	}
+
+	result, err, _ := healthDashboardGroup.Do("dashboard", func() (interface{}, error) {
+		ctx, cancel := context.WithTimeout(context.Background(), HealthCheckTimeout)
+		defer cancel()
+		res := probeHealthTargets(ctx, &http.Client{Timeout: HealthCheckTimeout}, healthDashboardTargets())
+		data, err := json.Marshal(res)
+		if err != nil {
+			return nil, err
+		}
+		Rdb.Set(context.Background(), HealthDashboardCacheKey, data, HealthDashboardCacheTTL)
+		return data, nil
+	})
+	if err != nil {
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{Error: "health dashboard failed"})
+	}
+
+	c.Set("X-Cache", "MISS")
+	c.Set("Content-Type", "application/json")
+	return c.Send(result.([]byte))
+}
@@ -0,0 +1,65 @@
+package core
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+)
+
+func TestParseHealthTargets(t *testing.T) {
+	got := parseHealthTargets(" finance-service=http://finance-service:3001/health , bad-entry, =http://x, rss-service = rss-service:3004/health/ ,")
+	want := []HealthTarget{
+		{Name: "finance-service", URL: "http://finance-service:3001/health"},
+		{Name: "rss-service", URL: "https://rss-service:3004/health"},
+	}
+	if len(got) != len(want) {
+		t.Fatalf("parseHealthTargets = %+v; want %+v", got, want)
+	}
+	for i := range want {
+		if got[i] != want[i] {
+			t.Errorf("target %d = %+v; want %+v", i, got[i], want[i])
+		}
+	}
+	if got := parseHealthTargets(""); len(got) != 0 {
+		t.Errorf("parseHealthTargets(\"\") = %+v; want none", got)
+	}
+}
+
+func TestProbeHealthTargets(t *testing.T) {
+	ok := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.Write([]byte(`{"status":"healthy"}`))
+	}))
+	defer ok.Close()
+	sick := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.WriteHeader(http.StatusServiceUnavailable)
+	}))
+	defer sick.Close()
+	gone := httptest.NewServer(http.HandlerFunc(func(http.ResponseWriter, *http.Request) {}))
+	gone.Close()
+
+	client := &http.Client{Timeout: time.Second}
+	res := probeHealthTargets(context.Background(), client, []HealthTarget{{Name: "ok", URL: ok.URL}})
+	if res.Status != "healthy" || res.Services["ok"].Status != "healthy" || res.Services["ok"].HTTPStatus != 200 {
+		t.Fatalf("all-healthy dashboard = %+v", res)
+	}
+
+	res = probeHealthTargets(context.Background(), client, []HealthTarget{
+		{Name: "ok", URL: ok.URL},
+		{Name: "sick", URL: sick.URL},
+		{Name: "gone", URL: gone.URL},
+	})
+	if res.Status != "degraded" {
+		t.Errorf("overall status = %q; want degraded", res.Status)
+	}
+	if got := res.Services["sick"]; got.Status != "degraded" || got.HTTPStatus != http.StatusServiceUnavailable {
+		t.Errorf("sick = %+v; want degraded/503", got)
+	}
+	if got := res.Services["gone"]; got.Status != "down" || got.HTTPStatus != 0 {
+		t.Errorf("gone = %+v; want down with no HTTP status", got)
+	}
+	if len(res.Services) != 3 {
+		t.Errorf("got %d services; want 3", len(res.Services))
+	}
+}
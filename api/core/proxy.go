@@ -7,6 +7,7 @@ import (
 	"io"
 	"log"
 	"net/http"
+	"net/url"
 	"strings"
 	"time"
 
@@ -105,6 +106,34 @@ func stripAuthCookies(cookieHeader string) string {
 	return strings.Join(kept, "; ")
 }
 
+// stripAuthQuery removes access_token and refresh_token parameters from a
+// raw query string, leaving the others byte-for-byte as sent. It is the
+// query-string counterpart of stripAuthCookies: with
+// AUTH_QUERY_TOKEN_ENABLED the user's JWT may arrive in the URL, and it
+// must not reach channel services or their access logs.
+func stripAuthQuery(rawQuery string) string {
+	parts := strings.Split(rawQuery, "&")
+	kept := make([]string, 0, len(parts))
+	for _, p := range parts {
+		if p == "" {
+			continue
+		}
+		name := p
+		if eq := strings.IndexByte(p, '='); eq >= 0 {
+			name = p[:eq]
+		}
+		if decoded, err := url.QueryUnescape(name); err == nil {
+			name = decoded
+		}
+		switch strings.ToLower(name) {
+		case "access_token", "refresh_token":
+			continue
+		}
+		kept = append(kept, p)
+	}
+	return strings.Join(kept, "&")
+}
+
 // matchRoute matches a Fiber-style route pattern (e.g. "/yahoo/league/:league_key/standings")
 // against an actual request path. Returns extracted params and whether it matched.
 func matchRoute(pattern, path string) (map[string]string, bool) {
@@ -133,8 +162,8 @@ func matchRoute(pattern, path string) (map[string]string, bool) {
 func proxyRequest(c *fiber.Ctx, intg *ChannelInfo, route ChannelRoute, targetPath string) error {
 	targetURL := intg.InternalURL + targetPath
 
-	// Forward query string
-	if queryString := string(c.Request().URI().QueryString()); queryString != "" {
+	// Forward query string, minus any credentials (see stripAuthQuery)
+	if queryString := stripAuthQuery(string(c.Request().URI().QueryString())); queryString != "" {
 		targetURL += "?" + queryString
 	}
 
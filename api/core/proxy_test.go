@@ -0,0 +1,28 @@
+package core
+
+import "testing"
+
+func TestStripAuthQuery(t *testing.T) {
+	tests := []struct {
+		name string
+		raw  string
+		want string
+	}{
+		{"empty", "", ""},
+		{"no credentials", "league=nfl&limit=10", "league=nfl&limit=10"},
+		{"access token dropped", "access_token=eyJ.abc&league=nfl", "league=nfl"},
+		{"refresh token dropped", "league=nfl&refresh_token=r1", "league=nfl"},
+		{"only credentials", "access_token=a&refresh_token=b", ""},
+		{"case insensitive", "Access_Token=a&x=1", "x=1"},
+		{"percent-encoded name", "access%5Ftoken=a&x=1", "x=1"},
+		{"other values untouched", "q=a%20b&token_hint=1", "q=a%20b&token_hint=1"},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			if got := stripAuthQuery(tc.raw); got != tc.want {
+				t.Errorf("stripAuthQuery(%q) = %q, want %q", tc.raw, got, tc.want)
+			}
+		})
+	}
+}
@@ -23,9 +23,10 @@ import (
 // singleflight groups prevent thundering herd on cache misses.
 // Multiple concurrent requests for the same key coalesce into one.
 var (
-	dashboardGroup   singleflight.Group
-	publicFeedGroup  singleflight.Group
-	healthCheckGroup singleflight.Group
+	dashboardGroup       singleflight.Group
+	publicFeedGroup      singleflight.Group
+	healthCheckGroup     singleflight.Group
+	healthDashboardGroup singleflight.Group
 )
 
 // Server holds the Fiber app and shared dependencies.
@@ -115,6 +116,7 @@ func (s *Server) setupMiddleware() {
 	// Core paths always exempt from rate limiting
 	coreExemptPaths := map[string]bool{
 		"/health":                           true,
+		"/health/dashboard":                 true,
 		"/events":                           true,
 		"/webhooks/sequin":                  true,
 		"/webhooks/stripe":                  true,
@@ -176,6 +178,7 @@ func (s *Server) setupRoutes() {
 
 	// --- Public Routes ---
 	s.App.Get("/health", s.healthCheck)
+	s.App.Get("/health/dashboard", s.healthDashboard)
 	s.App.Get("/public/feed", HandlePublicFeed)
 	s.App.Get("/events", StreamEvents)
 	s.App.Get("/events/count", GetActiveViewers)
@@ -0,0 +1,65 @@
+package main
+
+import (
+	"crypto/rand"
+	"encoding/hex"
+	"log"
+	"time"
+
+	"github.com/gofiber/fiber/v2"
+)
+
+// =============================================================================
+// Access log
+//
+// One line per request: method, path, status, latency and a correlation id.
+// The id is taken from X-Request-Id when the core gateway forwards one,
+// otherwise generated here, and is echoed back on the response so a failing
+// Yahoo OAuth round-trip can be followed across /yahoo/start, /yahoo/callback
+// and the handlers' own log lines.
+//
+// Only the path is logged — never the query string (the OAuth callback
+// carries `code` and `state`) or any header, so tokens can't leak here.
+// =============================================================================
+
+const requestIDHeader = "X-Request-Id"
+
+// accessLogMiddleware logs every request after its handler has run.
+func accessLogMiddleware() fiber.Handler {
+	return func(c *fiber.Ctx) error {
+		started := time.Now()
+
+		rid := c.Get(requestIDHeader)
+		if rid == "" || len(rid) > 64 {
+			rid = newRequestID()
+		}
+		c.Locals("request_id", rid)
+		c.Set(requestIDHeader, rid)
+
+		err := c.Next()
+
+		// An error returned by the handler is turned into a response by
+		// Fiber's error handler after this middleware returns, so derive
+		// the status the client will actually see.
+		status := c.Response().StatusCode()
+		if err != nil {
+			status = fiber.StatusInternalServerError
+			if fe, ok := err.(*fiber.Error); ok {
+				status = fe.Code
+			}
+		}
+
+		log.Printf("[HTTP] rid=%s %s %s %d %v",
+			rid, c.Method(), c.Path(), status, time.Since(started).Round(time.Microsecond))
+		return err
+	}
+}
+
+// newRequestID returns a random 16-hex-char correlation id.
+func newRequestID() string {
+	b := make([]byte, 8)
+	if _, err := rand.Read(b); err != nil {
+		return "unknown"
+	}
+	return hex.EncodeToString(b)
+}
@@ -0,0 +1,86 @@
+package main
+
+import (
+	"bytes"
+	"log"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/gofiber/fiber/v2"
+)
+
+func captureLog(t *testing.T) *bytes.Buffer {
+	t.Helper()
+	var buf bytes.Buffer
+	prevOut, prevFlags := log.Writer(), log.Flags()
+	log.SetOutput(&buf)
+	log.SetFlags(0)
+	t.Cleanup(func() {
+		log.SetOutput(prevOut)
+		log.SetFlags(prevFlags)
+	})
+	return &buf
+}
+
+func newAccessLogApp() *fiber.App {
+	app := fiber.New()
+	app.Use(accessLogMiddleware())
+	app.Get("/yahoo/callback", func(c *fiber.Ctx) error {
+		return c.SendStatus(fiber.StatusFound)
+	})
+	app.Get("/boom", func(c *fiber.Ctx) error {
+		return fiber.NewError(fiber.StatusBadGateway, "upstream")
+	})
+	return app
+}
+
+// The access log must never contain the OAuth callback's query string or
+// the bearer token, but must carry method, path, status and request id.
+func TestAccessLogRedactsQueryAndHeaders(t *testing.T) {
+	buf := captureLog(t)
+	app := newAccessLogApp()
+
+	req := httptest.NewRequest("GET", "/yahoo/callback?code=secret-code&state=csrf123", nil)
+	req.Header.Set("Authorization", "Bearer leaking-token")
+	resp, err := app.Test(req)
+	if err != nil {
+		t.Fatalf("app.Test: %v", err)
+	}
+
+	rid := resp.Header.Get(requestIDHeader)
+	if len(rid) != 16 {
+		t.Fatalf("generated %s = %q; want 16 hex chars", requestIDHeader, rid)
+	}
+
+	line := buf.String()
+	for _, want := range []string{"rid=" + rid, "GET /yahoo/callback 302"} {
+		if !strings.Contains(line, want) {
+			t.Errorf("log %q missing %q", line, want)
+		}
+	}
+	for _, leak := range []string{"secret-code", "csrf123", "leaking-token"} {
+		if strings.Contains(line, leak) {
+			t.Errorf("log %q leaks %q", line, leak)
+		}
+	}
+}
+
+func TestAccessLogKeepsForwardedRequestID(t *testing.T) {
+	buf := captureLog(t)
+	app := newAccessLogApp()
+
+	req := httptest.NewRequest("GET", "/boom", nil)
+	req.Header.Set(requestIDHeader, "gateway-req-1")
+	resp, err := app.Test(req)
+	if err != nil {
+		t.Fatalf("app.Test: %v", err)
+	}
+
+	if got := resp.Header.Get(requestIDHeader); got != "gateway-req-1" {
+		t.Errorf("%s = %q; want the forwarded id", requestIDHeader, got)
+	}
+	if line := buf.String(); !strings.Contains(line, "rid=gateway-req-1 GET /boom 502") {
+		t.Errorf("log %q; want forwarded id and the handler's error status", line)
+	}
+}
@@ -0,0 +1,97 @@
+package main
+
+import (
+	"context"
+	"fmt"
+	"log"
+	"os"
+	"strconv"
+	"time"
+
+	"github.com/jackc/pgx/v5/pgxpool"
+)
+
+const (
+	// defaultDBConnectRetries is how many times startup tries to reach
+	// Postgres before giving up, when DB_CONNECT_RETRIES is unset.
+	defaultDBConnectRetries = 5
+
+	// defaultDBConnectRetryDelay is the wait after the first failed
+	// attempt; it doubles after each further failure.
+	defaultDBConnectRetryDelay = 2 * time.Second
+
+	// maxDBConnectRetryDelay caps the doubling.
+	maxDBConnectRetryDelay = 30 * time.Second
+)
+
+// connectDB opens the pool and pings it, retrying with backoff so the
+// service waits out a database that is still starting instead of exiting
+// and crash-looping. Returns the last error once the attempts run out.
+func connectDB(ctx context.Context, cfg *pgxpool.Config) (*pgxpool.Pool, error) {
+	attempts := getDBConnectRetries()
+	baseDelay := getDBConnectRetryDelay()
+
+	var err error
+	for attempt := 1; ; attempt++ {
+		var pool *pgxpool.Pool
+		pool, err = pgxpool.NewWithConfig(ctx, cfg)
+		if err == nil {
+			if err = pool.Ping(ctx); err == nil {
+				return pool, nil
+			}
+			pool.Close()
+		}
+		if attempt == attempts {
+			return nil, fmt.Errorf("%d attempts: %w", attempts, err)
+		}
+
+		delay := dbConnectBackoff(baseDelay, attempt)
+		log.Printf("[Fantasy] PostgreSQL connection attempt %d/%d failed: %v; retrying in %s",
+			attempt, attempts, err, delay)
+		select {
+		case <-ctx.Done():
+			return nil, ctx.Err()
+		case <-time.After(delay):
+		}
+	}
+}
+
+// dbConnectBackoff is the wait after the given failed attempt (1-based):
+// base, doubled for each further attempt, capped at maxDBConnectRetryDelay.
+func dbConnectBackoff(base time.Duration, attempt int) time.Duration {
+	delay := base
+	for i := 1; i < attempt && delay < maxDBConnectRetryDelay; i++ {
+		delay *= 2
+	}
+	return min(delay, maxDBConnectRetryDelay)
+}
+
+// getDBConnectRetries reads DB_CONNECT_RETRIES, the number of connection
+// attempts made at startup.
+func getDBConnectRetries() int {
+	raw := os.Getenv("DB_CONNECT_RETRIES")
+	if raw == "" {
+		return defaultDBConnectRetries
+	}
+	v, err := strconv.Atoi(raw)
+	if err != nil || v <= 0 {
+		log.Printf("[Fantasy] DB_CONNECT_RETRIES=%q is invalid, defaulting to %d", raw, defaultDBConnectRetries)
+		return defaultDBConnectRetries
+	}
+	return v
+}
+
+// getDBConnectRetryDelay reads DB_CONNECT_RETRY_DELAY_SECS, the wait after
+// the first failed connection attempt.
+func getDBConnectRetryDelay() time.Duration {
+	raw := os.Getenv("DB_CONNECT_RETRY_DELAY_SECS")
+	if raw == "" {
+		return defaultDBConnectRetryDelay
+	}
+	v, err := strconv.Atoi(raw)
+	if err != nil || v <= 0 {
+		log.Printf("[Fantasy] DB_CONNECT_RETRY_DELAY_SECS=%q is invalid, defaulting to %s", raw, defaultDBConnectRetryDelay)
+		return defaultDBConnectRetryDelay
+	}
+	return time.Duration(v) * time.Second
+}
@@ -0,0 +1,44 @@
+package main
+
+import (
+	"testing"
+	"time"
+)
+
+func TestDBConnectBackoff(t *testing.T) {
+	want := []time.Duration{2 * time.Second, 4 * time.Second, 8 * time.Second, 16 * time.Second, 30 * time.Second, 30 * time.Second}
+	for i, w := range want {
+		if got := dbConnectBackoff(2*time.Second, i+1); got != w {
+			t.Errorf("dbConnectBackoff(2s, %d) = %s, want %s", i+1, got, w)
+		}
+	}
+}
+
+func TestGetDBConnectRetrySettings(t *testing.T) {
+	t.Setenv("DB_CONNECT_RETRIES", "")
+	t.Setenv("DB_CONNECT_RETRY_DELAY_SECS", "")
+	if got := getDBConnectRetries(); got != defaultDBConnectRetries {
+		t.Errorf("unset retries = %d, want %d", got, defaultDBConnectRetries)
+	}
+	if got := getDBConnectRetryDelay(); got != defaultDBConnectRetryDelay {
+		t.Errorf("unset delay = %s, want %s", got, defaultDBConnectRetryDelay)
+	}
+
+	t.Setenv("DB_CONNECT_RETRIES", "20")
+	t.Setenv("DB_CONNECT_RETRY_DELAY_SECS", "5")
+	if got := getDBConnectRetries(); got != 20 {
+		t.Errorf("retries = %d, want 20", got)
+	}
+	if got := getDBConnectRetryDelay(); got != 5*time.Second {
+		t.Errorf("delay = %s, want 5s", got)
+	}
+
+	t.Setenv("DB_CONNECT_RETRIES", "0")
+	t.Setenv("DB_CONNECT_RETRY_DELAY_SECS", "soon")
+	if got := getDBConnectRetries(); got != defaultDBConnectRetries {
+		t.Errorf("invalid retries = %d, want the default", got)
+	}
+	if got := getDBConnectRetryDelay(); got != defaultDBConnectRetryDelay {
+		t.Errorf("invalid delay = %s, want the default", got)
+	}
+}
@@ -5,7 +5,9 @@ import (
 	"encoding/json"
 	"fmt"
 	"log"
+	"net/url"
 	"os"
+	"strconv"
 	"strings"
 	"time"
 
@@ -24,9 +26,17 @@ const (
 	// Redis key prefix for CDC subscriber resolution — all tables route via league_key
 	RedisLeagueUsersPrefix = "fantasy:league_users:" // SET of logto_subs per league_key
 
-	// OAuth state management
-	RedisCSRFPrefix            = "csrf:"
-	RedisYahooStateLogtoPrefix = "yahoo_state_logto:"
+	// OAuth state management. csrfKey(state) holds the initiating logto_sub
+	// under OAUTH_CSRF_KEY_PREFIX (default DefaultCSRFKeyPrefix).
+	// legacyCSRFPrefix and yahoo_state_logto:{state} are only read for
+	// states issued before the namespace and the key merge respectively;
+	// both can be dropped once those have expired.
+	// yahoo_state_redirect:{state} is set only for the redirect flow.
+	DefaultCSRFKeyPrefix          = "myscrollr:csrf:"
+	legacyCSRFPrefix              = "csrf:"
+	RedisYahooStateLogtoPrefix    = "yahoo_state_logto:"
+	RedisYahooStateRedirectPrefix = "yahoo_state_redirect:"
+	legacyCSRFStateValue          = "1"
 
 	// Timeouts and expiries
 	YahooAPITimeout       = 10 * time.Second
@@ -49,6 +59,10 @@ type App struct {
 
 	// leagueFlight collapses concurrent cache-miss requests for the same user.
 	leagueFlight singleflight.Group
+
+	// yahooReadFlight collapses identical concurrent live Yahoo reads,
+	// keyed by endpoint, user and resource (see cachedYahooStandings).
+	yahooReadFlight singleflight.Group
 }
 
 // resolveFrontendURL returns the URL to use for postMessage targetOrigin.
@@ -74,6 +88,51 @@ func resolveFrontendURL() string {
 	return DefaultFrontendURL
 }
 
+// allowedAuthRedirect reports whether a /yahoo/start `redirect_uri` may
+// receive the end of the OAuth flow. Only exact matches against the
+// comma-separated YAHOO_AUTH_REDIRECT_ALLOWLIST pass, so the callback can't
+// be turned into an open redirect. An unset allowlist disables the
+// redirect flow entirely.
+func allowedAuthRedirect(raw string) bool {
+	u, err := url.Parse(raw)
+	if err != nil || u.Scheme == "" || u.Fragment != "" {
+		return false
+	}
+	for _, allowed := range strings.Split(os.Getenv("YAHOO_AUTH_REDIRECT_ALLOWLIST"), ",") {
+		if allowed = strings.TrimSpace(allowed); allowed != "" && allowed == raw {
+			return true
+		}
+	}
+	return false
+}
+
+// csrfKey is the Redis key binding an OAuth state to its logto_sub.
+// OAUTH_CSRF_KEY_PREFIX overrides the namespace when several deployments
+// share one Redis.
+func csrfKey(state string) string {
+	prefix := strings.TrimSpace(os.Getenv("OAUTH_CSRF_KEY_PREFIX"))
+	if prefix == "" {
+		prefix = DefaultCSRFKeyPrefix
+	}
+	return prefix + state
+}
+
+// oauthStateTTL is how long an issued OAuth state stays redeemable, read
+// from OAUTH_STATE_TTL_SECS. The redirect key shares it so both expire
+// together.
+func oauthStateTTL() time.Duration {
+	raw := os.Getenv("OAUTH_STATE_TTL_SECS")
+	if raw == "" {
+		return OAuthStateExpiry
+	}
+	v, err := strconv.Atoi(raw)
+	if err != nil || v <= 0 {
+		log.Printf("[OAuth] OAUTH_STATE_TTL_SECS=%q is invalid, defaulting to %s", raw, OAuthStateExpiry)
+		return OAuthStateExpiry
+	}
+	return time.Duration(v) * time.Second
+}
+
 // =============================================================================
 // Shared League Data Fetcher (used by both dashboard & user handlers)
 // =============================================================================
@@ -243,6 +302,47 @@ func (a *App) fetchLeagueBundleCached(ctx context.Context, guid string) ([]Leagu
 	return result.([]LeagueResponse), nil
 }
 
+// YahooReadCacheTTL is how long a live Yahoo read made on a user's behalf
+// is reused. Short: these endpoints exist to show fresher data than the
+// sync loop has stored.
+const YahooReadCacheTTL = 30 * time.Second
+const YahooStandingsCachePrefix = "fantasy:yahoo_standings:"
+
+// cachedYahooStandings fetches one league's standings live from Yahoo for
+// a user, sharing the result between identical concurrent requests. A hit
+// in Redis returns immediately; on a miss, the first caller makes the
+// Yahoo request and everyone else asking for the same user and league
+// waits on it, then the result is cached for YahooReadCacheTTL. Errors are
+// shared with the waiters but not cached. A nil rdb skips the cache.
+func (a *App) cachedYahooStandings(ctx context.Context, client *YahooClient, guid, leagueKey string) ([]map[string]any, error) {
+	cacheKey := YahooStandingsCachePrefix + guid + ":" + leagueKey
+	if a.rdb != nil {
+		if cached, err := a.rdb.Get(ctx, cacheKey).Bytes(); err == nil {
+			var standings []map[string]any
+			if json.Unmarshal(cached, &standings) == nil {
+				return standings, nil
+			}
+		}
+	}
+
+	result, err, _ := a.yahooReadFlight.Do("standings\x00"+guid+"\x00"+leagueKey, func() (any, error) {
+		standings, err := client.GetStandings(ctx, leagueKey)
+		if err != nil {
+			return nil, err
+		}
+		if a.rdb != nil {
+			if data, marshalErr := json.Marshal(standings); marshalErr == nil {
+				a.rdb.Set(ctx, cacheKey, data, YahooReadCacheTTL)
+			}
+		}
+		return standings, nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	return result.([]map[string]any), nil
+}
+
 // invalidateLeagueCache removes the cached league data for a user.
 // Called when CDC events arrive or after league import/disconnect.
 func (a *App) invalidateLeagueCache(ctx context.Context, guid string) {
@@ -314,18 +414,14 @@ func (a *App) handleInternalCDC(c *fiber.Ctx) error {
 
 	// Invalidate cached league bundles for all affected users (best-effort)
 	for _, sub := range users {
-		var guid string
-		if err := a.db.QueryRow(ctx,
-			"SELECT guid FROM yahoo_users WHERE logto_sub = $1", sub).Scan(&guid); err == nil {
-			a.invalidateLeagueCache(ctx, guid)
-		}
+		a.invalidateUserLeagueCaches(ctx, sub)
 	}
 
 	return c.JSON(fiber.Map{"users": users})
 }
 
-// handleInternalDashboard returns fantasy data for a user's dashboard.
-// Uses the shared fetchLeagueBundleCached to avoid query duplication.
+// handleInternalDashboard returns fantasy data for a user's dashboard,
+// merged across every linked Yahoo account (see fetchUserLeagues).
 // Query param: user={logto_sub}
 func (a *App) handleInternalDashboard(c *fiber.Ctx) error {
 	userSub := c.Query("user")
@@ -333,17 +429,12 @@ func (a *App) handleInternalDashboard(c *fiber.Ctx) error {
 		return c.JSON(fiber.Map{"fantasy": nil})
 	}
 
-	// Resolve logto_sub → guid
-	var guid string
-	err := a.db.QueryRow(context.Background(),
-		"SELECT guid FROM yahoo_users WHERE logto_sub = $1", userSub).Scan(&guid)
+	leagues, err := a.fetchUserLeagues(context.Background(), userSub)
 	if err != nil {
+		log.Printf("[Dashboard] fetchUserLeagues error: %v", err)
 		return c.JSON(fiber.Map{"fantasy": nil})
 	}
-
-	leagues, err := a.fetchLeagueBundleCached(context.Background(), guid)
-	if err != nil {
-		log.Printf("[Dashboard] fetchLeagueBundle error for guid=%s: %v", guid, err)
+	if leagues == nil {
 		return c.JSON(fiber.Map{"fantasy": nil})
 	}
 
@@ -360,5 +451,14 @@ func (a *App) healthHandler(c *fiber.Ctx) error {
 			health[k] = v
 		}
 	}
+	health["yahoo_api"] = yahooStats.snapshot()
 	return c.JSON(health)
 }
+
+// unmappedStatsHandler lists, per sport, the roster stat ids seen since the
+// last sync cycle started that the league's scoring modifiers didn't cover
+// (see unmappedStatIDs). Operator-facing, so it is not registered with the
+// core gateway.
+func (a *App) unmappedStatsHandler(c *fiber.Ctx) error {
+	return c.JSON(fiber.Map{"unmapped_stats": unmappedStats.snapshot()})
+}
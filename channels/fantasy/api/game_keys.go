@@ -6,6 +6,7 @@ import (
 	"fmt"
 	"log"
 	"strconv"
+	"strings"
 	"sync"
 )
 
@@ -73,6 +74,33 @@ func GameKey(gameCode string, season int) (int, error) {
 	return key, nil
 }
 
+// gameCodeForKey returns the sport code for the game key that prefixes a
+// Yahoo resource key (the "449" in "449.l.1.t.1"), checking the static table
+// and then the dynamic cache. "unknown" when neither has it.
+func gameCodeForKey(resourceKey string) string {
+	prefix, _, _ := strings.Cut(resourceKey, ".")
+	key, err := strconv.Atoi(prefix)
+	if err != nil {
+		return "unknown"
+	}
+	for code, seasons := range gameKeys {
+		for _, k := range seasons {
+			if k == key {
+				return code
+			}
+		}
+	}
+	dynamicGameKeyMu.RLock()
+	defer dynamicGameKeyMu.RUnlock()
+	for cacheKey, k := range dynamicGameKeyCache {
+		if k == key {
+			code, _, _ := strings.Cut(cacheKey, ":")
+			return code
+		}
+	}
+	return "unknown"
+}
+
 // =============================================================================
 // Dynamic game key resolution
 //
@@ -135,8 +163,8 @@ func ResolveGameKey(ctx context.Context, client *YahooClient, gameCode string, s
 	}
 
 	var resp gamesDiscoveryResponse
-	if err := xml.Unmarshal(xmlBody, &resp); err != nil {
-		return 0, fmt.Errorf("parse games discovery XML: %w", err)
+	if err := unmarshalYahooXML(xmlBody, "games discovery", &resp); err != nil {
+		return 0, err
 	}
 
 	for _, g := range resp.Games.Game {
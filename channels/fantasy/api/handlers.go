@@ -18,6 +18,7 @@ const InternalHealthTimeout = 3 * time.Second
 // =============================================================================
 
 // UpsertYahooUser inserts or updates a Yahoo user with an encrypted refresh token.
+// A fresh token re-enables a user the sync disabled for invalid_grant.
 func (a *App) UpsertYahooUser(guid, logtoSub, refreshToken string) error {
 	encryptedToken, err := Encrypt(refreshToken)
 	if err != nil {
@@ -29,7 +30,8 @@ func (a *App) UpsertYahooUser(guid, logtoSub, refreshToken string) error {
 		INSERT INTO yahoo_users (guid, logto_sub, refresh_token)
 		VALUES ($1, $2, $3)
 		ON CONFLICT (guid) DO UPDATE
-		SET logto_sub = EXCLUDED.logto_sub, refresh_token = EXCLUDED.refresh_token;
+		SET logto_sub = EXCLUDED.logto_sub, refresh_token = EXCLUDED.refresh_token,
+		    disabled_at = NULL, invalid_grant_count = 0;
 	`, guid, logtoSub, encryptedToken)
 
 	return err
@@ -125,7 +127,7 @@ func (a *App) handleInternalHealth(c *fiber.Ctx) error {
 	// is cheap and may clear a transient upstream error; if not, the
 	// sync will fail again and the pod will stay NotReady.
 	if a.syncState != nil && a.syncState.IsFailed() {
-		result["sync"] = "failed: exceeded max restarts"
+		result["sync"] = a.syncState.failureReason()
 		degraded = true
 	} else if a.syncState != nil {
 		result["sync"] = "running"
@@ -1,7 +1,10 @@
 package main
 
 import (
+	"errors"
+	"fmt"
 	"testing"
+	"time"
 )
 
 // TestSyncHealth_IsFailedFlagFollowsSetFailed is the specific invariant that
@@ -48,3 +51,146 @@ func TestSyncHealth_IsFailedConcurrent(t *testing.T) {
 	}
 	<-done
 }
+
+// TestSyncHealth_RecordParseError checks that only Yahoo parse failures land
+// in the health snapshot — generic errors (timeouts, 5xx) are log-only.
+func TestSyncHealth_RecordParseError(t *testing.T) {
+	sh := &syncHealth{status: "running"}
+
+	sh.recordParseError(errors.New("yahoo request: timeout"))
+	if _, ok := sh.snapshot()["last_error"]; ok {
+		t.Fatalf("non-parse error recorded in snapshot")
+	}
+
+	wrapped := fmt.Errorf("league 449.l.1: %w", &YahooParseError{
+		Resource: "standings", Snippet: "<html>", Err: errors.New("EOF"),
+	})
+	sh.recordParseError(wrapped)
+	got, ok := sh.snapshot()["last_error"].(string)
+	if !ok || got == "" {
+		t.Fatalf("parse error not recorded; snapshot = %v", sh.snapshot())
+	}
+
+	var nilHealth *syncHealth
+	nilHealth.recordParseError(wrapped) // must not panic
+}
+
+// TestSyncHealth_RecentErrors checks that every recorded error is kept, not
+// just the newest, and that the list stays bounded.
+func TestSyncHealth_RecentErrors(t *testing.T) {
+	sh := &syncHealth{status: "running"}
+	sh.setFetchFailed(1, errors.New("connection refused"))
+	sh.recordParseError(&YahooParseError{Resource: "standings", Snippet: "<html>", Err: errors.New("EOF")})
+
+	got, ok := sh.snapshot()["recent_errors"].([]syncError)
+	if !ok || len(got) != 2 {
+		t.Fatalf("recent_errors = %#v, want 2 entries", sh.snapshot()["recent_errors"])
+	}
+	if got[0].Message != "fetch user batch: connection refused" {
+		t.Errorf("oldest error = %q", got[0].Message)
+	}
+	if got[1].Message != sh.snapshot()["last_error"] {
+		t.Errorf("newest error %q does not match last_error", got[1].Message)
+	}
+
+	for i := 0; i < recentErrorsKept; i++ {
+		sh.setFetchFailed(1, fmt.Errorf("timeout %d", i))
+	}
+	got = sh.snapshot()["recent_errors"].([]syncError)
+	if len(got) != recentErrorsKept {
+		t.Fatalf("len(recent_errors) = %d, want %d", len(got), recentErrorsKept)
+	}
+	if got[0].Message != "fetch user batch: timeout 0" {
+		t.Errorf("oldest kept error = %q, want the first timeout", got[0].Message)
+	}
+}
+
+// TestSyncHealth_FetchFailuresEscalate checks that lost cycles only flip the
+// readiness flag once maxFetchFailures is reached, and that a good cycle
+// clears it again.
+func TestSyncHealth_FetchFailuresEscalate(t *testing.T) {
+	sh := &syncHealth{status: "running"}
+	dbErr := errors.New("connection refused")
+
+	for i := 1; i < maxFetchFailures; i++ {
+		sh.setFetchFailed(i, dbErr)
+		if sh.IsFailed() {
+			t.Fatalf("after %d failures: IsFailed() = true; want false", i)
+		}
+		if got := sh.snapshot()["sync_status"]; got != "degraded" {
+			t.Fatalf("after %d failures: sync_status = %v; want degraded", i, got)
+		}
+	}
+
+	sh.setFetchFailed(maxFetchFailures, dbErr)
+	if !sh.IsFailed() {
+		t.Fatalf("after %d failures: IsFailed() = false; want true", maxFetchFailures)
+	}
+	if got := sh.snapshot()["consecutive_fetch_failures"]; got != maxFetchFailures {
+		t.Fatalf("consecutive_fetch_failures = %v; want %d", got, maxFetchFailures)
+	}
+
+	sh.setRunning(10)
+	if sh.IsFailed() {
+		t.Fatalf("after setRunning: IsFailed() = true; want false")
+	}
+	if _, ok := sh.snapshot()["consecutive_fetch_failures"]; ok {
+		t.Fatalf("consecutive_fetch_failures still reported after recovery")
+	}
+}
+
+func TestSyncBackoff(t *testing.T) {
+	interval := 2 * time.Minute
+	cases := []struct {
+		failures int
+		want     time.Duration
+	}{
+		{0, 2 * time.Minute},
+		{1, 4 * time.Minute},
+		{3, 16 * time.Minute},
+		{4, maxSyncBackoff},
+		{50, maxSyncBackoff},
+	}
+	for _, tc := range cases {
+		if got := syncBackoff(interval, tc.failures); got != tc.want {
+			t.Errorf("syncBackoff(%v, %d) = %v; want %v", interval, tc.failures, got, tc.want)
+		}
+	}
+}
+
+// TestSyncHealth_RecordUserSync checks that a failing user is listed until
+// their next successful sync, and healthy users are counted but not listed.
+func TestSyncHealth_RecordUserSync(t *testing.T) {
+	sh := &syncHealth{status: "running"}
+
+	sh.recordUserSync("guid-ok", nil, 2*time.Second)
+	sh.recordUserSync("guid-bad", errors.New("token refresh failed: invalid_grant"), 300*time.Millisecond)
+
+	snap := sh.snapshot()
+	if got := snap["tracked_users"]; got != 2 {
+		t.Fatalf("tracked_users = %v; want 2", got)
+	}
+	failing, _ := snap["failing_users"].([]userSyncStatus)
+	if len(failing) != 1 || failing[0].GUID != "guid-bad" || failing[0].LastSync != nil {
+		t.Fatalf("failing_users = %+v; want only guid-bad with no last_sync", failing)
+	}
+
+	slowest, _ := snap["slowest_users"].([]userSyncStatus)
+	if len(slowest) != 2 || slowest[0].GUID != "guid-ok" || slowest[0].LastDurationMs != 2000 {
+		t.Fatalf("slowest_users = %+v; want guid-ok (2000ms) first", slowest)
+	}
+
+	sh.recordUserSync("guid-bad", nil, 5*time.Second)
+	snap = sh.snapshot()
+	failing, _ = snap["failing_users"].([]userSyncStatus)
+	if len(failing) != 0 {
+		t.Fatalf("failing_users after recovery = %+v; want empty", failing)
+	}
+	slowest, _ = snap["slowest_users"].([]userSyncStatus)
+	if slowest[0].GUID != "guid-bad" || slowest[0].LastDurationMs != 5000 {
+		t.Fatalf("slowest_users after a slow retry = %+v; want guid-bad (5000ms) first", slowest)
+	}
+
+	var nilHealth *syncHealth
+	nilHealth.recordUserSync("guid", nil, time.Second) // must not panic
+}
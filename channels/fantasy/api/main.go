@@ -100,15 +100,11 @@ func main() {
 	poolConfig.MinConns = 2
 	poolConfig.MaxConnLifetime = 30 * time.Minute
 
-	pool, err := pgxpool.NewWithConfig(context.Background(), poolConfig)
+	pool, err := connectDB(context.Background(), poolConfig)
 	if err != nil {
-		log.Fatalf("[Fantasy] Failed to connect to PostgreSQL: %v", err)
+		log.Fatalf("[Fantasy] Failed to connect to PostgreSQL after %v", err)
 	}
 	defer pool.Close()
-
-	if err := pool.Ping(context.Background()); err != nil {
-		log.Fatalf("[Fantasy] PostgreSQL ping failed: %v", err)
-	}
 	log.Printf("[Fantasy] Connected to PostgreSQL (pool: max=%d, min=%d)",
 		poolConfig.MaxConns, poolConfig.MinConns)
 
@@ -257,6 +253,7 @@ func main() {
 		fiberApp.Use(sentryMiddleware())
 		fiberApp.Use(sentryUserHook())
 	}
+	fiberApp.Use(accessLogMiddleware())
 
 	// Yahoo OAuth routes.
 	//   /yahoo/start    — Auth REQUIRED. Core gateway verifies the Scrollr
@@ -271,6 +268,7 @@ func main() {
 	fiberApp.Get("/yahoo/start", app.YahooStart)
 	fiberApp.Get("/yahoo/callback", app.YahooCallback)
 	fiberApp.Get("/yahoo/health", app.healthHandler)
+	fiberApp.Get("/yahoo/debug/unmapped-stats", app.unmappedStatsHandler)
 
 	// Protected routes (core gateway sets X-User-Sub header)
 	fiberApp.Get("/users/me/yahoo-status", app.GetYahooStatus)
@@ -279,6 +277,9 @@ func main() {
 	fiberApp.Post("/users/me/yahoo-leagues/discover", app.DiscoverYahooLeagues)
 	fiberApp.Post("/users/me/yahoo-leagues/import", app.ImportYahooLeague)
 	fiberApp.Delete("/users/me/yahoo", app.DisconnectYahoo)
+	fiberApp.Post("/yahoo/standings/batch", app.BatchYahooStandings)
+	fiberApp.Get("/yahoo/league/:key/settings", app.GetYahooLeagueSettings)
+	fiberApp.Get("/yahoo/league/:key/standings/history", app.GetYahooStandingsHistory)
 
 	// Internal routes (called by core gateway directly, not proxied)
 	fiberApp.Post("/internal/cdc", app.handleInternalCDC)
@@ -349,6 +350,8 @@ func startRegistration(ctx context.Context, rdb *redis.Client) {
 			{Method: "POST", Path: "/users/me/yahoo-leagues/discover", Auth: true},
 			{Method: "POST", Path: "/users/me/yahoo-leagues/import", Auth: true},
 			{Method: "DELETE", Path: "/users/me/yahoo", Auth: true},
+			{Method: "POST", Path: "/yahoo/standings/batch", Auth: true},
+			{Method: "GET", Path: "/yahoo/league/:key/settings", Auth: true},
 		},
 	}
 
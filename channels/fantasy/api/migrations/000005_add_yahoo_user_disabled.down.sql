@@ -0,0 +1,2 @@
+ALTER TABLE yahoo_users DROP COLUMN IF EXISTS invalid_grant_count;
+ALTER TABLE yahoo_users DROP COLUMN IF EXISTS disabled_at;
@@ -0,0 +1,5 @@
+-- Track consecutive invalid_grant refreshes so the sync can disable users
+-- whose Yahoo refresh token has been revoked. Disabled users are skipped
+-- until they reconnect Yahoo.
+ALTER TABLE yahoo_users ADD COLUMN IF NOT EXISTS disabled_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE yahoo_users ADD COLUMN IF NOT EXISTS invalid_grant_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_yahoo_users_logto_sub;
+-- Keep the most recently linked account per user before restoring UNIQUE.
+DELETE FROM yahoo_users a USING yahoo_users b
+WHERE a.logto_sub = b.logto_sub
+  AND a.logto_sub IS NOT NULL
+  AND (a.created_at, a.guid) < (b.created_at, b.guid);
+ALTER TABLE yahoo_users ADD CONSTRAINT yahoo_users_logto_sub_key UNIQUE (logto_sub);
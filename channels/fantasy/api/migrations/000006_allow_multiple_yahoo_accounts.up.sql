@@ -0,0 +1,5 @@
+-- A Scrollr user may link several Yahoo accounts (e.g. a personal and a work
+-- login). Each yahoo_users row is still one Yahoo guid with its own refresh
+-- token; logto_sub now just groups them, so it is indexed instead of unique.
+ALTER TABLE yahoo_users DROP CONSTRAINT IF EXISTS yahoo_users_logto_sub_key;
+CREATE INDEX IF NOT EXISTS idx_yahoo_users_logto_sub ON yahoo_users (logto_sub);
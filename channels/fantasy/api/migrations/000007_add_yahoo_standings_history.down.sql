@@ -0,0 +1 @@
+DROP TABLE IF EXISTS yahoo_standings_history;
@@ -0,0 +1,12 @@
+-- Snapshots of each league's standings over time. yahoo_standings only
+-- holds the latest copy; a row is appended here whenever a sync sees the
+-- standings change, and rows older than STANDINGS_HISTORY_DAYS are pruned.
+CREATE TABLE IF NOT EXISTS yahoo_standings_history (
+    league_key VARCHAR(50) NOT NULL REFERENCES yahoo_leagues(league_key) ON DELETE CASCADE,
+    captured_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    data JSONB NOT NULL,
+    PRIMARY KEY (league_key, captured_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_yahoo_standings_history_captured_at
+    ON yahoo_standings_history (captured_at);
@@ -1,7 +1,10 @@
 package main
 
-import "encoding/json"
-import "encoding/xml"
+import (
+	"encoding/json"
+	"encoding/xml"
+	"time"
+)
 
 // =============================================================================
 // Yahoo Fantasy XML Types — Parsed from Yahoo API responses
@@ -269,11 +272,26 @@ type XMLStat struct {
 	Value  string `xml:"value" json:"value"`
 }
 
-// XMLLeagueSettings mirrors <league_settings> from the league/settings endpoint.
-// We only need the stat_categories + stat_modifiers to compute synthetic points.
+// XMLLeagueSettings mirrors <settings> from the league/settings endpoint.
+// stat_categories + stat_modifiers feed synthetic points; the rest backs
+// GET /yahoo/league/:key/settings. Any element may be missing.
 type XMLLeagueSettings struct {
-	StatCategories XMLStatCategories `xml:"stat_categories" json:"stat_categories"`
-	StatModifiers  XMLStatModifiers  `xml:"stat_modifiers" json:"stat_modifiers"`
+	ScoringType      string             `xml:"scoring_type" json:"scoring_type"`
+	PlayoffStartWeek string             `xml:"playoff_start_week" json:"playoff_start_week"`
+	RosterPositions  XMLRosterPositions `xml:"roster_positions" json:"roster_positions"`
+	StatCategories   XMLStatCategories  `xml:"stat_categories" json:"stat_categories"`
+	StatModifiers    XMLStatModifiers   `xml:"stat_modifiers" json:"stat_modifiers"`
+}
+
+type XMLRosterPositions struct {
+	RosterPosition []XMLRosterPosition `xml:"roster_position" json:"roster_position"`
+}
+
+type XMLRosterPosition struct {
+	Position           string `xml:"position" json:"position"`
+	PositionType       string `xml:"position_type" json:"position_type"`
+	Count              string `xml:"count" json:"count"`
+	IsStartingPosition string `xml:"is_starting_position" json:"is_starting_position"`
 }
 
 type XMLStatCategories struct {
@@ -317,6 +335,28 @@ type LeagueStatCatalog struct {
 	Modifiers map[string]float64 `json:"modifiers"`
 }
 
+// RosterPositionSetting is one slot type in a league's roster, e.g.
+// {"position": "WR", "count": 3}.
+type RosterPositionSetting struct {
+	Position     string `json:"position"`
+	PositionType string `json:"position_type,omitempty"`
+	Count        int    `json:"count"`
+	IsStarting   bool   `json:"is_starting"`
+}
+
+// LeagueSettings is the structured subset of a league's settings returned
+// by GET /yahoo/league/:key/settings. NumTeams and PlayoffStartWeek are
+// nil when Yahoo omits them (e.g. leagues without playoffs).
+type LeagueSettings struct {
+	LeagueKey        string                  `json:"league_key"`
+	Name             string                  `json:"name"`
+	ScoringType      string                  `json:"scoring_type"`
+	NumTeams         *int                    `json:"num_teams"`
+	RosterPositions  []RosterPositionSetting `json:"roster_positions"`
+	StatCategories   []StatCatalogEntry      `json:"stat_categories"`
+	PlayoffStartWeek *int                    `json:"playoff_start_week"`
+}
+
 type XMLStatModifiers struct {
 	Stats XMLStatModifierList `xml:"stats" json:"stats"`
 }
@@ -336,8 +376,9 @@ type XMLStatModifier struct {
 
 // YahooStatusResponse returns whether user has Yahoo connected.
 type YahooStatusResponse struct {
-	Connected bool `json:"connected"`
-	Synced    bool `json:"synced"`
+	Connected bool     `json:"connected"`
+	Synced    bool     `json:"synced"`
+	Accounts  []string `json:"accounts"`
 }
 
 // LeagueResponse is a single league with all associated data.
@@ -360,6 +401,41 @@ type MyLeaguesResponse struct {
 	Leagues []LeagueResponse `json:"leagues"`
 }
 
+// BatchStandingsRequest is the body of POST /yahoo/standings/batch.
+type BatchStandingsRequest struct {
+	LeagueKeys []string `json:"league_keys"`
+	// GUID optionally picks the linked account used for leagues the user
+	// hasn't imported; the oldest link is used when empty.
+	GUID string `json:"guid"`
+}
+
+// BatchStandingsResult is one league's outcome in a batch standings
+// response: either Standings (null before the draft) or Error.
+type BatchStandingsResult struct {
+	Standings []map[string]any `json:"standings"`
+	Error     string           `json:"error,omitempty"`
+}
+
+// BatchStandingsResponse is the response for POST /yahoo/standings/batch,
+// keyed by league key.
+type BatchStandingsResponse struct {
+	Results map[string]BatchStandingsResult `json:"results"`
+}
+
+// StandingsSnapshot is one captured copy of a league's standings.
+type StandingsSnapshot struct {
+	CapturedAt time.Time       `json:"captured_at"`
+	Standings  json.RawMessage `json:"standings"`
+}
+
+// StandingsHistoryResponse is the response for
+// GET /yahoo/league/:key/standings/history, oldest snapshot first.
+type StandingsHistoryResponse struct {
+	LeagueKey     string              `json:"league_key"`
+	RetentionDays int                 `json:"retention_days"`
+	Snapshots     []StandingsSnapshot `json:"snapshots"`
+}
+
 // CDCRecord represents a Change Data Capture record from Sequin.
 type CDCRecord struct {
 	Action   string                 `json:"action"`
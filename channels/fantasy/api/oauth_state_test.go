@@ -0,0 +1,209 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"net/http/httptest"
+	"net/url"
+	"os"
+	"sync"
+	"testing"
+	"time"
+
+	"github.com/gofiber/fiber/v2"
+	"github.com/redis/go-redis/v9"
+	"golang.org/x/oauth2"
+)
+
+// testRedis connects to REDIS_URL, skipping the test when it is unset or
+// unreachable so plain `go test` runs without a Redis instance.
+func testRedis(t *testing.T) *redis.Client {
+	t.Helper()
+	url := os.Getenv("REDIS_URL")
+	if url == "" {
+		t.Skip("REDIS_URL not set; skipping Redis-backed test")
+	}
+	opts, err := redis.ParseURL(url)
+	if err != nil {
+		t.Fatalf("parse REDIS_URL: %v", err)
+	}
+	rdb := redis.NewClient(opts)
+	if err := rdb.Ping(context.Background()).Err(); err != nil {
+		t.Skipf("Redis unreachable: %v", err)
+	}
+	t.Cleanup(func() { rdb.Close() })
+	return rdb
+}
+
+// TestConsumeOAuthState_SingleUseUnderRace fires concurrent callbacks with
+// the same state; exactly one may win, and it must get the bound logto_sub.
+func TestConsumeOAuthState_SingleUseUnderRace(t *testing.T) {
+	rdb := testRedis(t)
+	ctx := context.Background()
+	state := "test-race-state"
+	if err := rdb.Set(ctx, csrfKey(state), "user-a", OAuthStateExpiry).Err(); err != nil {
+		t.Fatalf("seed state: %v", err)
+	}
+
+	const callers = 16
+	var wg sync.WaitGroup
+	results := make(chan string, callers)
+	for i := 0; i < callers; i++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			if sub, ok := consumeOAuthState(ctx, rdb, state); ok {
+				results <- sub
+			}
+		}()
+	}
+	wg.Wait()
+	close(results)
+
+	var winners []string
+	for sub := range results {
+		winners = append(winners, sub)
+	}
+	if len(winners) != 1 {
+		t.Fatalf("%d callbacks consumed the state; want exactly 1", len(winners))
+	}
+	if winners[0] != "user-a" {
+		t.Errorf("winner got logto_sub %q; want %q", winners[0], "user-a")
+	}
+}
+
+func TestConsumeOAuthState_LegacyTwoKeyState(t *testing.T) {
+	rdb := testRedis(t)
+	ctx := context.Background()
+	state := "test-legacy-state"
+	rdb.Set(ctx, legacyCSRFPrefix+state, legacyCSRFStateValue, OAuthStateExpiry)
+	rdb.Set(ctx, RedisYahooStateLogtoPrefix+state, "user-b", OAuthStateExpiry)
+
+	sub, ok := consumeOAuthState(ctx, rdb, state)
+	if !ok || sub != "user-b" {
+		t.Fatalf("consumeOAuthState = (%q, %v); want (%q, true)", sub, ok, "user-b")
+	}
+	if _, ok := consumeOAuthState(ctx, rdb, state); ok {
+		t.Error("legacy state was accepted twice")
+	}
+}
+
+func TestConsumeOAuthState_UnknownState(t *testing.T) {
+	rdb := testRedis(t)
+	if _, ok := consumeOAuthState(context.Background(), rdb, "test-never-issued"); ok {
+		t.Error("unknown state was accepted")
+	}
+}
+
+// TestYahooStart_NamespacedStateKey checks that YahooStart writes the state
+// under OAUTH_CSRF_KEY_PREFIX with OAUTH_STATE_TTL_SECS, and that the
+// callback side burns that same key.
+func TestYahooStart_NamespacedStateKey(t *testing.T) {
+	rdb := testRedis(t)
+	ctx := context.Background()
+	t.Setenv("OAUTH_CSRF_KEY_PREFIX", "test-ns:csrf:")
+	t.Setenv("OAUTH_STATE_TTL_SECS", "120")
+
+	a := &App{rdb: rdb, yahooConfig: &oauth2.Config{
+		ClientID: "test-client",
+		Endpoint: oauth2.Endpoint{AuthURL: "https://yahoo.example/auth"},
+	}}
+	app := fiber.New()
+	app.Get("/yahoo/start", a.YahooStart)
+
+	req := httptest.NewRequest("GET", "/yahoo/start?response=json", nil)
+	req.Header.Set("X-User-Sub", "user-ns")
+	resp, err := app.Test(req)
+	if err != nil {
+		t.Fatalf("YahooStart: %v", err)
+	}
+	defer resp.Body.Close()
+	var body struct {
+		RedirectURL string `json:"redirect_url"`
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&body); err != nil {
+		t.Fatalf("decode response: %v", err)
+	}
+	consent, err := url.Parse(body.RedirectURL)
+	if err != nil {
+		t.Fatalf("parse redirect_url %q: %v", body.RedirectURL, err)
+	}
+	state := consent.Query().Get("state")
+	key := "test-ns:csrf:" + state
+	t.Cleanup(func() { rdb.Del(ctx, key) })
+
+	if got, err := rdb.Get(ctx, key).Result(); err != nil || got != "user-ns" {
+		t.Fatalf("GET %s = (%q, %v); want the initiating logto_sub", key, got, err)
+	}
+	if ttl := rdb.TTL(ctx, key).Val(); ttl <= 0 || ttl > 120*time.Second {
+		t.Errorf("TTL on %s = %s; want at most 120s", key, ttl)
+	}
+	if n := rdb.Exists(ctx, legacyCSRFPrefix+state).Val(); n != 0 {
+		t.Errorf("state also written under the unprefixed key")
+	}
+
+	if sub, ok := consumeOAuthState(ctx, rdb, state); !ok || sub != "user-ns" {
+		t.Fatalf("consumeOAuthState = (%q, %v); want (%q, true)", sub, ok, "user-ns")
+	}
+	if n := rdb.Exists(ctx, key).Val(); n != 0 {
+		t.Errorf("%s still exists after the callback consumed it", key)
+	}
+}
+
+func TestCSRFKey(t *testing.T) {
+	t.Setenv("OAUTH_CSRF_KEY_PREFIX", "")
+	if got := csrfKey("abc"); got != "myscrollr:csrf:abc" {
+		t.Errorf("csrfKey with no override = %q", got)
+	}
+	t.Setenv("OAUTH_CSRF_KEY_PREFIX", "staging:csrf:")
+	if got := csrfKey("abc"); got != "staging:csrf:abc" {
+		t.Errorf("csrfKey with override = %q", got)
+	}
+}
+
+func TestAllowedAuthRedirect(t *testing.T) {
+	t.Setenv("YAHOO_AUTH_REDIRECT_ALLOWLIST", "myscrollr://auth/yahoo, https://myscrollr.com/yahoo/done")
+
+	tests := []struct {
+		uri  string
+		want bool
+	}{
+		{"myscrollr://auth/yahoo", true},
+		{"https://myscrollr.com/yahoo/done", true},
+		{"https://myscrollr.com/yahoo/done/", false},
+		{"https://myscrollr.com/yahoo/done?next=https://evil.example", false},
+		{"https://myscrollr.com/yahoo/done#x", false},
+		{"https://evil.example/yahoo/done", false},
+		{"//myscrollr.com/yahoo/done", false},
+		{"", false},
+	}
+	for _, tt := range tests {
+		if got := allowedAuthRedirect(tt.uri); got != tt.want {
+			t.Errorf("allowedAuthRedirect(%q) = %v; want %v", tt.uri, got, tt.want)
+		}
+	}
+
+	t.Setenv("YAHOO_AUTH_REDIRECT_ALLOWLIST", "")
+	if allowedAuthRedirect("myscrollr://auth/yahoo") {
+		t.Error("redirect allowed with an empty allowlist")
+	}
+}
+
+func TestConsumeAuthRedirect(t *testing.T) {
+	rdb := testRedis(t)
+	ctx := context.Background()
+	t.Setenv("YAHOO_AUTH_REDIRECT_ALLOWLIST", "myscrollr://auth/yahoo")
+
+	rdb.Set(ctx, RedisYahooStateRedirectPrefix+"test-redirect", "myscrollr://auth/yahoo", OAuthStateExpiry)
+	if got := consumeAuthRedirect(ctx, rdb, "test-redirect"); got != "myscrollr://auth/yahoo" {
+		t.Fatalf("consumeAuthRedirect = %q; want the stored URI", got)
+	}
+	if got := consumeAuthRedirect(ctx, rdb, "test-redirect"); got != "" {
+		t.Errorf("redirect returned twice: %q", got)
+	}
+
+	rdb.Set(ctx, RedisYahooStateRedirectPrefix+"test-revoked", "https://evil.example", OAuthStateExpiry)
+	if got := consumeAuthRedirect(ctx, rdb, "test-revoked"); got != "" {
+		t.Errorf("consumeAuthRedirect = %q for a URI off the allowlist; want empty", got)
+	}
+}
@@ -0,0 +1,139 @@
+package main
+
+import (
+	"context"
+	"log"
+	"os"
+	"strconv"
+	"strings"
+
+	"github.com/gofiber/fiber/v2"
+)
+
+// defaultStandingsHistoryDays is how long standings snapshots are kept when
+// STANDINGS_HISTORY_DAYS is unset — long enough to cover a full season.
+const defaultStandingsHistoryDays = 365
+
+// appendStandingsSnapshot records data as the league's newest snapshot,
+// unless it is identical to the current newest one — most syncs see no
+// change, and repeating them would only bloat the table.
+func (a *App) appendStandingsSnapshot(ctx context.Context, leagueKey, data string) error {
+	_, err := a.db.Exec(ctx,
+		`INSERT INTO yahoo_standings_history (league_key, captured_at, data)
+		 SELECT $1, CURRENT_TIMESTAMP, $2::jsonb
+		 WHERE $2::jsonb IS DISTINCT FROM (
+		     SELECT data FROM yahoo_standings_history
+		     WHERE league_key = $1
+		     ORDER BY captured_at DESC
+		     LIMIT 1
+		 )
+		 ON CONFLICT (league_key, captured_at) DO NOTHING`,
+		leagueKey, data,
+	)
+	return err
+}
+
+// pruneStandingsHistory deletes snapshots older than the retention window.
+// Called once per sync cycle; failures are logged and retried next cycle.
+func (a *App) pruneStandingsHistory(ctx context.Context) {
+	tag, err := a.db.Exec(ctx,
+		`DELETE FROM yahoo_standings_history
+		 WHERE captured_at < CURRENT_TIMESTAMP - make_interval(days => $1)`,
+		getStandingsHistoryDays(),
+	)
+	if err != nil {
+		log.Printf("[Sync] Failed to prune standings history: %v", err)
+		return
+	}
+	if n := tag.RowsAffected(); n > 0 {
+		log.Printf("[Sync] Pruned %d standings snapshots", n)
+	}
+}
+
+// getStandingsHistoryDays reads STANDINGS_HISTORY_DAYS, the number of days
+// of standings snapshots to keep.
+func getStandingsHistoryDays() int {
+	raw := os.Getenv("STANDINGS_HISTORY_DAYS")
+	if raw == "" {
+		return defaultStandingsHistoryDays
+	}
+	v, err := strconv.Atoi(raw)
+	if err != nil || v <= 0 {
+		log.Printf("[Sync] STANDINGS_HISTORY_DAYS=%q is invalid, defaulting to %d", raw, defaultStandingsHistoryDays)
+		return defaultStandingsHistoryDays
+	}
+	return v
+}
+
+// GetYahooStandingsHistory returns the stored standings snapshots for a
+// league the caller has imported under one of their linked accounts.
+func (a *App) GetYahooStandingsHistory(c *fiber.Ctx) error {
+	userID := GetUserSub(c)
+	if userID == "" {
+		return c.Status(fiber.StatusUnauthorized).JSON(ErrorResponse{
+			Status: "unauthorized",
+			Error:  "Authentication required",
+		})
+	}
+
+	leagueKey := strings.TrimSpace(c.Params("key"))
+	if leagueKey == "" {
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "league key is required",
+		})
+	}
+
+	ctx := context.Background()
+	owners, err := a.leagueOwners(ctx, userID, []string{leagueKey})
+	if err != nil {
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to read imported leagues",
+		})
+	}
+	if owners[leagueKey] == "" {
+		return c.Status(fiber.StatusNotFound).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "League not imported",
+		})
+	}
+
+	rows, err := a.db.Query(ctx,
+		`SELECT captured_at, data
+		 FROM yahoo_standings_history
+		 WHERE league_key = $1
+		 ORDER BY captured_at`,
+		leagueKey,
+	)
+	if err != nil {
+		log.Printf("[StandingsHistory] Query failed for %s: %v", leagueKey, err)
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to read standings history",
+		})
+	}
+	defer rows.Close()
+
+	snapshots := make([]StandingsSnapshot, 0)
+	for rows.Next() {
+		var snap StandingsSnapshot
+		if err := rows.Scan(&snap.CapturedAt, &snap.Standings); err != nil {
+			log.Printf("[StandingsHistory] Scan failed for %s: %v", leagueKey, err)
+			return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+				Status: "error", Error: "Failed to read standings history",
+			})
+		}
+		snapshots = append(snapshots, snap)
+	}
+	if err := rows.Err(); err != nil {
+		log.Printf("[StandingsHistory] Rows failed for %s: %v", leagueKey, err)
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to read standings history",
+		})
+	}
+
+	return c.JSON(StandingsHistoryResponse{
+		LeagueKey:     leagueKey,
+		RetentionDays: getStandingsHistoryDays(),
+		Snapshots:     snapshots,
+	})
+}
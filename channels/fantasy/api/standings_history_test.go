@@ -0,0 +1,22 @@
+package main
+
+import "testing"
+
+func TestGetStandingsHistoryDays(t *testing.T) {
+	cases := []struct {
+		raw  string
+		want int
+	}{
+		{"", defaultStandingsHistoryDays},
+		{"90", 90},
+		{"0", defaultStandingsHistoryDays},
+		{"-5", defaultStandingsHistoryDays},
+		{"a season", defaultStandingsHistoryDays},
+	}
+	for _, tc := range cases {
+		t.Setenv("STANDINGS_HISTORY_DAYS", tc.raw)
+		if got := getStandingsHistoryDays(); got != tc.want {
+			t.Errorf("STANDINGS_HISTORY_DAYS=%q: got %d, want %d", tc.raw, got, tc.want)
+		}
+	}
+}
@@ -3,10 +3,13 @@ package main
 import (
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
 	"log"
 	"os"
+	"sort"
 	"strconv"
+	"strings"
 	"sync"
 	"sync/atomic"
 	"time"
@@ -28,6 +31,27 @@ const (
 	syncRestartDelay       = 10 * time.Second
 )
 
+// disableAfterInvalidGrants is how many consecutive cycles a user's refresh
+// token must be rejected with invalid_grant before the user is disabled.
+// One rejection is not enough to stop syncing someone.
+const disableAfterInvalidGrants = 3
+
+// defaultLeagueSyncConcurrency is the per-user league fan-out. apiDelay
+// spaces out calls within one goroutine, not across the client, so N leagues
+// in parallel send roughly N times the requests per second for that user.
+// It also multiplies with SYNC_CONCURRENCY, so keep it small to stay well
+// inside Yahoo's per-app rate limit.
+const defaultLeagueSyncConcurrency = 3
+
+// When the user batch query itself fails (Postgres down, pool exhausted)
+// the whole cycle is lost. After maxFetchFailures in a row the sync is
+// reported unhealthy, and the loop interval doubles per failure up to
+// maxSyncBackoff so a dead database isn't hammered every two minutes.
+const (
+	maxFetchFailures = 3
+	maxSyncBackoff   = 30 * time.Minute
+)
+
 // syncHealth tracks the state of the sync loop for health reporting.
 //
 // `failed` is a dedicated atomic flag so the hot path in /internal/health
@@ -40,18 +64,80 @@ type syncHealth struct {
 	lastCycleTime  time.Time
 	lastCycleUsers int
 	restartCount   int
+	lastError      string
+	lastErrorTime  time.Time
+	recentErrors   []syncError
+	fetchFailures  int
+	users          map[string]userSyncStatus
 	failed         atomic.Bool
 }
 
+// userSyncStatus is the outcome of a user's most recent sync attempt.
+// LastSync is the last successful sync; LastError is cleared on success.
+// LastDurationMs is how long the attempt took, success or not.
+type userSyncStatus struct {
+	GUID           string     `json:"guid"`
+	LastSync       *time.Time `json:"last_sync,omitempty"`
+	LastError      string     `json:"last_error,omitempty"`
+	LastErrorAt    *time.Time `json:"last_error_at,omitempty"`
+	LastDurationMs int64      `json:"last_duration_ms"`
+}
+
+// slowestUsersReported caps the "slowest_users" list in /health.
+const slowestUsersReported = 5
+
+// recentErrorsKept caps the "recent_errors" list in /health; the oldest
+// entry is dropped first.
+const recentErrorsKept = 20
+
+// syncError is one error recorded as the sync's last error. lastError only
+// holds the newest, so these show whether failures share a cause.
+type syncError struct {
+	Message string `json:"message"`
+	At      string `json:"at"`
+}
+
+// setLastError records msg as the last error and appends it to
+// recentErrors. Callers hold sh.mu.
+func (sh *syncHealth) setLastError(msg string) {
+	sh.lastError = msg
+	sh.lastErrorTime = time.Now()
+	if len(sh.recentErrors) == recentErrorsKept {
+		sh.recentErrors = append(sh.recentErrors[:0], sh.recentErrors[1:]...)
+	}
+	sh.recentErrors = append(sh.recentErrors, syncError{
+		Message: msg,
+		At:      sh.lastErrorTime.Format(time.RFC3339),
+	})
+}
+
 func (sh *syncHealth) setRunning(users int) {
 	sh.mu.Lock()
 	defer sh.mu.Unlock()
 	sh.status = "running"
 	sh.lastCycleTime = time.Now()
 	sh.lastCycleUsers = users
+	sh.fetchFailures = 0
 	sh.failed.Store(false)
 }
 
+// setFetchFailed records a cycle lost to a user batch query error. The
+// status escalates from "degraded" to "unhealthy" (and the pod goes
+// NotReady) once `consecutive` reaches maxFetchFailures; the next
+// successful cycle clears it via setRunning.
+func (sh *syncHealth) setFetchFailed(consecutive int, err error) {
+	sh.mu.Lock()
+	defer sh.mu.Unlock()
+	sh.fetchFailures = consecutive
+	sh.setLastError(fmt.Sprintf("fetch user batch: %v", err))
+	if consecutive >= maxFetchFailures {
+		sh.status = "unhealthy"
+		sh.failed.Store(true)
+	} else {
+		sh.status = "degraded"
+	}
+}
+
 func (sh *syncHealth) setFailed(restarts int) {
 	sh.mu.Lock()
 	defer sh.mu.Unlock()
@@ -60,12 +146,64 @@ func (sh *syncHealth) setFailed(restarts int) {
 	sh.failed.Store(true)
 }
 
+// recordParseError stores a YahooParseError as the last sync error so the
+// raw-body snippet shows up in /health. Other errors are already visible
+// in logs and are ignored here. Nil-safe so sync can run without health.
+func (sh *syncHealth) recordParseError(err error) {
+	var parseErr *YahooParseError
+	if sh == nil || !errors.As(err, &parseErr) {
+		return
+	}
+	sh.mu.Lock()
+	defer sh.mu.Unlock()
+	sh.setLastError(parseErr.Error())
+}
+
+// recordUserSync stores the outcome and duration of a single user's sync so
+// operators can spot users that fail every cycle (e.g. a revoked refresh
+// token) or hold a concurrency slot for too long from /health instead of
+// grepping logs. Nil-safe.
+func (sh *syncHealth) recordUserSync(guid string, err error, took time.Duration) {
+	if sh == nil {
+		return
+	}
+	now := time.Now()
+	sh.mu.Lock()
+	defer sh.mu.Unlock()
+	if sh.users == nil {
+		sh.users = make(map[string]userSyncStatus)
+	}
+	st := sh.users[guid]
+	st.GUID = guid
+	st.LastDurationMs = took.Milliseconds()
+	if err != nil {
+		st.LastError = err.Error()
+		st.LastErrorAt = &now
+	} else {
+		st.LastSync = &now
+		st.LastError = ""
+		st.LastErrorAt = nil
+	}
+	sh.users[guid] = st
+}
+
 // IsFailed reports whether the sync loop has exhausted its restart budget
-// and given up. Safe to call from any goroutine without locking.
+// and given up, or has lost maxFetchFailures cycles in a row. Safe to call
+// from any goroutine without locking.
 func (sh *syncHealth) IsFailed() bool {
 	return sh.failed.Load()
 }
 
+// failureReason describes why IsFailed is true, for the readiness body.
+func (sh *syncHealth) failureReason() string {
+	sh.mu.RLock()
+	defer sh.mu.RUnlock()
+	if sh.status == "unhealthy" {
+		return fmt.Sprintf("unhealthy: %d consecutive fetch failures", sh.fetchFailures)
+	}
+	return "failed: exceeded max restarts"
+}
+
 func (sh *syncHealth) snapshot() map[string]any {
 	sh.mu.RLock()
 	defer sh.mu.RUnlock()
@@ -73,10 +211,43 @@ func (sh *syncHealth) snapshot() map[string]any {
 		"sync_status":   sh.status,
 		"restart_count": sh.restartCount,
 	}
+	if sh.fetchFailures > 0 {
+		m["consecutive_fetch_failures"] = sh.fetchFailures
+	}
 	if !sh.lastCycleTime.IsZero() {
 		m["last_cycle"] = sh.lastCycleTime.Format(time.RFC3339)
 		m["last_cycle_users"] = sh.lastCycleUsers
 	}
+	if sh.lastError != "" {
+		m["last_error"] = sh.lastError
+		m["last_error_at"] = sh.lastErrorTime.Format(time.RFC3339)
+		m["recent_errors"] = append([]syncError(nil), sh.recentErrors...)
+	}
+	if len(sh.users) > 0 {
+		// Only failing users are listed; healthy ones would just bloat the
+		// payload once the user count grows.
+		failing := make([]userSyncStatus, 0)
+		for _, st := range sh.users {
+			if st.LastError != "" {
+				failing = append(failing, st)
+			}
+		}
+		sort.Slice(failing, func(i, j int) bool { return failing[i].GUID < failing[j].GUID })
+		m["tracked_users"] = len(sh.users)
+		m["failing_users"] = failing
+
+		slowest := make([]userSyncStatus, 0, len(sh.users))
+		for _, st := range sh.users {
+			slowest = append(slowest, st)
+		}
+		sort.Slice(slowest, func(i, j int) bool {
+			if slowest[i].LastDurationMs != slowest[j].LastDurationMs {
+				return slowest[i].LastDurationMs > slowest[j].LastDurationMs
+			}
+			return slowest[i].GUID < slowest[j].GUID
+		})
+		m["slowest_users"] = slowest[:min(len(slowest), slowestUsersReported)]
+	}
 	return m
 }
 
@@ -134,6 +305,7 @@ func (a *App) runSyncLoop(ctx context.Context) error {
 	log.Printf("[Sync] Starting (interval=%ds, concurrency=%d, client_id=%s...)",
 		int(interval.Seconds()), concurrency, truncate(clientID, 8))
 
+	var fetchFailures int
 	for {
 		select {
 		case <-ctx.Done():
@@ -141,25 +313,54 @@ func (a *App) runSyncLoop(ctx context.Context) error {
 		default:
 		}
 
-		totalSynced := a.runSyncCycle(ctx, clientID, clientSecret, concurrency)
-		a.syncState.setRunning(totalSynced)
-		log.Printf("[Sync] Cycle complete: %d users synced", totalSynced)
+		totalSynced, fetchErr := a.runSyncCycle(ctx, clientID, clientSecret, concurrency)
+		if ctx.Err() != nil {
+			return nil
+		}
+		if fetchErr != nil {
+			fetchFailures++
+			a.syncState.setFetchFailed(fetchFailures, fetchErr)
+			log.Printf("[Sync] Cycle failed (%d consecutive): %d users synced", fetchFailures, totalSynced)
+		} else {
+			fetchFailures = 0
+			a.syncState.setRunning(totalSynced)
+			log.Printf("[Sync] Cycle complete: %d users synced", totalSynced)
+			a.pruneStandingsHistory(ctx)
+		}
 
 		// Sleep with cancellation
 		select {
 		case <-ctx.Done():
 			return nil
-		case <-time.After(interval):
+		case <-time.After(syncBackoff(interval, fetchFailures)):
 		}
 	}
 }
 
+// syncBackoff returns the sleep before the next cycle: the configured
+// interval, doubled for each consecutive fetch failure and capped at
+// maxSyncBackoff.
+func syncBackoff(interval time.Duration, failures int) time.Duration {
+	d := interval
+	for i := 0; i < failures && d < maxSyncBackoff; i++ {
+		d *= 2
+	}
+	return min(d, maxSyncBackoff)
+}
+
 // runSyncCycle processes all users in batches with bounded concurrency.
-func (a *App) runSyncCycle(ctx context.Context, clientID, clientSecret string, concurrency int) int {
+// The returned error is non-nil only when a user batch query failed, i.e.
+// the cycle stopped early; per-user sync failures are logged and skipped.
+func (a *App) runSyncCycle(ctx context.Context, clientID, clientSecret string, concurrency int) (int, error) {
 	sem := make(chan struct{}, concurrency)
 	var wg sync.WaitGroup
 	var totalSynced atomic.Int32
 	offset := 0
+	var fetchErr error
+
+	// Every league's stat catalog is fetched again below, so ids recorded
+	// against the previous catalogs no longer apply.
+	unmappedStats.clear()
 
 	for {
 		if ctx.Err() != nil {
@@ -169,6 +370,7 @@ func (a *App) runSyncCycle(ctx context.Context, clientID, clientSecret string, c
 		users, err := a.fetchUserBatch(ctx, defaultSyncBatchSize, offset)
 		if err != nil {
 			log.Printf("[Sync] Failed to fetch user batch: %v", err)
+			fetchErr = err
 			break
 		}
 		if len(users) == 0 {
@@ -196,7 +398,16 @@ func (a *App) runSyncCycle(ctx context.Context, clientID, clientSecret string, c
 					return
 				}
 
-				if err := a.syncUser(ctx, u, clientID, clientSecret); err != nil {
+				started := time.Now()
+				err := a.syncUser(ctx, u, clientID, clientSecret)
+				if err != nil && ctx.Err() != nil {
+					return // shutdown, not a user failure
+				}
+				a.syncState.recordUserSync(u.guid, err, time.Since(started))
+				if errors.Is(err, ErrYahooTokenRevoked) {
+					a.handleRevokedToken(ctx, u.guid)
+				}
+				if err != nil {
 					log.Printf("[Sync] Failed user %s: %v", u.guid, err)
 				} else {
 					totalSynced.Add(1)
@@ -208,7 +419,7 @@ func (a *App) runSyncCycle(ctx context.Context, clientID, clientSecret string, c
 	}
 
 	wg.Wait()
-	return int(totalSynced.Load())
+	return int(totalSynced.Load()), fetchErr
 }
 
 // ---------------------------------------------------------------------------
@@ -226,6 +437,7 @@ type yahooUser struct {
 // syncUser syncs all imported leagues for a single user.
 // Each user gets its own YahooClient — no shared state between users.
 func (a *App) syncUser(ctx context.Context, user yahooUser, clientID, clientSecret string) error {
+	started := time.Now()
 	client := NewYahooClient(clientID, clientSecret, user.refreshToken)
 
 	// Get this user's imported league keys
@@ -237,6 +449,12 @@ func (a *App) syncUser(ctx context.Context, user yahooUser, clientID, clientSecr
 		return nil // nothing to sync
 	}
 
+	// Refresh up front so a revoked token fails the user instead of being
+	// swallowed by the per-game-code loop below as "no leagues".
+	if err := client.ensureToken(ctx); err != nil {
+		return fmt.Errorf("token refresh: %w", err)
+	}
+
 	// Fetch leagues from Yahoo across all game codes and recent seasons.
 	// Include currentYear+1 to catch Yahoo-side early rollover (e.g. 2026 NFL
 	// leagues created while it's still 2025 in real-world time, or MLB 2026
@@ -251,6 +469,7 @@ func (a *App) syncUser(ctx context.Context, user yahooUser, clientID, clientSecr
 			if err != nil {
 				log.Printf("[Sync] No %s leagues for user %s season %d: %v",
 					gameCode, user.guid, season, err)
+				a.syncState.recordParseError(err)
 				continue
 			}
 			for _, leagueData := range leagues {
@@ -267,7 +486,12 @@ func (a *App) syncUser(ctx context.Context, user yahooUser, clientID, clientSecr
 
 	log.Printf("[Sync] Matched %d imported leagues for user %s", len(allLeagues), user.guid)
 
-	// Upsert league metadata and update team_key
+	includeFinished := getSyncIncludeFinished()
+
+	// Upsert league metadata and update team_key. Metadata is stored for
+	// finished leagues too so their is_finished flag lands in the database,
+	// but unless SYNC_INCLUDE_FINISHED is set a finished league whose team
+	// is already known costs no further Yahoo calls.
 	for _, item := range allLeagues {
 		lk, _ := item.data["league_key"].(string)
 		name, _ := item.data["name"].(string)
@@ -277,11 +501,15 @@ func (a *App) syncUser(ctx context.Context, user yahooUser, clientID, clientSecr
 			log.Printf("[Sync] Failed upsert league %s: %v", lk, err)
 			continue
 		}
+		if !includeFinished && item.isFinished() && importedKeys[lk] != nil {
+			continue
+		}
 
 		// Find user's team in this league and update team_key
 		teams, err := client.GetTeams(ctx, lk)
 		if err != nil {
 			log.Printf("[Sync] Failed to get teams for %s: %v", lk, err)
+			a.syncState.recordParseError(err)
 		} else {
 			teamKey, teamName := findUserTeam(teams, user.guid)
 			if err := a.upsertUserLeague(ctx, user.guid, lk, teamKey, teamName); err != nil {
@@ -290,163 +518,196 @@ func (a *App) syncUser(ctx context.Context, user yahooUser, clientID, clientSecr
 		}
 	}
 
-	// Filter to active (not finished) leagues
-	var activeLeagues []leagueSyncItem
-	for _, item := range allLeagues {
-		isFinished, _ := item.data["is_finished"].(bool)
-		if !isFinished {
-			activeLeagues = append(activeLeagues, item)
-		}
+	activeLeagues, skipped := selectLeaguesToSync(allLeagues, includeFinished)
+	if skipped > 0 {
+		log.Printf("[Sync] Skipping %d finished leagues for user %s", skipped, user.guid)
 	}
 
-	if skipped := len(allLeagues) - len(activeLeagues); skipped > 0 {
-		log.Printf("[Sync] Skipping %d finished leagues", skipped)
-	}
-
-	// Sync standings, matchups, and rosters for active leagues
-	for _, item := range activeLeagues {
-		lk, _ := item.data["league_key"].(string)
+	// Sync standings, matchups, and rosters for active leagues. Leagues are
+	// independent of each other, so they run in parallel (bounded by
+	// SYNC_LEAGUE_CONCURRENCY). The YahooClient serializes token refreshes
+	// behind its mutex, so a refresh in one goroutine is seen by the rest,
+	// and each league logs and swallows its own failures.
+	forEachLeague(ctx, activeLeagues, getLeagueSyncConcurrency(), func(item leagueSyncItem) {
+		a.syncActiveLeague(ctx, client, item)
+	})
 
-		// Standings
-		standings, err := client.GetStandings(ctx, lk)
+	// Persist rotated refresh token if changed
+	newToken := client.RefreshedToken()
+	if newToken != "" && newToken != user.refreshToken {
+		log.Printf("[Sync] Refresh token updated for user %s, persisting...", user.guid)
+		encrypted, err := Encrypt(newToken)
 		if err != nil {
-			log.Printf("[Sync] Failed standings for %s: %v", lk, err)
-		} else if standings != nil {
-			if err := a.upsertStandings(ctx, lk, standings); err != nil {
-				log.Printf("[Sync] Failed upsert standings for %s: %v", lk, err)
-			} else {
-				log.Printf("[Sync] Synced standings for %s (%d teams)", lk, len(standings))
+			log.Printf("[Sync] Failed to encrypt rotated token for %s: %v", user.guid, err)
+		} else {
+			if err := a.updateRefreshToken(ctx, user.guid, encrypted); err != nil {
+				log.Printf("[Sync] Failed to persist rotated token for %s: %v", user.guid, err)
 			}
 		}
+	}
 
-		// Matchups — current week + previous week
-		currentWeek := 0
-		if cw, ok := item.data["current_week"]; ok && cw != nil {
-			switch v := cw.(type) {
-			case int:
-				currentWeek = v
-			case *int:
-				if v != nil {
-					currentWeek = *v
-				}
-			}
-		}
+	// Mark sync complete
+	if err := a.updateUserSyncTime(ctx, user.guid); err != nil {
+		log.Printf("[Sync] Failed to update sync time for %s: %v", user.guid, err)
+	}
 
-		if currentWeek > 0 {
-			weeksToSync := []int{currentWeek}
-			if currentWeek > 1 {
-				weeksToSync = append(weeksToSync, currentWeek-1)
-			}
+	// Invalidate league cache
+	a.invalidateLeagueCache(ctx, user.guid)
 
-			for _, weekNum := range weeksToSync {
-				wk, matchups, err := client.GetScoreboard(ctx, lk, weekNum)
-				if err != nil {
-					log.Printf("[Sync] Failed matchups for %s week %d: %v", lk, weekNum, err)
-					continue
-				}
-				if wk <= 0 {
-					wk = weekNum
-				}
-				if matchups != nil {
-					if err := a.upsertMatchups(ctx, lk, wk, matchups); err != nil {
-						log.Printf("[Sync] Failed upsert matchups for %s week %d: %v", lk, wk, err)
-					} else {
-						log.Printf("[Sync] Synced %d matchups for %s week %d", len(matchups), lk, wk)
-					}
-				}
-			}
-		}
+	log.Printf("[Sync] Complete for user %s (%d active leagues in %v)",
+		user.guid, len(activeLeagues), time.Since(started).Round(time.Millisecond))
+	return nil
+}
 
-		// Rosters — all teams in the league
-		teams, err := client.GetTeams(ctx, lk)
-		if err != nil {
-			log.Printf("[Sync] Failed to get teams for rosters %s: %v", lk, err)
-			continue
-		}
+// syncActiveLeague syncs standings, the current and previous week's
+// matchups, and every team's roster for one active league. Errors are
+// logged per step and never propagated — one bad league must not abort
+// the rest of the user's sync.
+func (a *App) syncActiveLeague(ctx context.Context, client *YahooClient, item leagueSyncItem) {
+	lk, _ := item.data["league_key"].(string)
 
-		// Fetch the league's authoritative stat catalog + scoring modifiers
-		// once per league. The catalog drives label rendering in the UI;
-		// modifiers drive synthetic points for H2H/roto points leagues.
-		catalog, err := client.GetLeagueStatCatalog(ctx, lk)
-		if err != nil {
-			log.Printf("[Sync] Failed league stat catalog for %s: %v (continuing without it)", lk, err)
-			catalog = nil
-		}
-		var statModifiers map[string]float64
-		if catalog != nil {
-			statModifiers = catalog.Modifiers
-			// Persist the catalog onto the league row so the dashboard
-			// bundle can ship it to the frontend without a second call.
-			if err := a.upsertLeagueStatCatalog(ctx, lk, catalog); err != nil {
-				log.Printf("[Sync] Failed to persist stat catalog for %s: %v", lk, err)
-			}
+	// Standings
+	standings, err := client.GetStandings(ctx, lk)
+	if err != nil {
+		log.Printf("[Sync] Failed standings for %s: %v", lk, err)
+		a.syncState.recordParseError(err)
+	} else if standings != nil {
+		if err := a.upsertStandings(ctx, lk, standings); err != nil {
+			log.Printf("[Sync] Failed upsert standings for %s: %v", lk, err)
+		} else {
+			log.Printf("[Sync] Synced standings for %s (%d teams)", lk, len(standings))
 		}
+	}
 
-		// Pre-compute the set of stat IDs this league scores so the daily
-		// fetch returns only relevant stats (Yahoo's date endpoint otherwise
-		// ships 30-50 stats per player, most of them unused).
-		var enabledStatIDs map[string]bool
-		if catalog != nil && len(catalog.Stats) > 0 {
-			enabledStatIDs = make(map[string]bool, len(catalog.Stats))
-			for _, s := range catalog.Stats {
-				enabledStatIDs[s.StatID] = true
+	// Matchups — current week + previous week
+	currentWeek := 0
+	if cw, ok := item.data["current_week"]; ok && cw != nil {
+		switch v := cw.(type) {
+		case int:
+			currentWeek = v
+		case *int:
+			if v != nil {
+				currentWeek = *v
 			}
 		}
+	}
 
-		todayDate := todayInEastern()
+	if currentWeek > 0 {
+		weeksToSync := []int{currentWeek}
+		if currentWeek > 1 {
+			weeksToSync = append(weeksToSync, currentWeek-1)
+		}
 
-		for _, team := range teams {
-			// Pass currentWeek so Yahoo populates per-player points for that
-			// week. If currentWeek is 0 (finished league or missing metadata)
-			// the call falls back to a roster-only request.
-			roster, err := client.GetRoster(ctx, team.TeamKey, lk, team.Name, currentWeek, statModifiers)
+		for _, weekNum := range weeksToSync {
+			wk, matchups, err := client.GetScoreboard(ctx, lk, weekNum)
 			if err != nil {
-				log.Printf("[Sync] Failed roster for %s: %v", team.TeamKey, err)
+				log.Printf("[Sync] Failed matchups for %s week %d: %v", lk, weekNum, err)
+				a.syncState.recordParseError(err)
 				continue
 			}
-
-			// Enrich each player with today's stats. One extra Yahoo call per
-			// team — not free, but the payoff is letting the UI toggle
-			// between week-to-date and today without a separate fetch path.
-			dailyStats, dErr := client.GetTeamDailyStats(ctx, team.TeamKey, todayDate, enabledStatIDs)
-			if dErr != nil {
-				log.Printf("[Sync] Failed daily stats for %s: %v (continuing without today)", team.TeamKey, dErr)
-			} else if len(dailyStats) > 0 {
-				mergeDailyStatsIntoRoster(roster, dailyStats)
+			if wk <= 0 {
+				wk = weekNum
 			}
-
-			if err := a.upsertRoster(ctx, team.TeamKey, lk, roster); err != nil {
-				log.Printf("[Sync] Failed upsert roster for %s: %v", team.TeamKey, err)
-			} else {
-				log.Printf("[Sync] Synced roster for %s (%s)", team.TeamKey, team.Name)
+			if matchups != nil {
+				if err := a.upsertMatchups(ctx, lk, wk, matchups); err != nil {
+					log.Printf("[Sync] Failed upsert matchups for %s week %d: %v", lk, wk, err)
+				} else {
+					log.Printf("[Sync] Synced %d matchups for %s week %d", len(matchups), lk, wk)
+				}
 			}
 		}
 	}
 
-	// Persist rotated refresh token if changed
-	newToken := client.RefreshedToken()
-	if newToken != "" && newToken != user.refreshToken {
-		log.Printf("[Sync] Refresh token updated for user %s, persisting...", user.guid)
-		encrypted, err := Encrypt(newToken)
-		if err != nil {
-			log.Printf("[Sync] Failed to encrypt rotated token for %s: %v", user.guid, err)
-		} else {
-			if err := a.updateRefreshToken(ctx, user.guid, encrypted); err != nil {
-				log.Printf("[Sync] Failed to persist rotated token for %s: %v", user.guid, err)
-			}
+	// Rosters — all teams in the league
+	teams, err := client.GetTeams(ctx, lk)
+	if err != nil {
+		log.Printf("[Sync] Failed to get teams for rosters %s: %v", lk, err)
+		a.syncState.recordParseError(err)
+		return
+	}
+
+	// Fetch the league's authoritative stat catalog + scoring modifiers
+	// once per league. The catalog drives label rendering in the UI;
+	// modifiers drive synthetic points for H2H/roto points leagues.
+	catalog, err := client.GetLeagueStatCatalog(ctx, lk)
+	if err != nil {
+		log.Printf("[Sync] Failed league stat catalog for %s: %v (continuing without it)", lk, err)
+		a.syncState.recordParseError(err)
+		catalog = nil
+	}
+	var statModifiers map[string]float64
+	if catalog != nil {
+		statModifiers = catalog.Modifiers
+		// Persist the catalog onto the league row so the dashboard
+		// bundle can ship it to the frontend without a second call.
+		if err := a.upsertLeagueStatCatalog(ctx, lk, catalog); err != nil {
+			log.Printf("[Sync] Failed to persist stat catalog for %s: %v", lk, err)
 		}
 	}
 
-	// Mark sync complete
-	if err := a.updateUserSyncTime(ctx, user.guid); err != nil {
-		log.Printf("[Sync] Failed to update sync time for %s: %v", user.guid, err)
+	// Pre-compute the set of stat IDs this league scores so the daily
+	// fetch returns only relevant stats (Yahoo's date endpoint otherwise
+	// ships 30-50 stats per player, most of them unused).
+	var enabledStatIDs map[string]bool
+	if catalog != nil && len(catalog.Stats) > 0 {
+		enabledStatIDs = make(map[string]bool, len(catalog.Stats))
+		for _, s := range catalog.Stats {
+			enabledStatIDs[s.StatID] = true
+		}
 	}
 
-	// Invalidate league cache
-	a.invalidateLeagueCache(ctx, user.guid)
+	todayDate := todayInEastern()
 
-	log.Printf("[Sync] Complete for user %s", user.guid)
-	return nil
+	for _, team := range teams {
+		// Pass currentWeek so Yahoo populates per-player points for that
+		// week. If currentWeek is 0 (finished league or missing metadata)
+		// the call falls back to a roster-only request.
+		roster, err := client.GetRoster(ctx, team.TeamKey, lk, team.Name, currentWeek, statModifiers)
+		if err != nil {
+			log.Printf("[Sync] Failed roster for %s: %v", team.TeamKey, err)
+			a.syncState.recordParseError(err)
+			continue
+		}
+
+		// Enrich each player with today's stats. One extra Yahoo call per
+		// team — not free, but the payoff is letting the UI toggle
+		// between week-to-date and today without a separate fetch path.
+		dailyStats, dErr := client.GetTeamDailyStats(ctx, team.TeamKey, todayDate, enabledStatIDs)
+		if dErr != nil {
+			log.Printf("[Sync] Failed daily stats for %s: %v (continuing without today)", team.TeamKey, dErr)
+			a.syncState.recordParseError(dErr)
+		} else if len(dailyStats) > 0 {
+			mergeDailyStatsIntoRoster(roster, dailyStats)
+		}
+
+		if err := a.upsertRoster(ctx, team.TeamKey, lk, roster); err != nil {
+			log.Printf("[Sync] Failed upsert roster for %s: %v", team.TeamKey, err)
+		} else {
+			log.Printf("[Sync] Synced roster for %s (%s)", team.TeamKey, team.Name)
+		}
+	}
+}
+
+// forEachLeague runs fn for every league, at most limit at a time, and
+// returns once all of them finish. Leagues not yet started when ctx is
+// cancelled are skipped.
+func forEachLeague(ctx context.Context, leagues []leagueSyncItem, limit int, fn func(leagueSyncItem)) {
+	sem := make(chan struct{}, limit)
+	var wg sync.WaitGroup
+	for _, item := range leagues {
+		sem <- struct{}{}
+		if ctx.Err() != nil {
+			<-sem
+			break
+		}
+		wg.Add(1)
+		go func(item leagueSyncItem) {
+			defer wg.Done()
+			defer func() { <-sem }()
+			fn(item)
+		}(item)
+	}
+	wg.Wait()
 }
 
 type leagueSyncItem struct {
@@ -454,6 +715,27 @@ type leagueSyncItem struct {
 	gameCode string
 }
 
+func (item leagueSyncItem) isFinished() bool {
+	finished, _ := item.data["is_finished"].(bool)
+	return finished
+}
+
+// selectLeaguesToSync returns the leagues whose standings, matchups and
+// rosters should be synced, and how many finished ones were left out.
+// With includeFinished every league is synced.
+func selectLeaguesToSync(leagues []leagueSyncItem, includeFinished bool) ([]leagueSyncItem, int) {
+	if includeFinished {
+		return leagues, 0
+	}
+	var active []leagueSyncItem
+	for _, item := range leagues {
+		if !item.isFinished() {
+			active = append(active, item)
+		}
+	}
+	return active, len(leagues) - len(active)
+}
+
 // ---------------------------------------------------------------------------
 // Database operations for sync
 // ---------------------------------------------------------------------------
@@ -462,6 +744,7 @@ func (a *App) fetchUserBatch(ctx context.Context, limit, offset int) ([]yahooUse
 	rows, err := a.db.Query(ctx,
 		`SELECT guid, logto_sub, refresh_token, last_sync
 		 FROM yahoo_users
+		 WHERE disabled_at IS NULL
 		 ORDER BY last_sync ASC NULLS FIRST
 		 LIMIT $1 OFFSET $2`,
 		limit, offset,
@@ -573,6 +856,8 @@ func (a *App) upsertLeagueStatCatalog(ctx context.Context, leagueKey string, cat
 	return err
 }
 
+// upsertStandings replaces the league's latest standings and appends them
+// to yahoo_standings_history when they changed.
 func (a *App) upsertStandings(ctx context.Context, leagueKey string, data []map[string]any) error {
 	jsonData, err := json.Marshal(data)
 	if err != nil {
@@ -585,7 +870,13 @@ func (a *App) upsertStandings(ctx context.Context, leagueKey string, data []map[
 		 SET data = EXCLUDED.data, updated_at = CURRENT_TIMESTAMP`,
 		leagueKey, string(jsonData),
 	)
-	return err
+	if err != nil {
+		return err
+	}
+	if err := a.appendStandingsSnapshot(ctx, leagueKey, string(jsonData)); err != nil {
+		return fmt.Errorf("append standings snapshot: %w", err)
+	}
+	return nil
 }
 
 func (a *App) upsertMatchups(ctx context.Context, leagueKey string, week int, data []map[string]any) error {
@@ -632,12 +923,41 @@ func (a *App) upsertUserLeague(ctx context.Context, guid, leagueKey string, team
 
 func (a *App) updateUserSyncTime(ctx context.Context, guid string) error {
 	_, err := a.db.Exec(ctx,
-		`UPDATE yahoo_users SET last_sync = CURRENT_TIMESTAMP WHERE guid = $1`,
+		`UPDATE yahoo_users SET last_sync = CURRENT_TIMESTAMP, invalid_grant_count = 0 WHERE guid = $1`,
 		guid,
 	)
 	return err
 }
 
+// handleRevokedToken counts an invalid_grant refresh for the user and
+// disables them once disableAfterInvalidGrants is reached, so the sync stops
+// hitting Yahoo with a dead token every cycle. Nothing is deleted — the
+// user's leagues stay in place and reconnecting Yahoo re-enables them.
+func (a *App) handleRevokedToken(ctx context.Context, guid string) {
+	var count int
+	var disabled bool
+	err := a.db.QueryRow(ctx,
+		`UPDATE yahoo_users
+		 SET invalid_grant_count = invalid_grant_count + 1,
+		     disabled_at = CASE WHEN invalid_grant_count + 1 >= $2
+		                        THEN CURRENT_TIMESTAMP ELSE disabled_at END
+		 WHERE guid = $1
+		 RETURNING invalid_grant_count, disabled_at IS NOT NULL`,
+		guid, disableAfterInvalidGrants,
+	).Scan(&count, &disabled)
+	if err != nil {
+		log.Printf("[Sync] Failed to record invalid_grant for %s: %v", guid, err)
+		return
+	}
+	if disabled {
+		log.Printf("[Sync] Disabled user %s after %d consecutive invalid_grant refreshes — user must reconnect Yahoo",
+			guid, count)
+		return
+	}
+	log.Printf("[Sync] Refresh token rejected for user %s (%d/%d before disable)",
+		guid, count, disableAfterInvalidGrants)
+}
+
 func (a *App) updateRefreshToken(ctx context.Context, guid, encryptedToken string) error {
 	_, err := a.db.Exec(ctx,
 		`UPDATE yahoo_users SET refresh_token = $2 WHERE guid = $1`,
@@ -675,3 +995,32 @@ func getSyncConcurrency() int {
 	}
 	return v
 }
+
+// getSyncIncludeFinished reports whether SYNC_INCLUDE_FINISHED asks the
+// sync to keep refreshing leagues whose season is over. Off by default:
+// their standings and rosters no longer change.
+func getSyncIncludeFinished() bool {
+	raw := os.Getenv("SYNC_INCLUDE_FINISHED")
+	switch strings.ToLower(strings.TrimSpace(raw)) {
+	case "", "0", "false":
+		return false
+	case "1", "true":
+		return true
+	default:
+		log.Printf("[Sync] SYNC_INCLUDE_FINISHED=%q is invalid, skipping finished leagues", raw)
+		return false
+	}
+}
+
+func getLeagueSyncConcurrency() int {
+	raw := os.Getenv("SYNC_LEAGUE_CONCURRENCY")
+	if raw == "" {
+		return defaultLeagueSyncConcurrency
+	}
+	v, err := strconv.Atoi(raw)
+	if err != nil || v <= 0 {
+		log.Printf("[Sync] SYNC_LEAGUE_CONCURRENCY=%q is invalid, defaulting to %d", raw, defaultLeagueSyncConcurrency)
+		return defaultLeagueSyncConcurrency
+	}
+	return v
+}
@@ -0,0 +1,140 @@
+package main
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"sync"
+	"sync/atomic"
+	"testing"
+	"time"
+)
+
+func TestSelectLeaguesToSync(t *testing.T) {
+	leagues := []leagueSyncItem{
+		{data: map[string]any{"league_key": "nfl.l.1", "is_finished": false}},
+		{data: map[string]any{"league_key": "nfl.l.2", "is_finished": true}},
+		{data: map[string]any{"league_key": "nba.l.3"}},
+		{data: map[string]any{"league_key": "mlb.l.4", "is_finished": true}},
+	}
+
+	active, skipped := selectLeaguesToSync(leagues, false)
+	if skipped != 2 {
+		t.Errorf("skipped = %d, want 2", skipped)
+	}
+	var keys []string
+	for _, item := range active {
+		keys = append(keys, item.data["league_key"].(string))
+	}
+	if len(keys) != 2 || keys[0] != "nfl.l.1" || keys[1] != "nba.l.3" {
+		t.Errorf("active leagues = %v, want [nfl.l.1 nba.l.3]", keys)
+	}
+
+	all, skipped := selectLeaguesToSync(leagues, true)
+	if len(all) != len(leagues) || skipped != 0 {
+		t.Errorf("includeFinished: got %d leagues, %d skipped; want %d, 0", len(all), skipped, len(leagues))
+	}
+}
+
+func TestGetSyncIncludeFinished(t *testing.T) {
+	for raw, want := range map[string]bool{"": false, "0": false, "true": true, "1": true, " TRUE ": true, "sometimes": false} {
+		t.Setenv("SYNC_INCLUDE_FINISHED", raw)
+		if got := getSyncIncludeFinished(); got != want {
+			t.Errorf("SYNC_INCLUDE_FINISHED=%q: got %v, want %v", raw, got, want)
+		}
+	}
+}
+
+// slowTransport answers every Yahoo call after a fixed latency and tracks
+// how many requests were in flight at once.
+type slowTransport struct {
+	latency  time.Duration
+	requests atomic.Int32
+	inFlight atomic.Int32
+
+	mu          sync.Mutex
+	maxInFlight int32
+}
+
+func (st *slowTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	st.requests.Add(1)
+	n := st.inFlight.Add(1)
+	defer st.inFlight.Add(-1)
+	st.mu.Lock()
+	if n > st.maxInFlight {
+		st.maxInFlight = n
+	}
+	st.mu.Unlock()
+
+	time.Sleep(st.latency)
+	return &http.Response{
+		StatusCode: http.StatusOK,
+		Header:     http.Header{},
+		Body:       io.NopCloser(bytes.NewReader([]byte(`<fantasy_content/>`))),
+		Request:    req,
+	}, nil
+}
+
+// A 10-league user shares one client across the fan-out: the leagues run
+// at most `limit` at a time, every league still gets its calls, and the
+// whole sync takes about ceil(10/limit) league-lengths instead of 10.
+func TestForEachLeagueFansOutOnOneClient(t *testing.T) {
+	const (
+		leagueCount    = 10
+		callsPerLeague = 4
+		limit          = 3
+		apiDelay       = 10 * time.Millisecond
+		latency        = 40 * time.Millisecond
+	)
+	st := &slowTransport{latency: latency}
+	client, _ := newCannedYahooClient()
+	client.httpClient = &http.Client{Transport: st}
+	client.apiDelay = apiDelay
+
+	leagues := make([]leagueSyncItem, leagueCount)
+	for i := range leagues {
+		leagues[i] = leagueSyncItem{data: map[string]any{"league_key": fmt.Sprintf("nfl.l.%d", i)}}
+	}
+
+	started := time.Now()
+	forEachLeague(context.Background(), leagues, limit, func(item leagueSyncItem) {
+		lk := item.data["league_key"].(string)
+		for i := 0; i < callsPerLeague; i++ {
+			_, _ = client.GetStandings(context.Background(), lk)
+		}
+	})
+	elapsed := time.Since(started)
+
+	if got := st.requests.Load(); got != leagueCount*callsPerLeague {
+		t.Errorf("requests = %d; want %d", got, leagueCount*callsPerLeague)
+	}
+	if st.maxInFlight > limit {
+		t.Errorf("max in-flight requests = %d; want at most %d", st.maxInFlight, limit)
+	}
+	if st.maxInFlight < 2 {
+		t.Errorf("max in-flight requests = %d; leagues did not overlap", st.maxInFlight)
+	}
+
+	perLeague := callsPerLeague * (apiDelay + latency)
+	sequential := leagueCount * perLeague
+	t.Logf("10 leagues: %v in parallel (limit %d), %v if run one at a time", elapsed.Round(time.Millisecond), limit, sequential)
+	if elapsed >= sequential*3/4 {
+		t.Errorf("fan-out took %v; want well under the sequential %v", elapsed, sequential)
+	}
+}
+
+func TestForEachLeagueStopsStartingAfterCancel(t *testing.T) {
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	leagues := make([]leagueSyncItem, 5)
+	var ran atomic.Int32
+	forEachLeague(ctx, leagues, 1, func(leagueSyncItem) {
+		ran.Add(1)
+		cancel()
+	})
+	if got := ran.Load(); got != 1 {
+		t.Errorf("leagues run after cancel = %d; want 1", got)
+	}
+}
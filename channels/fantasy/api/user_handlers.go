@@ -3,7 +3,6 @@ package main
 import (
 	"context"
 	"crypto/rand"
-	"database/sql"
 	"encoding/xml"
 	"fmt"
 	"io"
@@ -11,6 +10,7 @@ import (
 	"net/http"
 	"os"
 	"strings"
+	"sync"
 	"time"
 
 	"github.com/gofiber/fiber/v2"
@@ -38,6 +38,11 @@ import (
 //     URL externally.
 //   - Otherwise (HTML / wildcard) → 307 redirect to Yahoo. Used when a
 //     logged-in browser session hits the URL directly.
+//
+// `?redirect_uri=` opts into the redirect flow for callers that can't
+// receive the popup's postMessage: YahooCallback then 302s to that URI
+// instead of rendering the popup page. The URI must be on
+// YAHOO_AUTH_REDIRECT_ALLOWLIST.
 func (a *App) YahooStart(c *fiber.Ctx) error {
 	logtoSub := GetUserSub(c)
 	if logtoSub == "" {
@@ -49,6 +54,15 @@ func (a *App) YahooStart(c *fiber.Ctx) error {
 	}
 	log.Printf("[YahooStart] Hit — logto_sub=%s", logtoSub)
 
+	redirectURI := c.Query("redirect_uri")
+	if redirectURI != "" && !allowedAuthRedirect(redirectURI) {
+		log.Printf("[YahooStart] Rejected redirect_uri=%q — not on YAHOO_AUTH_REDIRECT_ALLOWLIST", redirectURI)
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "redirect_uri not allowed",
+		})
+	}
+
 	b := make([]byte, OAuthStateBytes)
 	if _, err := rand.Read(b); err != nil {
 		log.Printf("[YahooStart] Failed to generate CSRF state: %v", err)
@@ -59,13 +73,22 @@ func (a *App) YahooStart(c *fiber.Ctx) error {
 	}
 	state := fmt.Sprintf("%x", b)
 
-	// Store state and logto_sub mapping
-	pipe := a.rdb.Pipeline()
-	pipe.Set(context.Background(), RedisCSRFPrefix+state, "1", OAuthStateExpiry)
-	pipe.Set(context.Background(), RedisYahooStateLogtoPrefix+state, logtoSub, OAuthStateExpiry)
-	_, err := pipe.Exec(context.Background())
+	// The redirect key is written first so it always exists by the time the
+	// state below can be consumed.
+	ttl := oauthStateTTL()
+	if redirectURI != "" {
+		if err := a.rdb.Set(context.Background(), RedisYahooStateRedirectPrefix+state, redirectURI, ttl).Err(); err != nil {
+			log.Printf("[YahooStart] Failed to store redirect_uri: %v", err)
+			return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{Status: "error", Error: "Failed to store state"})
+		}
+	}
+
+	// The state key's value is the initiating logto_sub, so validating the
+	// state and recovering who started the flow is one atomic GETDEL in
+	// YahooCallback — there is no second key to race on.
+	err := a.rdb.Set(context.Background(), csrfKey(state), logtoSub, ttl).Err()
 	if err != nil {
-		log.Printf("[YahooStart] Redis pipeline failed: %v", err)
+		log.Printf("[YahooStart] Failed to store OAuth state: %v", err)
 		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{Status: "error", Error: "Failed to store state"})
 	}
 
@@ -87,9 +110,16 @@ func (a *App) YahooStart(c *fiber.Ctx) error {
 }
 
 // YahooCallback handles the Yahoo OAuth callback.
-// No cookies are set — the Python service owns all Yahoo token management.
-// We only persist the refresh token to Postgres (encrypted) and populate
-// Redis CDC subscriber sets.
+//
+// No cookies are set: neither the access nor the refresh token ever leaves
+// the server. The access token is used once (to resolve the Yahoo GUID) and
+// dropped; the refresh token is persisted to Postgres AES-GCM encrypted
+// under ENCRYPTION_KEY (see UpsertYahooUser). Keep it that way — a token in
+// a cookie is a bearer credential for anyone who can read the cookie.
+//
+// The same holds for the redirect flow: the fragment only carries the
+// outcome (`#yahoo_auth=complete` or `#yahoo_auth=error`), never a token.
+// The caller confirms the link via /users/me/yahoo-status.
 func (a *App) YahooCallback(c *fiber.Ctx) error {
 	state, code := c.Query("state"), c.Query("code")
 	log.Printf("[YahooCallback] Hit — state=%q code_present=%v", state, code != "")
@@ -99,25 +129,13 @@ func (a *App) YahooCallback(c *fiber.Ctx) error {
 		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{Status: "error", Error: "Missing state or code"})
 	}
 
-	val, err := a.rdb.GetDel(context.Background(), RedisCSRFPrefix+state).Result()
-	if err != nil || val == "" {
-		log.Printf("[YahooCallback] CSRF validation failed — state=%s err=%v val=%q", state, err, val)
+	logtoSub, ok := consumeOAuthState(context.Background(), a.rdb, state)
+	if !ok {
+		log.Printf("[YahooCallback] CSRF validation failed — state=%s (unknown, expired or already used)", state)
 		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{Status: "error", Error: "Invalid or expired state"})
 	}
-	log.Printf("[YahooCallback] CSRF validated for state=%s…", state[:8])
-
-	// Retrieve (and atomically consume) the logto_sub associated with this
-	// state. GetDel prevents replay of the same state value within the 10-
-	// minute OAuth state window.
-	logtoSub, err := a.rdb.GetDel(context.Background(), RedisYahooStateLogtoPrefix+state).Result()
-	if err == redis.Nil {
-		logtoSub = ""
-		log.Printf("[YahooCallback] No logto_sub found for state=%s (expired or already consumed)", state[:8])
-	} else if err != nil {
-		log.Printf("[YahooCallback] Warning: Failed to retrieve logto_sub from Redis for state %s: %v", state, err)
-	} else if logtoSub != "" {
-		log.Printf("[YahooCallback] Retrieved logto_sub=%s for state=%s…", logtoSub, state[:8])
-	}
+	log.Printf("[YahooCallback] CSRF validated for state=%s… logto_sub=%s", state[:8], logtoSub)
+	redirectURI := consumeAuthRedirect(context.Background(), a.rdb, state)
 
 	// Yahoo intermittently rejects the first token exchange with INVALID_REDIRECT_URI
 	// even when the redirect URI is correct. Retry once after a brief delay.
@@ -138,6 +156,9 @@ func (a *App) YahooCallback(c *fiber.Ctx) error {
 	}
 	if exchangeErr != nil {
 		log.Printf("[YahooCallback] Token exchange failed after retries: %v", exchangeErr)
+		if redirectURI != "" {
+			return c.Redirect(redirectURI+"#yahoo_auth=error", fiber.StatusFound)
+		}
 		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{Status: "error", Error: "Failed to exchange code"})
 	}
 	log.Printf("[YahooCallback] Token exchange succeeded — access_token_len=%d refresh_token_present=%v expires=%v",
@@ -150,6 +171,9 @@ func (a *App) YahooCallback(c *fiber.Ctx) error {
 		linkErr := a.fetchAndLinkYahooUser(token.AccessToken, token.RefreshToken, logtoSub)
 		if linkErr != nil {
 			log.Printf("[YahooCallback] Failed to link Yahoo account: %v", linkErr)
+			if redirectURI != "" {
+				return c.Redirect(redirectURI+"#yahoo_auth=error", fiber.StatusFound)
+			}
 
 			userMsg := "Yahoo authentication succeeded, but we failed to link your account. Please try again."
 
@@ -166,6 +190,11 @@ func (a *App) YahooCallback(c *fiber.Ctx) error {
 		log.Println("[YahooCallback] Warning: No refresh token received from Yahoo")
 	}
 
+	if redirectURI != "" {
+		log.Printf("[YahooCallback] Auth complete — redirecting to %s", redirectURI)
+		return c.Redirect(redirectURI+"#yahoo_auth=complete", fiber.StatusFound)
+	}
+
 	frontendURL := resolveFrontendURL()
 
 	log.Printf("[YahooCallback] Auth complete — sending postMessage to %s and closing popup", frontendURL)
@@ -176,6 +205,60 @@ func (a *App) YahooCallback(c *fiber.Ctx) error {
 	return c.Status(fiber.StatusOK).SendString(html)
 }
 
+// consumeOAuthState validates and burns an OAuth state value, returning the
+// logto_sub that started the flow.
+//
+// GETDEL is the sole gate: Redis executes it atomically, so when several
+// callbacks race on the same state exactly one receives the value and the
+// rest see redis.Nil. Nothing is checked before the delete, leaving no
+// window between "exists" and "consumed". Binding the state to the
+// initiating logto_sub means a consumed state can only ever link Yahoo to
+// the Scrollr account that requested it.
+func consumeOAuthState(ctx context.Context, rdb *redis.Client, state string) (string, bool) {
+	val, err := rdb.GetDel(ctx, csrfKey(state)).Result()
+	if err == redis.Nil {
+		// Issued before the key was namespaced.
+		val, err = rdb.GetDel(ctx, legacyCSRFPrefix+state).Result()
+	}
+	if err != nil || val == "" {
+		if err != nil && err != redis.Nil {
+			log.Printf("[consumeOAuthState] Redis GETDEL failed for state=%s: %v", state, err)
+		}
+		return "", false
+	}
+
+	if val != legacyCSRFStateValue {
+		return val, true
+	}
+
+	// State issued by an older pod: the logto_sub lives in its own key.
+	// Whoever won the GETDEL above owns it, so this read cannot race.
+	logtoSub, err := rdb.GetDel(ctx, RedisYahooStateLogtoPrefix+state).Result()
+	if err != nil && err != redis.Nil {
+		log.Printf("[consumeOAuthState] Failed to read legacy logto_sub for state=%s: %v", state, err)
+	}
+	return logtoSub, true
+}
+
+// consumeAuthRedirect returns the redirect_uri stored for a state by
+// YahooStart, or "" for the default popup flow. Only the caller that won
+// consumeOAuthState gets here, so the GETDEL cannot race. The URI is checked
+// against the allowlist again in case it changed mid-flow.
+func consumeAuthRedirect(ctx context.Context, rdb *redis.Client, state string) string {
+	redirectURI, err := rdb.GetDel(ctx, RedisYahooStateRedirectPrefix+state).Result()
+	if err != nil {
+		if err != redis.Nil {
+			log.Printf("[consumeAuthRedirect] Redis GETDEL failed for state=%s: %v", state, err)
+		}
+		return ""
+	}
+	if !allowedAuthRedirect(redirectURI) {
+		log.Printf("[consumeAuthRedirect] Dropping redirect_uri=%q — no longer allowed", redirectURI)
+		return ""
+	}
+	return redirectURI
+}
+
 // =============================================================================
 // Yahoo Account Linking
 // =============================================================================
@@ -253,26 +336,9 @@ func (a *App) fetchAndLinkYahooUser(accessToken, refreshToken, logtoSub string)
 		}
 	}
 
-	// Clean up any *previous* Yahoo account this Scrollr user had linked.
-	// Without this, connecting a new Yahoo account leaves the old yahoo_users
-	// row (and its yahoo_user_leagues rows) behind, causing the dashboard to
-	// show stale leagues from the old account.
-	var oldGUID string
-	oldErr := a.db.QueryRow(context.Background(),
-		"SELECT guid FROM yahoo_users WHERE logto_sub = $1", logtoIdentifier,
-	).Scan(&oldGUID)
-	if oldErr == nil && oldGUID != guid {
-		log.Printf("[fetchAndLinkYahooUser] Replacing old Yahoo link — old_guid=%s new_guid=%s logto_sub=%s", oldGUID, guid, logtoIdentifier)
-		// Remove from Redis CDC subscriber sets before deleting DB rows
-		a.CleanupLeagueSubscribers(context.Background(), oldGUID, logtoIdentifier)
-		// Delete the old yahoo_users row; CASCADE removes yahoo_user_leagues rows
-		_, delErr := a.db.Exec(context.Background(),
-			"DELETE FROM yahoo_users WHERE guid = $1", oldGUID)
-		if delErr != nil {
-			log.Printf("[fetchAndLinkYahooUser] Warning: failed to delete old Yahoo link guid=%s: %v", oldGUID, delErr)
-		}
-	}
-
+	// Any other Yahoo accounts this Scrollr user already linked are kept:
+	// a user may link several (see linkedAccounts), and each is synced
+	// with its own refresh token.
 	log.Printf("[fetchAndLinkYahooUser] Upserting user — guid=%s logto_sub=%s", guid, logtoIdentifier)
 	if err := a.UpsertYahooUser(guid, logtoIdentifier, refreshToken); err != nil {
 		return fmt.Errorf("upsert Yahoo user: %w", err)
@@ -292,7 +358,9 @@ func (a *App) fetchAndLinkYahooUser(accessToken, refreshToken, logtoSub string)
 // User Management Routes
 // =============================================================================
 
-// GetYahooStatus returns whether the current user has Yahoo connected.
+// GetYahooStatus returns whether the current user has Yahoo connected, and
+// the guids of their linked accounts. Synced means every linked account has
+// completed a sync.
 func (a *App) GetYahooStatus(c *fiber.Ctx) error {
 	userID := GetUserSub(c)
 	log.Printf("[GetYahooStatus] Hit — X-User-Sub=%q", userID)
@@ -303,16 +371,8 @@ func (a *App) GetYahooStatus(c *fiber.Ctx) error {
 		})
 	}
 
-	var lastSync sql.NullTime
-	err := a.db.QueryRow(context.Background(), `
-		SELECT last_sync FROM yahoo_users WHERE logto_sub = $1
-	`, userID).Scan(&lastSync)
-
+	accounts, err := a.linkedAccounts(context.Background(), userID)
 	if err != nil {
-		errStr := err.Error()
-		if err == sql.ErrNoRows || strings.Contains(errStr, "no rows") {
-			return c.JSON(YahooStatusResponse{Connected: false, Synced: false})
-		}
 		log.Printf("[GetYahooStatus] DB error for logto_sub=%s: %v", userID, err)
 		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
 			Status: "error",
@@ -320,15 +380,20 @@ func (a *App) GetYahooStatus(c *fiber.Ctx) error {
 		})
 	}
 
+	guids := make([]string, 0, len(accounts))
+	for _, acct := range accounts {
+		guids = append(guids, acct.guid)
+	}
 	return c.JSON(YahooStatusResponse{
-		Connected: true,
-		Synced:    lastSync.Valid,
+		Connected: len(accounts) > 0,
+		Synced:    allSynced(accounts),
+		Accounts:  guids,
 	})
 }
 
 // GetMyYahooLeagues returns all leagues + standings + matchups + rosters for
-// the authenticated user in a single response. Uses the shared
-// fetchLeagueBundleCached for efficient, cached data fetching.
+// the authenticated user in a single response, merged across every linked
+// Yahoo account via the cached per-account bundles.
 func (a *App) GetMyYahooLeagues(c *fiber.Ctx) error {
 	userID := GetUserSub(c)
 	if userID == "" {
@@ -338,19 +403,14 @@ func (a *App) GetMyYahooLeagues(c *fiber.Ctx) error {
 		})
 	}
 
-	// Resolve logto_sub → guid
-	var guid string
-	err := a.db.QueryRow(context.Background(),
-		"SELECT guid FROM yahoo_users WHERE logto_sub = $1", userID).Scan(&guid)
+	leagues, err := a.fetchUserLeagues(context.Background(), userID)
 	if err != nil {
-		return c.JSON(MyLeaguesResponse{Leagues: []LeagueResponse{}})
-	}
-
-	leagues, err := a.fetchLeagueBundleCached(context.Background(), guid)
-	if err != nil {
-		log.Printf("[GetMyYahooLeagues] fetchLeagueBundle error: %v", err)
+		log.Printf("[GetMyYahooLeagues] fetchUserLeagues error: %v", err)
 		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{Status: "error", Error: "Failed to fetch leagues"})
 	}
+	if leagues == nil {
+		leagues = []LeagueResponse{}
+	}
 
 	return c.JSON(MyLeaguesResponse{Leagues: leagues})
 }
@@ -358,6 +418,10 @@ func (a *App) GetMyYahooLeagues(c *fiber.Ctx) error {
 // DiscoverYahooLeagues discovers all Yahoo Fantasy leagues for the current
 // user across all game codes and recent seasons.  Returns league metadata
 // WITHOUT persisting to the database (used for the "Add Leagues" UI).
+//
+// Every linked Yahoo account is searched unless `?guid=` names one; each
+// league carries the `guid` of the account it was found under, which the
+// import request passes back.
 func (a *App) DiscoverYahooLeagues(c *fiber.Ctx) error {
 	userID := GetUserSub(c)
 	if userID == "" {
@@ -367,42 +431,55 @@ func (a *App) DiscoverYahooLeagues(c *fiber.Ctx) error {
 		})
 	}
 
-	var guid string
-	err := a.db.QueryRow(context.Background(),
-		"SELECT guid FROM yahoo_users WHERE logto_sub = $1", userID,
-	).Scan(&guid)
+	accounts, err := a.linkedAccounts(context.Background(), userID)
 	if err != nil {
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to read linked Yahoo accounts",
+		})
+	}
+	if guid := c.Query("guid"); guid != "" {
+		acct, err := pickAccount(accounts, guid)
+		if err != nil {
+			return c.Status(fiber.StatusNotFound).JSON(ErrorResponse{
+				Status: "error",
+				Error:  "Yahoo account not connected",
+			})
+		}
+		accounts = []linkedAccount{acct}
+	}
+	if len(accounts) == 0 {
 		return c.Status(fiber.StatusNotFound).JSON(ErrorResponse{
 			Status: "error",
 			Error:  "Yahoo account not connected",
 		})
 	}
 
-	// Fetch + decrypt refresh token
-	var encryptedToken string
-	err = a.db.QueryRow(context.Background(),
-		"SELECT refresh_token FROM yahoo_users WHERE guid = $1", guid,
-	).Scan(&encryptedToken)
-	if err != nil {
-		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
-			Status: "error", Error: "Failed to read user token",
-		})
-	}
+	ctx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
+	defer cancel()
 
-	refreshToken, err := Decrypt(encryptedToken)
-	if err != nil {
-		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
-			Status: "error", Error: "Failed to decrypt token",
-		})
+	allLeagues := make([]map[string]any, 0)
+	for _, acct := range accounts {
+		leagues, err := a.discoverAccountLeagues(ctx, acct)
+		if err != nil {
+			log.Printf("[Discover] Skipping account %s: %v", acct.guid, err)
+			continue
+		}
+		allLeagues = append(allLeagues, leagues...)
 	}
 
-	clientID := os.Getenv("YAHOO_CLIENT_ID")
-	clientSecret := os.Getenv("YAHOO_CLIENT_SECRET")
+	return c.JSON(fiber.Map{"leagues": allLeagues})
+}
 
-	ctx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
-	defer cancel()
+// discoverAccountLeagues lists one linked account's leagues for every
+// supported game code over the last, current and next season, tagging each
+// with the account's guid.
+func (a *App) discoverAccountLeagues(ctx context.Context, acct linkedAccount) ([]map[string]any, error) {
+	refreshToken, err := Decrypt(acct.encryptedToken)
+	if err != nil {
+		return nil, fmt.Errorf("decrypt token: %w", err)
+	}
 
-	client := NewYahooClient(clientID, clientSecret, refreshToken)
+	client := NewYahooClient(os.Getenv("YAHOO_CLIENT_ID"), os.Getenv("YAHOO_CLIENT_SECRET"), refreshToken)
 
 	// Include currentYear+1 so Yahoo-side early rollover leagues (created
 	// before the calendar year ticks over) appear during discovery.
@@ -432,25 +509,177 @@ func (a *App) DiscoverYahooLeagues(c *fiber.Ctx) error {
 			log.Printf("[Discover] Game/season fetch error: %v", r.err)
 			continue
 		}
+		for _, l := range r.leagues {
+			l["guid"] = acct.guid
+		}
 		allLeagues = append(allLeagues, r.leagues...)
 	}
 
-	log.Printf("[Discover] Found %d leagues for user %s", len(allLeagues), guid)
+	log.Printf("[Discover] Found %d leagues for user %s", len(allLeagues), acct.guid)
 
 	// Persist rotated refresh token if changed
 	if newToken := client.RefreshedToken(); newToken != "" && newToken != refreshToken {
 		if encrypted, err := Encrypt(newToken); err == nil {
-			a.updateRefreshToken(context.Background(), guid, encrypted)
+			a.updateRefreshToken(context.Background(), acct.guid, encrypted)
 		}
 	}
 
-	return c.JSON(fiber.Map{"leagues": allLeagues})
+	return allLeagues, nil
 }
 
-// ImportYahooLeague imports a single league directly via the Yahoo Fantasy API.
-// Fetches league metadata, standings, matchups, and rosters, then persists
-// everything to the database and populates the Redis CDC subscriber set.
-func (a *App) ImportYahooLeague(c *fiber.Ctx) error {
+// maxBatchStandingsLeagues caps how many leagues one batch standings request
+// may name, so a single call can't queue an unbounded number of Yahoo
+// requests against the user's token.
+const maxBatchStandingsLeagues = 25
+
+// BatchYahooStandings fetches live standings for several leagues in one
+// request. All fetches share one YahooClient, so the access token is
+// refreshed at most once for the whole batch, and they run with the same
+// bounded fan-out the sync worker uses per user (SYNC_LEAGUE_CONCURRENCY).
+//
+// With several Yahoo accounts linked, each league is fetched with the token
+// of the account that imported it; leagues not imported yet use the oldest
+// linked account, or the one named by the optional `guid` body field.
+//
+// A league that fails doesn't fail the batch: its entry carries an error
+// instead of standings. The response is 200 when every league succeeded
+// and 207 Multi-Status when any failed.
+func (a *App) BatchYahooStandings(c *fiber.Ctx) error {
+	userID := GetUserSub(c)
+	if userID == "" {
+		return c.Status(fiber.StatusUnauthorized).JSON(ErrorResponse{
+			Status: "unauthorized",
+			Error:  "Authentication required",
+		})
+	}
+
+	var incoming BatchStandingsRequest
+	if err := c.BodyParser(&incoming); err != nil {
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "Invalid request body",
+		})
+	}
+	leagueKeys := dedupeLeagueKeys(incoming.LeagueKeys)
+	if len(leagueKeys) == 0 {
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "league_keys is required",
+		})
+	}
+	if len(leagueKeys) > maxBatchStandingsLeagues {
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error",
+			Error:  fmt.Sprintf("at most %d league_keys per request", maxBatchStandingsLeagues),
+		})
+	}
+
+	accounts, err := a.linkedAccounts(context.Background(), userID)
+	if err != nil {
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to read linked Yahoo accounts",
+		})
+	}
+	fallback, err := pickAccount(accounts, incoming.GUID)
+	if err != nil {
+		return c.Status(fiber.StatusNotFound).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "Yahoo account not connected",
+		})
+	}
+	owners, err := a.leagueOwners(context.Background(), userID, leagueKeys)
+	if err != nil {
+		log.Printf("[BatchStandings] Failed to look up league owners: %v", err)
+		owners = nil
+	}
+
+	ctx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
+	defer cancel()
+
+	results := make(map[string]BatchStandingsResult, len(leagueKeys))
+	for guid, keys := range groupLeaguesByAccount(leagueKeys, owners, fallback.guid) {
+		acct, _ := pickAccount(accounts, guid)
+		refreshToken, err := Decrypt(acct.encryptedToken)
+		if err != nil {
+			for _, lk := range keys {
+				results[lk] = BatchStandingsResult{Error: "Failed to decrypt token"}
+			}
+			continue
+		}
+
+		client := NewYahooClient(os.Getenv("YAHOO_CLIENT_ID"), os.Getenv("YAHOO_CLIENT_SECRET"), refreshToken)
+		for lk, r := range a.fetchStandingsBatch(ctx, client, guid, keys, getLeagueSyncConcurrency()) {
+			results[lk] = r
+		}
+
+		// Persist rotated refresh token if changed
+		if newToken := client.RefreshedToken(); newToken != "" && newToken != refreshToken {
+			if encrypted, err := Encrypt(newToken); err == nil {
+				a.updateRefreshToken(context.Background(), guid, encrypted)
+			}
+		}
+	}
+
+	status := fiber.StatusOK
+	for _, r := range results {
+		if r.Error != "" {
+			status = fiber.StatusMultiStatus
+			break
+		}
+	}
+	return c.Status(status).JSON(BatchStandingsResponse{Results: results})
+}
+
+// fetchStandingsBatch fetches standings for each league key with at most
+// `concurrency` requests in flight, through cachedYahooStandings so
+// overlapping batches for the same user share Yahoo calls. The token is
+// ensured once up front so the fan-out starts with a valid access token
+// instead of every goroutine racing to refresh it; if that fails, every
+// league reports the error.
+func (a *App) fetchStandingsBatch(ctx context.Context, client *YahooClient, guid string, leagueKeys []string, concurrency int) map[string]BatchStandingsResult {
+	results := make(map[string]BatchStandingsResult, len(leagueKeys))
+	if err := client.ensureToken(ctx); err != nil {
+		log.Printf("[BatchStandings] Token refresh failed: %v", err)
+		for _, lk := range leagueKeys {
+			results[lk] = BatchStandingsResult{Error: "Yahoo authorization failed"}
+		}
+		return results
+	}
+
+	var mu sync.Mutex
+	var wg sync.WaitGroup
+	sem := make(chan struct{}, concurrency)
+	for _, lk := range leagueKeys {
+		wg.Add(1)
+		sem <- struct{}{}
+		go func(leagueKey string) {
+			defer wg.Done()
+			defer func() { <-sem }()
+
+			var r BatchStandingsResult
+			standings, err := a.cachedYahooStandings(ctx, client, guid, leagueKey)
+			if err != nil {
+				log.Printf("[BatchStandings] Failed standings for %s: %v", leagueKey, err)
+				r.Error = "Failed to fetch standings"
+			} else {
+				r.Standings = standings
+			}
+
+			mu.Lock()
+			results[leagueKey] = r
+			mu.Unlock()
+		}(lk)
+	}
+	wg.Wait()
+	return results
+}
+
+// GetYahooLeagueSettings returns a league's settings (name, scoring type,
+// team count, roster slots, stat categories, playoff start week) fetched
+// live with the caller's Yahoo token, so Yahoo decides whether they may
+// see the league. The token is that of the linked account that imported
+// the league, else the one named by `?guid=`, else the oldest link.
+func (a *App) GetYahooLeagueSettings(c *fiber.Ctx) error {
 	userID := GetUserSub(c)
 	if userID == "" {
 		return c.Status(fiber.StatusUnauthorized).JSON(ErrorResponse{
@@ -459,22 +688,100 @@ func (a *App) ImportYahooLeague(c *fiber.Ctx) error {
 		})
 	}
 
-	var guid string
-	err := a.db.QueryRow(context.Background(),
-		"SELECT guid FROM yahoo_users WHERE logto_sub = $1", userID,
-	).Scan(&guid)
+	leagueKey := strings.TrimSpace(c.Params("key"))
+	if leagueKey == "" {
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "league key is required",
+		})
+	}
+
+	accounts, err := a.linkedAccounts(context.Background(), userID)
+	if err != nil {
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to read linked Yahoo accounts",
+		})
+	}
+	guid := c.Query("guid")
+	if owners, err := a.leagueOwners(context.Background(), userID, []string{leagueKey}); err == nil && owners[leagueKey] != "" {
+		guid = owners[leagueKey]
+	}
+	acct, err := pickAccount(accounts, guid)
 	if err != nil {
 		return c.Status(fiber.StatusNotFound).JSON(ErrorResponse{
 			Status: "error",
 			Error:  "Yahoo account not connected",
 		})
 	}
+	guid = acct.guid
+
+	refreshToken, err := Decrypt(acct.encryptedToken)
+	if err != nil {
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to decrypt token",
+		})
+	}
+
+	ctx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
+	defer cancel()
+
+	client := NewYahooClient(os.Getenv("YAHOO_CLIENT_ID"), os.Getenv("YAHOO_CLIENT_SECRET"), refreshToken)
+	settings, err := client.GetLeagueSettings(ctx, leagueKey)
+
+	// Persist rotated refresh token if changed
+	if newToken := client.RefreshedToken(); newToken != "" && newToken != refreshToken {
+		if encrypted, err := Encrypt(newToken); err == nil {
+			a.updateRefreshToken(context.Background(), guid, encrypted)
+		}
+	}
+
+	if err != nil {
+		log.Printf("[LeagueSettings] Failed settings for %s: %v", leagueKey, err)
+		return c.Status(fiber.StatusBadGateway).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to fetch league settings from Yahoo",
+		})
+	}
+	return c.JSON(settings)
+}
+
+// dedupeLeagueKeys trims the keys and drops blanks and repeats, keeping
+// first-seen order.
+func dedupeLeagueKeys(keys []string) []string {
+	seen := make(map[string]bool, len(keys))
+	out := make([]string, 0, len(keys))
+	for _, k := range keys {
+		k = strings.TrimSpace(k)
+		if k == "" || seen[k] {
+			continue
+		}
+		seen[k] = true
+		out = append(out, k)
+	}
+	return out
+}
+
+// ImportYahooLeague imports a single league directly via the Yahoo Fantasy API.
+// Fetches league metadata, standings, matchups, and rosters, then persists
+// everything to the database and populates the Redis CDC subscriber set.
+//
+// The optional `guid` body field (as returned by discovery) picks which
+// linked Yahoo account the league is imported under; the oldest link is
+// used when it is empty.
+func (a *App) ImportYahooLeague(c *fiber.Ctx) error {
+	userID := GetUserSub(c)
+	if userID == "" {
+		return c.Status(fiber.StatusUnauthorized).JSON(ErrorResponse{
+			Status: "unauthorized",
+			Error:  "Authentication required",
+		})
+	}
 
 	// Parse the incoming request body
 	var incoming struct {
 		LeagueKey string `json:"league_key"`
 		GameCode  string `json:"game_code"`
 		Season    int    `json:"season"`
+		GUID      string `json:"guid"`
 	}
 	if err := c.BodyParser(&incoming); err != nil {
 		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
@@ -489,32 +796,43 @@ func (a *App) ImportYahooLeague(c *fiber.Ctx) error {
 		})
 	}
 
+	accounts, err := a.linkedAccounts(context.Background(), userID)
+	if err != nil {
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "Failed to read linked Yahoo accounts",
+		})
+	}
+	acct, err := pickAccount(accounts, incoming.GUID)
+	if err != nil {
+		return c.Status(fiber.StatusNotFound).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "Yahoo account not connected",
+		})
+	}
+	guid := acct.guid
+
 	// -------------------------------------------------------------------------
 	// Tier enforcement — cap on number of imported leagues per user.
 	//
 	// Re-importing an already-linked league does NOT count against the cap
 	// (it's effectively a refresh), so we only block when the user is
 	// adding a *new* league that would push them over their tier's limit.
+	// Both checks span all of the user's linked Yahoo accounts.
 	// -------------------------------------------------------------------------
 	tier := GetUserTier(c)
 	cap := FantasyLeagueCap(tier)
 	if cap != -1 {
-		var alreadyLinked bool
-		if err := a.db.QueryRow(context.Background(),
-			"SELECT EXISTS(SELECT 1 FROM yahoo_user_leagues WHERE guid = $1 AND league_key = $2)",
-			guid, incoming.LeagueKey,
-		).Scan(&alreadyLinked); err != nil {
+		owners, err := a.leagueOwners(context.Background(), userID, []string{incoming.LeagueKey})
+		if err != nil {
 			log.Printf("[Import] Failed to check existing league link for guid=%s league=%s: %v", guid, incoming.LeagueKey, err)
 			return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
 				Status: "error",
 				Error:  "Failed to verify league subscription",
 			})
 		}
-		if !alreadyLinked {
-			var currentCount int
-			if err := a.db.QueryRow(context.Background(),
-				"SELECT count(*) FROM yahoo_user_leagues WHERE guid = $1", guid,
-			).Scan(&currentCount); err != nil {
+		if _, alreadyLinked := owners[incoming.LeagueKey]; !alreadyLinked {
+			currentCount, err := a.userLeagueCount(context.Background(), userID)
+			if err != nil {
 				log.Printf("[Import] Failed to count leagues for guid=%s: %v", guid, err)
 				return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
 					Status: "error",
@@ -533,18 +851,7 @@ func (a *App) ImportYahooLeague(c *fiber.Ctx) error {
 		}
 	}
 
-	// Fetch + decrypt refresh token
-	var encryptedToken string
-	err = a.db.QueryRow(context.Background(),
-		"SELECT refresh_token FROM yahoo_users WHERE guid = $1", guid,
-	).Scan(&encryptedToken)
-	if err != nil {
-		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
-			Status: "error", Error: "Failed to read user token",
-		})
-	}
-
-	refreshToken, err := Decrypt(encryptedToken)
+	refreshToken, err := Decrypt(acct.encryptedToken)
 	if err != nil {
 		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
 			Status: "error", Error: "Failed to decrypt token",
@@ -748,7 +1055,8 @@ func (a *App) ImportYahooLeague(c *fiber.Ctx) error {
 }
 
 // DisconnectYahoo removes the user's Yahoo connection and all associated data,
-// including Redis CDC subscriber sets.
+// including Redis CDC subscriber sets. `?guid=` disconnects just that linked
+// account; without it every linked account is removed.
 func (a *App) DisconnectYahoo(c *fiber.Ctx) error {
 	userID := GetUserSub(c)
 	if userID == "" {
@@ -758,30 +1066,56 @@ func (a *App) DisconnectYahoo(c *fiber.Ctx) error {
 		})
 	}
 
-	// Look up the user's Yahoo GUID before deleting
-	var guid string
-	err := a.db.QueryRow(context.Background(),
-		"SELECT guid FROM yahoo_users WHERE logto_sub = $1", userID).Scan(&guid)
+	// Look up the user's Yahoo GUIDs before deleting
+	accounts, err := a.linkedAccounts(context.Background(), userID)
 	if err != nil {
-		return c.JSON(fiber.Map{"status": "ok", "message": "No Yahoo account connected"})
-	}
-
-	// Clean up Redis CDC subscriber sets and cache BEFORE deleting DB rows
-	// (we need the user_leagues data to know which sets to clean)
-	a.CleanupLeagueSubscribers(context.Background(), guid, userID)
-	a.invalidateLeagueCache(context.Background(), guid)
-
-	// Delete from yahoo_users — cascading deletes handle leagues, standings, etc.
-	_, err = a.db.Exec(context.Background(),
-		"DELETE FROM yahoo_users WHERE logto_sub = $1", userID)
-	if err != nil {
-		log.Printf("[DisconnectYahoo] Error deleting yahoo_users: %v", err)
+		log.Printf("[DisconnectYahoo] Error listing linked accounts: %v", err)
 		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
 			Status: "error",
 			Error:  "Failed to disconnect Yahoo account",
 		})
 	}
+	if guid := c.Query("guid"); guid != "" {
+		acct, err := pickAccount(accounts, guid)
+		if err != nil {
+			return c.JSON(fiber.Map{"status": "ok", "message": "No Yahoo account connected"})
+		}
+		accounts = []linkedAccount{acct}
+	}
+	if len(accounts) == 0 {
+		return c.JSON(fiber.Map{"status": "ok", "message": "No Yahoo account connected"})
+	}
+
+	for _, acct := range accounts {
+		// Clean up Redis CDC subscriber sets and cache BEFORE deleting DB rows
+		// (we need the user_leagues data to know which sets to clean)
+		a.CleanupLeagueSubscribers(context.Background(), acct.guid, userID)
+		a.invalidateLeagueCache(context.Background(), acct.guid)
+
+		// Delete from yahoo_users — cascading deletes handle leagues, standings, etc.
+		_, err = a.db.Exec(context.Background(),
+			"DELETE FROM yahoo_users WHERE guid = $1 AND logto_sub = $2", acct.guid, userID)
+		if err != nil {
+			log.Printf("[DisconnectYahoo] Error deleting yahoo_users: %v", err)
+			return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+				Status: "error",
+				Error:  "Failed to disconnect Yahoo account",
+			})
+		}
+
+		log.Printf("[DisconnectYahoo] User %s disconnected Yahoo (GUID: %s)", userID, acct.guid)
+	}
+
+	// A league shared by two linked accounts lost its subscriber entry above
+	// even if the other account is still linked; restore those.
+	remaining, err := a.linkedAccounts(context.Background(), userID)
+	if err == nil {
+		for _, acct := range remaining {
+			if err := a.PopulateLeagueSubscribers(context.Background(), acct.guid, userID); err != nil {
+				log.Printf("[DisconnectYahoo] Warning: failed to restore league subscribers for %s: %v", acct.guid, err)
+			}
+		}
+	}
 
-	log.Printf("[DisconnectYahoo] User %s disconnected Yahoo (GUID: %s)", userID, guid)
 	return c.JSON(fiber.Map{"status": "ok", "message": "Yahoo account disconnected"})
 }
@@ -4,24 +4,31 @@ import (
 	"context"
 	"encoding/json"
 	"encoding/xml"
+	"errors"
 	"fmt"
 	"io"
 	"log"
+	"math"
 	"math/rand"
 	"net/http"
 	"net/url"
 	"os"
+	"sort"
 	"strconv"
 	"strings"
 	"sync"
+	"sync/atomic"
 	"time"
+
+	"golang.org/x/sync/singleflight"
 )
 
 // =============================================================================
 // Yahoo Fantasy API Client
 //
 // Pure Go replacement for the yahoofantasy Python library.  Each user gets
-// their own YahooClient instance — no global state, no locks between users.
+// their own YahooClient instance — no locks between users.  The only shared
+// state is tokenRefreshes, which de-duplicates exchanges of one refresh token.
 //
 // API reference: https://fantasysports.yahooapis.com/fantasy/v2/
 // All responses are XML.  Headers: Authorization: Bearer, User-Agent: Mozilla/5.0
@@ -35,9 +42,13 @@ const (
 	// Default delay between Yahoo API calls (per-user rate limiting).
 	DefaultAPIDelay = 500 * time.Millisecond
 
-	// Retry configuration
-	maxRetries     = 3
-	retryBaseDelay = 1 * time.Second
+	// Retry configuration. Attempts per call are overridable via
+	// YAHOO_MAX_RETRIES; an expired-token refresh doesn't use one up.
+	defaultMaxRetries = 3
+	retryBaseDelay    = 1 * time.Second
+
+	// How much of an unparseable response body to keep in YahooParseError.
+	parseErrorSnippetLen = 300
 )
 
 // yahooBaseURL returns the Yahoo Fantasy API base URL, overridable via
@@ -58,13 +69,38 @@ func getYahooTokenURL() string {
 	return defaultYahooTokenURL
 }
 
+// ErrYahooTokenRevoked means Yahoo rejected the refresh token with
+// invalid_grant — the user revoked access or the grant expired. Unlike a
+// 5xx or timeout, retrying with the same token will never succeed.
+var ErrYahooTokenRevoked = errors.New("yahoo refresh token revoked (invalid_grant)")
+
+// getYahooMaxRetries returns the attempts withRetry makes per Yahoo call,
+// from YAHOO_MAX_RETRIES.
+func getYahooMaxRetries() int {
+	raw := os.Getenv("YAHOO_MAX_RETRIES")
+	if raw == "" {
+		return defaultMaxRetries
+	}
+	v, err := strconv.Atoi(raw)
+	if err != nil || v <= 0 {
+		log.Printf("[Yahoo] YAHOO_MAX_RETRIES=%q is invalid, defaulting to %d", raw, defaultMaxRetries)
+		return defaultMaxRetries
+	}
+	return v
+}
+
 // YahooClient is a per-user Yahoo Fantasy API client.  Each instance holds
 // its own access token and refresh token — no shared global state.
+//
+// httpClient is the transport seam: tests swap its Transport for an
+// http.RoundTripper that returns canned XML, so retry and token-refresh
+// behaviour can be exercised without Yahoo.
 type YahooClient struct {
 	httpClient   *http.Client
 	clientID     string
 	clientSecret string
 	apiDelay     time.Duration
+	maxRetries   int
 
 	mu           sync.Mutex // protects token fields within this client
 	accessToken  string
@@ -80,6 +116,7 @@ func NewYahooClient(clientID, clientSecret, refreshToken string) *YahooClient {
 		clientSecret: clientSecret,
 		refreshToken: refreshToken,
 		apiDelay:     DefaultAPIDelay,
+		maxRetries:   getYahooMaxRetries(),
 	}
 }
 
@@ -106,29 +143,76 @@ func (yc *YahooClient) refreshAccessToken(ctx context.Context) error {
 		return nil
 	}
 
+	// Another client holding the same refresh token (the active-sync worker
+	// and a user's own request build separate clients from the stored
+	// token) may be exchanging it right now. Join that exchange instead of
+	// starting a second one: Yahoo rotates the token on use, so whichever
+	// exchange lost the race would leave its caller with a dead token.
+	// The exchange is detached from ctx so one caller giving up doesn't fail
+	// the others waiting on it; httpClient's timeout still bounds it.
+	key := yc.clientID + "\x00" + yc.refreshToken
+	refreshToken := yc.refreshToken
+	v, err, _ := tokenRefreshes.Do(key, func() (any, error) {
+		yahooStats.tokenRefreshes.Add(1)
+		return yc.exchangeRefreshToken(context.WithoutCancel(ctx), refreshToken)
+	})
+	if err != nil {
+		return err
+	}
+	tok := v.(yahooToken)
+
+	yc.accessToken = tok.accessToken
+	yc.tokenExpiry = tok.expiry
+
+	// Yahoo rotates refresh tokens on each use
+	if tok.refreshToken != "" {
+		yc.refreshToken = tok.refreshToken
+	}
+
+	return nil
+}
+
+// tokenRefreshes collapses concurrent refreshes of the same refresh token
+// across YahooClient instances, keyed by client ID and refresh token.
+var tokenRefreshes singleflight.Group
+
+// yahooToken is the result of one refresh-token exchange, shared by every
+// caller that joined it.
+type yahooToken struct {
+	accessToken  string
+	refreshToken string
+	expiry       time.Time
+}
+
+// exchangeRefreshToken performs the token endpoint call itself. It only
+// touches the immutable client fields, so it runs without yc.mu.
+func (yc *YahooClient) exchangeRefreshToken(ctx context.Context, refreshToken string) (yahooToken, error) {
 	form := url.Values{
 		"client_id":     {yc.clientID},
 		"client_secret": {yc.clientSecret},
-		"refresh_token": {yc.refreshToken},
+		"refresh_token": {refreshToken},
 		"grant_type":    {"refresh_token"},
 	}
 
 	req, err := http.NewRequestWithContext(ctx, "POST", getYahooTokenURL(), strings.NewReader(form.Encode()))
 	if err != nil {
-		return fmt.Errorf("yahoo token request: %w", err)
+		return yahooToken{}, fmt.Errorf("yahoo token request: %w", err)
 	}
 	req.Header.Set("Content-Type", "application/x-www-form-urlencoded")
 
 	resp, err := yc.httpClient.Do(req)
 	if err != nil {
-		return fmt.Errorf("yahoo token exchange: %w", err)
+		return yahooToken{}, fmt.Errorf("yahoo token exchange: %w", err)
 	}
 	defer resp.Body.Close()
 
 	body, _ := io.ReadAll(resp.Body)
 
 	if resp.StatusCode != http.StatusOK {
-		return fmt.Errorf("yahoo token refresh failed (status %d): %s", resp.StatusCode, string(body))
+		if isInvalidGrant(resp.StatusCode, body) {
+			return yahooToken{}, fmt.Errorf("%w (status %d): %s", ErrYahooTokenRevoked, resp.StatusCode, string(body))
+		}
+		return yahooToken{}, fmt.Errorf("yahoo token refresh failed (status %d): %s", resp.StatusCode, string(body))
 	}
 
 	var tokenResp struct {
@@ -137,18 +221,29 @@ func (yc *YahooClient) refreshAccessToken(ctx context.Context) error {
 		ExpiresIn    int    `json:"expires_in"`
 	}
 	if err := json.Unmarshal(body, &tokenResp); err != nil {
-		return fmt.Errorf("yahoo token parse: %w", err)
+		return yahooToken{}, fmt.Errorf("yahoo token parse: %w", err)
 	}
 
-	yc.accessToken = tokenResp.AccessToken
-	yc.tokenExpiry = time.Now().Add(time.Duration(tokenResp.ExpiresIn) * time.Second)
+	return yahooToken{
+		accessToken:  tokenResp.AccessToken,
+		refreshToken: tokenResp.RefreshToken,
+		expiry:       time.Now().Add(time.Duration(tokenResp.ExpiresIn) * time.Second),
+	}, nil
+}
 
-	// Yahoo rotates refresh tokens on each use
-	if tokenResp.RefreshToken != "" {
-		yc.refreshToken = tokenResp.RefreshToken
+// isInvalidGrant reports whether a token endpoint error is the OAuth2
+// invalid_grant error (RFC 6749 §5.2), which Yahoo returns as a 400 or 401.
+func isInvalidGrant(status int, body []byte) bool {
+	if status != http.StatusBadRequest && status != http.StatusUnauthorized {
+		return false
 	}
-
-	return nil
+	var oauthErr struct {
+		Error string `json:"error"`
+	}
+	if json.Unmarshal(body, &oauthErr) == nil && oauthErr.Error != "" {
+		return oauthErr.Error == "invalid_grant"
+	}
+	return strings.Contains(string(body), "invalid_grant")
 }
 
 // ensureToken refreshes the access token if it's missing or expired.
@@ -169,47 +264,251 @@ func (yc *YahooClient) ensureToken(ctx context.Context) error {
 
 // makeRequest sends an authenticated GET to the Yahoo Fantasy API.
 // urlPath is appended to the base URL (e.g., "league/449.l.12345/standings").
+//
+// If Yahoo answers 401 token_expired (the access token died before our
+// recorded expiry), the token is refreshed and the request re-sent once
+// here, so the refresh doesn't spend one of withRetry's attempts.
 func (yc *YahooClient) makeRequest(ctx context.Context, urlPath string) ([]byte, error) {
-	if err := yc.ensureToken(ctx); err != nil {
-		return nil, err
-	}
-
 	fullURL := getYahooBaseURL() + "/" + urlPath
 
+	for refreshed := false; ; refreshed = true {
+		if err := yc.ensureToken(ctx); err != nil {
+			return nil, err
+		}
+
+		yc.mu.Lock()
+		token := yc.accessToken
+		yc.mu.Unlock()
+
+		status, header, body, err := yc.doGet(ctx, fullURL, token)
+		if err != nil {
+			return nil, err
+		}
+
+		if status == http.StatusUnauthorized && !refreshed && isTokenExpired(header, body) {
+			yc.expireAccessToken(token)
+			continue
+		}
+		if status != http.StatusOK {
+			return nil, fmt.Errorf("yahoo API error (status %d) for %s: %s", status, urlPath, truncate(string(body), 200))
+		}
+		return body, nil
+	}
+}
+
+// doGet performs a single bearer-authenticated GET and reads the body.
+func (yc *YahooClient) doGet(ctx context.Context, fullURL, token string) (int, http.Header, []byte, error) {
 	req, err := http.NewRequestWithContext(ctx, "GET", fullURL, nil)
 	if err != nil {
-		return nil, fmt.Errorf("yahoo request build: %w", err)
+		return 0, nil, nil, fmt.Errorf("yahoo request build: %w", err)
 	}
-
-	yc.mu.Lock()
-	token := yc.accessToken
-	yc.mu.Unlock()
-
 	req.Header.Set("Authorization", "Bearer "+token)
 	req.Header.Set("User-Agent", yahooUA)
 
+	start := time.Now()
+	defer func() { yahooStats.latency.record(time.Since(start)) }()
 	resp, err := yc.httpClient.Do(req)
 	if err != nil {
-		return nil, fmt.Errorf("yahoo request: %w", err)
+		return 0, nil, nil, fmt.Errorf("yahoo request: %w", err)
 	}
 	defer resp.Body.Close()
 
 	body, err := io.ReadAll(resp.Body)
 	if err != nil {
-		return nil, fmt.Errorf("yahoo read body: %w", err)
+		return 0, nil, nil, fmt.Errorf("yahoo read body: %w", err)
 	}
+	return resp.StatusCode, resp.Header, body, nil
+}
 
-	if resp.StatusCode != http.StatusOK {
-		return nil, fmt.Errorf("yahoo API error (status %d) for %s: %s", resp.StatusCode, urlPath, truncate(string(body), 200))
+// expireAccessToken drops the access token so the next ensureToken
+// refreshes it. A no-op if another goroutine already replaced `stale`.
+func (yc *YahooClient) expireAccessToken(stale string) {
+	yc.mu.Lock()
+	defer yc.mu.Unlock()
+	if yc.accessToken == stale {
+		yc.accessToken = ""
+		yc.tokenExpiry = time.Time{}
 	}
+}
+
+// isTokenExpired reports whether a 401 is Yahoo's expired-access-token
+// response. Yahoo puts oauth_problem="token_expired" in WWW-Authenticate,
+// and sometimes only in the body.
+func isTokenExpired(header http.Header, body []byte) bool {
+	return strings.Contains(header.Get("WWW-Authenticate"), "token_expired") ||
+		strings.Contains(string(body), "token_expired")
+}
+
+// YahooParseError is returned when Yahoo answers 200 but the body doesn't
+// unmarshal into the expected shape. It carries the start of the raw body
+// so operators can see what Yahoo actually sent instead of a bare
+// "XML syntax error on line 1".
+type YahooParseError struct {
+	Resource string // e.g. "standings", "roster"
+	Snippet  string // leading bytes of the body, whitespace-trimmed
+	Err      error
+}
+
+func (e *YahooParseError) Error() string {
+	return fmt.Sprintf("parse %s XML: %v (body: %q)", e.Resource, e.Err, e.Snippet)
+}
+
+func (e *YahooParseError) Unwrap() error { return e.Err }
+
+// unmarshalYahooXML decodes a Yahoo response, wrapping failures in a
+// YahooParseError that records a truncated snippet of the raw body.
+func unmarshalYahooXML(body []byte, resource string, v any) error {
+	if err := xml.Unmarshal(body, v); err != nil {
+		return &YahooParseError{
+			Resource: resource,
+			Snippet:  truncate(strings.TrimSpace(string(body)), parseErrorSnippetLen),
+			Err:      err,
+		}
+	}
+	return nil
+}
+
+// yahooAPIStats counts, process-wide, how hard the Yahoo calls are working
+// to succeed. Clients are built per user, so the counters live at package
+// level. A jump in token refreshes or retries tends to come before a Yahoo
+// outage shows up as outright failures.
+type yahooAPIStats struct {
+	tokenRefreshes atomic.Int64 // token endpoint exchanges (joined refreshes count once)
+	retriesUsed    atomic.Int64 // withRetry attempts after the first
+	firstTry       atomic.Int64 // withRetry calls that succeeded on attempt one
+	latency        latencyWindow
+}
+
+var yahooStats yahooAPIStats
+
+func (s *yahooAPIStats) snapshot() map[string]int64 {
+	out := map[string]int64{
+		"token_refreshes":                   s.tokenRefreshes.Load(),
+		"retries_used":                      s.retriesUsed.Load(),
+		"requests_that_succeeded_first_try": s.firstTry.Load(),
+	}
+	for k, v := range s.latency.summary() {
+		out[k] = v
+	}
+	return out
+}
+
+// unmappedStatIDs records, per sport, the stat ids rosters carried that the
+// league's scoring modifiers had no entry for, i.e. the stats that added
+// nothing to a synthetic points total. Some are expected (games played and
+// other unscored counters); an id that should be scoring points here is why
+// a roster came back with zeros. Served by GET /yahoo/debug/unmapped-stats
+// and reset at the start of each sync cycle, when every league's stat
+// catalog is fetched again.
+type unmappedStatIDs struct {
+	mu      sync.Mutex
+	bySport map[string]map[string]struct{}
+}
+
+var unmappedStats = &unmappedStatIDs{bySport: map[string]map[string]struct{}{}}
+
+func (u *unmappedStatIDs) add(sport, statID string) {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	ids, ok := u.bySport[sport]
+	if !ok {
+		ids = map[string]struct{}{}
+		u.bySport[sport] = ids
+	}
+	ids[statID] = struct{}{}
+}
+
+// snapshot returns sport -> sorted stat ids.
+func (u *unmappedStatIDs) snapshot() map[string][]string {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	out := make(map[string][]string, len(u.bySport))
+	for sport, ids := range u.bySport {
+		list := make([]string, 0, len(ids))
+		for id := range ids {
+			list = append(list, id)
+		}
+		sort.Slice(list, func(i, j int) bool {
+			a, aErr := strconv.Atoi(list[i])
+			b, bErr := strconv.Atoi(list[j])
+			if aErr == nil && bErr == nil {
+				return a < b
+			}
+			return list[i] < list[j]
+		})
+		out[sport] = list
+	}
+	return out
+}
+
+func (u *unmappedStatIDs) clear() {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	u.bySport = map[string]map[string]struct{}{}
+}
+
+// yahooLatencyWindow is how many recent Yahoo API requests the latency
+// figures in /health cover.
+const yahooLatencyWindow = 256
 
-	return body, nil
+// latencyWindow keeps the durations of the last yahooLatencyWindow requests
+// so a Yahoo slowdown shows up in /health before calls start timing out.
+type latencyWindow struct {
+	mu      sync.Mutex
+	samples []time.Duration
+	next    int
+}
+
+func (w *latencyWindow) record(d time.Duration) {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	if len(w.samples) < yahooLatencyWindow {
+		w.samples = append(w.samples, d)
+		return
+	}
+	w.samples[w.next] = d
+	w.next = (w.next + 1) % yahooLatencyWindow
+}
+
+// summary returns sample count, min, mean, nearest-rank p50/p95 and max in
+// milliseconds, all zero before the first request.
+func (w *latencyWindow) summary() map[string]int64 {
+	w.mu.Lock()
+	sorted := append([]time.Duration(nil), w.samples...)
+	w.mu.Unlock()
+
+	out := map[string]int64{
+		"latency_samples": int64(len(sorted)),
+		"latency_min_ms":  0,
+		"latency_avg_ms":  0,
+		"latency_p50_ms":  0,
+		"latency_p95_ms":  0,
+		"latency_max_ms":  0,
+	}
+	n := len(sorted)
+	if n == 0 {
+		return out
+	}
+	sort.Slice(sorted, func(i, j int) bool { return sorted[i] < sorted[j] })
+	var total time.Duration
+	for _, d := range sorted {
+		total += d
+	}
+	percentile := func(p int) int64 {
+		return sorted[(n*p+99)/100-1].Milliseconds()
+	}
+	out["latency_min_ms"] = sorted[0].Milliseconds()
+	out["latency_avg_ms"] = (total / time.Duration(n)).Milliseconds()
+	out["latency_p50_ms"] = percentile(50)
+	out["latency_p95_ms"] = percentile(95)
+	out["latency_max_ms"] = sorted[n-1].Milliseconds()
+	return out
 }
 
 // withRetry wraps a function with exponential backoff retry and per-user API delay.
 func (yc *YahooClient) withRetry(ctx context.Context, label string, fn func() error) error {
 	var lastErr error
-	for attempt := 0; attempt < maxRetries; attempt++ {
+	for attempt := 0; attempt < yc.maxRetries; attempt++ {
 		// Per-user API delay before each attempt
 		select {
 		case <-ctx.Done():
@@ -217,19 +516,28 @@ func (yc *YahooClient) withRetry(ctx context.Context, label string, fn func() er
 		case <-time.After(yc.apiDelay):
 		}
 
+		if attempt > 0 {
+			yahooStats.retriesUsed.Add(1)
+		}
 		lastErr = fn()
 		if lastErr == nil {
+			if attempt == 0 {
+				yahooStats.firstTry.Add(1)
+			}
 			return nil
 		}
+		if errors.Is(lastErr, ErrYahooTokenRevoked) {
+			return lastErr
+		}
 
-		if attempt == maxRetries-1 {
+		if attempt == yc.maxRetries-1 {
 			break
 		}
 
 		// Exponential backoff with jitter
 		delay := retryBaseDelay * time.Duration(1<<uint(attempt))
 		jitter := time.Duration(rand.Int63n(int64(500 * time.Millisecond)))
-		log.Printf("[Retry] %s attempt %d/%d failed, retrying in %v: %v", label, attempt+1, maxRetries, delay+jitter, lastErr)
+		log.Printf("[Retry] %s attempt %d/%d failed, retrying in %v: %v", label, attempt+1, yc.maxRetries, delay+jitter, lastErr)
 
 		select {
 		case <-ctx.Done():
@@ -238,7 +546,7 @@ func (yc *YahooClient) withRetry(ctx context.Context, label string, fn func() er
 		}
 	}
 
-	log.Printf("[Retry] %s failed after %d attempts: %v", label, maxRetries, lastErr)
+	log.Printf("[Retry] %s failed after %d attempts: %v", label, yc.maxRetries, lastErr)
 	return lastErr
 }
 
@@ -266,28 +574,7 @@ func (yc *YahooClient) GetLeagues(ctx context.Context, gameCode string, season i
 		return nil, err
 	}
 
-	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return nil, fmt.Errorf("parse leagues XML: %w", err)
-	}
-
-	var result []map[string]any
-
-	if fc.Users == nil {
-		return result, nil
-	}
-	for _, user := range fc.Users.User {
-		if user.Games == nil {
-			continue
-		}
-		for _, game := range user.Games.Game {
-			for _, league := range game.Leagues.League {
-				result = append(result, serializeLeague(league, gameCode))
-			}
-		}
-	}
-
-	return result, nil
+	return parseLeaguesXML(xmlBody, gameCode)
 }
 
 // GetStandings fetches standings for a league.
@@ -305,16 +592,7 @@ func (yc *YahooClient) GetStandings(ctx context.Context, leagueKey string) ([]ma
 		return nil, err
 	}
 
-	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return nil, fmt.Errorf("parse standings XML: %w", err)
-	}
-
-	if fc.League == nil || fc.League.Standings == nil {
-		return nil, nil
-	}
-
-	return serializeStandings(fc.League.Standings.Teams.Team), nil
+	return parseStandingsXML(xmlBody)
 }
 
 // GetScoreboard fetches matchups for a specific week.
@@ -332,17 +610,7 @@ func (yc *YahooClient) GetScoreboard(ctx context.Context, leagueKey string, week
 		return 0, nil, err
 	}
 
-	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return 0, nil, fmt.Errorf("parse scoreboard XML: %w", err)
-	}
-
-	if fc.League == nil || fc.League.Scoreboard == nil {
-		return week, nil, nil
-	}
-
-	weekNum, matchups := serializeScoreboard(fc.League.Scoreboard, week)
-	return weekNum, matchups, nil
+	return parseScoreboardXML(xmlBody, week)
 }
 
 // GetTeams fetches all teams in a league.  Returns the raw XML team structs
@@ -361,8 +629,8 @@ func (yc *YahooClient) GetTeams(ctx context.Context, leagueKey string) ([]XMLTea
 	}
 
 	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return nil, fmt.Errorf("parse teams XML: %w", err)
+	if err := unmarshalYahooXML(xmlBody, "teams", &fc); err != nil {
+		return nil, err
 	}
 
 	if fc.League == nil || fc.League.Teams == nil {
@@ -381,6 +649,25 @@ func (yc *YahooClient) GetTeams(ctx context.Context, leagueKey string) ([]XMLTea
 // The returned Modifiers map is empty (not nil) for pure categories leagues
 // like MLB H2H cats where no single-number scoring applies.
 func (yc *YahooClient) GetLeagueStatCatalog(ctx context.Context, leagueKey string) (*LeagueStatCatalog, error) {
+	xmlBody, err := yc.getLeagueSettingsXML(ctx, leagueKey)
+	if err != nil {
+		return nil, err
+	}
+	return parseLeagueSettingsXML(xmlBody)
+}
+
+// GetLeagueSettings fetches the league's settings (name, scoring type,
+// roster slots, enabled stat categories, playoff start) from the same
+// league/{key}/settings resource GetLeagueStatCatalog reads.
+func (yc *YahooClient) GetLeagueSettings(ctx context.Context, leagueKey string) (*LeagueSettings, error) {
+	xmlBody, err := yc.getLeagueSettingsXML(ctx, leagueKey)
+	if err != nil {
+		return nil, err
+	}
+	return parseLeagueSettingsSummaryXML(xmlBody, leagueKey)
+}
+
+func (yc *YahooClient) getLeagueSettingsXML(ctx context.Context, leagueKey string) ([]byte, error) {
 	urlPath := fmt.Sprintf("league/%s/settings", leagueKey)
 
 	var xmlBody []byte
@@ -389,54 +676,7 @@ func (yc *YahooClient) GetLeagueStatCatalog(ctx context.Context, leagueKey strin
 		xmlBody, reqErr = yc.makeRequest(ctx, urlPath)
 		return reqErr
 	})
-	if err != nil {
-		return nil, err
-	}
-
-	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return nil, fmt.Errorf("parse league settings XML: %w", err)
-	}
-
-	if fc.League == nil || fc.League.Settings == nil {
-		return &LeagueStatCatalog{Stats: []StatCatalogEntry{}, Modifiers: map[string]float64{}}, nil
-	}
-
-	// Parse <stat_categories><stats><stat> into our JSON-serializable form.
-	defs := fc.League.Settings.StatCategories.Stats.Stat
-	stats := make([]StatCatalogEntry, 0, len(defs))
-	for _, d := range defs {
-		if d.StatID == "" {
-			continue
-		}
-		// Enabled may be absent; when present only "1" counts.
-		if d.Enabled != "" && d.Enabled != "1" {
-			continue
-		}
-		sortOrder, _ := strconv.Atoi(d.SortOrder)
-		stats = append(stats, StatCatalogEntry{
-			StatID:       d.StatID,
-			DisplayName:  d.DisplayName,
-			Name:         d.Name,
-			PositionType: d.PositionType,
-			SortOrder:    sortOrder,
-			DisplayOnly:  d.IsOnlyDisplayStat == "1",
-		})
-	}
-
-	modifiers := map[string]float64{}
-	for _, mod := range fc.League.Settings.StatModifiers.Stats.Stat {
-		if mod.StatID == "" {
-			continue
-		}
-		v, err := strconv.ParseFloat(mod.Value, 64)
-		if err != nil {
-			continue
-		}
-		modifiers[mod.StatID] = v
-	}
-
-	return &LeagueStatCatalog{Stats: stats, Modifiers: modifiers}, nil
+	return xmlBody, err
 }
 
 // GetRoster fetches the live roster for a team. When `week` > 0 we request
@@ -457,17 +697,24 @@ func (yc *YahooClient) GetRoster(
 	week int,
 	statModifiers map[string]float64,
 ) (map[string]any, error) {
-	// Note: roster URL uses team key directly (not league-prefixed)
-	var urlPath string
-	if week > 0 {
-		// Pin the lineup AND the stats to the same week so player_points is populated.
-		urlPath = fmt.Sprintf("team/%s/roster;week=%d/players/stats;type=week;week=%d", teamKey, week, week)
-	} else {
-		urlPath = fmt.Sprintf("team/%s/roster;", teamKey)
-	}
+	return yc.GetRosterPage(ctx, teamKey, leagueKey, teamName, week, statModifiers, 0, 0)
+}
+
+// GetRosterPage is GetRoster restricted to one page of players via Yahoo's
+// `;start=;count=` players filter. A count <= 0 fetches every player in a
+// single request, which is what GetRoster does. Rosters never need paging;
+// this exists so the same parser can walk multi-thousand-player pools.
+func (yc *YahooClient) GetRosterPage(
+	ctx context.Context,
+	teamKey, leagueKey, teamName string,
+	week int,
+	statModifiers map[string]float64,
+	start, count int,
+) (map[string]any, error) {
+	urlPath := rosterURLPath(teamKey, week, start, count)
 
 	var xmlBody []byte
-	err := yc.withRetry(ctx, fmt.Sprintf("roster(%s,week=%d)", teamKey, week), func() error {
+	err := yc.withRetry(ctx, fmt.Sprintf("roster(%s,week=%d,start=%d,count=%d)", teamKey, week, start, count), func() error {
 		var reqErr error
 		xmlBody, reqErr = yc.makeRequest(ctx, urlPath)
 		return reqErr
@@ -476,20 +723,24 @@ func (yc *YahooClient) GetRoster(
 		return nil, err
 	}
 
-	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return nil, fmt.Errorf("parse roster XML: %w", err)
-	}
+	return parseRosterXML(xmlBody, teamKey, teamName, statModifiers)
+}
 
-	if fc.Team == nil || fc.Team.Roster == nil {
-		return map[string]any{
-			"team_key":  teamKey,
-			"team_name": teamName,
-			"players":   []any{},
-		}, nil
+// rosterURLPath builds the roster request path. Note the roster URL uses
+// the team key directly (not league-prefixed).
+func rosterURLPath(teamKey string, week, start, count int) string {
+	players := "players"
+	if count > 0 {
+		players = fmt.Sprintf("players;start=%d;count=%d", start, count)
 	}
-
-	return serializeRoster(fc.Team.Roster.Players.Player, teamKey, teamName, statModifiers), nil
+	if week > 0 {
+		// Pin the lineup AND the stats to the same week so player_points is populated.
+		return fmt.Sprintf("team/%s/roster;week=%d/%s/stats;type=week;week=%d", teamKey, week, players, week)
+	}
+	if count > 0 {
+		return fmt.Sprintf("team/%s/roster/%s", teamKey, players)
+	}
+	return fmt.Sprintf("team/%s/roster;", teamKey)
 }
 
 // GetTeamDailyStats fetches each player's stats for a single ISO-8601 date
@@ -521,8 +772,8 @@ func (yc *YahooClient) GetTeamDailyStats(
 	}
 
 	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return nil, fmt.Errorf("parse daily roster XML: %w", err)
+	if err := unmarshalYahooXML(xmlBody, "daily roster", &fc); err != nil {
+		return nil, err
 	}
 
 	if fc.Team == nil || fc.Team.Roster == nil {
@@ -559,8 +810,8 @@ func (yc *YahooClient) GetUserGUID(ctx context.Context) (string, error) {
 	}
 
 	var fc FantasyContent
-	if err := xml.Unmarshal(xmlBody, &fc); err != nil {
-		return "", fmt.Errorf("parse user XML: %w", err)
+	if err := unmarshalYahooXML(xmlBody, "user", &fc); err != nil {
+		return "", err
 	}
 
 	if fc.Users == nil || len(fc.Users.User) == 0 {
@@ -570,6 +821,189 @@ func (yc *YahooClient) GetUserGUID(ctx context.Context) (string, error) {
 	return fc.Users.User[0].Guid, nil
 }
 
+// ---------------------------------------------------------------------------
+// Response parsing — raw XML body in, serialized data out. Kept separate from
+// the fetchers above so the parsers can be tested against captured fixtures
+// (testdata/*.xml) without a Yahoo connection.
+// ---------------------------------------------------------------------------
+
+// parseLeaguesXML parses a users;use_login=1/games/leagues response.
+func parseLeaguesXML(body []byte, gameCode string) ([]map[string]any, error) {
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "leagues", &fc); err != nil {
+		return nil, err
+	}
+
+	var result []map[string]any
+
+	if fc.Users == nil {
+		return result, nil
+	}
+	for _, user := range fc.Users.User {
+		if user.Games == nil {
+			continue
+		}
+		for _, game := range user.Games.Game {
+			for _, league := range game.Leagues.League {
+				result = append(result, serializeLeague(league, gameCode))
+			}
+		}
+	}
+
+	return result, nil
+}
+
+// parseStandingsXML parses a league/{key}/standings response. Returns nil
+// when the league has no standings block yet (pre-draft).
+func parseStandingsXML(body []byte) ([]map[string]any, error) {
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "standings", &fc); err != nil {
+		return nil, err
+	}
+
+	if fc.League == nil || fc.League.Standings == nil {
+		return nil, nil
+	}
+
+	return serializeStandings(fc.League.Standings.Teams.Team), nil
+}
+
+// parseScoreboardXML parses a league/{key}/scoreboard response. `week` is
+// the requested week, used when the body doesn't name one.
+func parseScoreboardXML(body []byte, week int) (int, []map[string]any, error) {
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "scoreboard", &fc); err != nil {
+		return 0, nil, err
+	}
+
+	if fc.League == nil || fc.League.Scoreboard == nil {
+		return week, nil, nil
+	}
+
+	weekNum, matchups := serializeScoreboard(fc.League.Scoreboard, week)
+	return weekNum, matchups, nil
+}
+
+// parseLeagueSettingsXML parses a league/{key}/settings response into the
+// stat catalog and scoring modifiers.
+func parseLeagueSettingsXML(body []byte) (*LeagueStatCatalog, error) {
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "league settings", &fc); err != nil {
+		return nil, err
+	}
+
+	if fc.League == nil || fc.League.Settings == nil {
+		return &LeagueStatCatalog{Stats: []StatCatalogEntry{}, Modifiers: map[string]float64{}}, nil
+	}
+
+	stats := statCatalogEntries(fc.League.Settings.StatCategories.Stats.Stat)
+
+	modifiers := map[string]float64{}
+	for _, mod := range fc.League.Settings.StatModifiers.Stats.Stat {
+		if mod.StatID == "" {
+			continue
+		}
+		v, err := strconv.ParseFloat(mod.Value, 64)
+		if err != nil {
+			continue
+		}
+		modifiers[mod.StatID] = v
+	}
+
+	return &LeagueStatCatalog{Stats: stats, Modifiers: modifiers}, nil
+}
+
+// parseLeagueSettingsSummaryXML parses a league/{key}/settings response
+// into LeagueSettings. Missing elements leave their field zero, nil or
+// empty rather than failing; leagueKey fills in when the response lacks one.
+func parseLeagueSettingsSummaryXML(body []byte, leagueKey string) (*LeagueSettings, error) {
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "league settings", &fc); err != nil {
+		return nil, err
+	}
+
+	out := &LeagueSettings{
+		LeagueKey:       leagueKey,
+		RosterPositions: []RosterPositionSetting{},
+		StatCategories:  []StatCatalogEntry{},
+	}
+	if fc.League == nil {
+		return out, nil
+	}
+	if fc.League.LeagueKey != "" {
+		out.LeagueKey = fc.League.LeagueKey
+	}
+	out.Name = fc.League.Name
+	out.ScoringType = fc.League.ScoringType
+	out.NumTeams = safeAtoiPtr(&fc.League.NumTeams)
+
+	settings := fc.League.Settings
+	if settings == nil {
+		return out, nil
+	}
+	if out.ScoringType == "" {
+		out.ScoringType = settings.ScoringType
+	}
+	out.PlayoffStartWeek = safeAtoiPtr(&settings.PlayoffStartWeek)
+	out.StatCategories = statCatalogEntries(settings.StatCategories.Stats.Stat)
+	for _, rp := range settings.RosterPositions.RosterPosition {
+		if rp.Position == "" {
+			continue
+		}
+		out.RosterPositions = append(out.RosterPositions, RosterPositionSetting{
+			Position:     rp.Position,
+			PositionType: rp.PositionType,
+			Count:        safeAtoi(rp.Count),
+			IsStarting:   rp.IsStartingPosition == "1",
+		})
+	}
+	return out, nil
+}
+
+// statCatalogEntries converts <stat_categories><stats><stat> into our
+// JSON-serializable form, keeping only enabled stats.
+func statCatalogEntries(defs []XMLStatDef) []StatCatalogEntry {
+	stats := make([]StatCatalogEntry, 0, len(defs))
+	for _, d := range defs {
+		if d.StatID == "" {
+			continue
+		}
+		// Enabled may be absent; when present only "1" counts.
+		if d.Enabled != "" && d.Enabled != "1" {
+			continue
+		}
+		sortOrder, _ := strconv.Atoi(d.SortOrder)
+		stats = append(stats, StatCatalogEntry{
+			StatID:       d.StatID,
+			DisplayName:  d.DisplayName,
+			Name:         d.Name,
+			PositionType: d.PositionType,
+			SortOrder:    sortOrder,
+			DisplayOnly:  d.IsOnlyDisplayStat == "1",
+		})
+	}
+	return stats
+}
+
+// parseRosterXML parses a team/{key}/roster response. A team with no
+// roster block yields an empty player list rather than an error.
+func parseRosterXML(body []byte, teamKey, teamName string, statModifiers map[string]float64) (map[string]any, error) {
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "roster", &fc); err != nil {
+		return nil, err
+	}
+
+	if fc.Team == nil || fc.Team.Roster == nil {
+		return map[string]any{
+			"team_key":  teamKey,
+			"team_name": teamName,
+			"players":   []any{},
+		}, nil
+	}
+
+	return serializeRoster(fc.Team.Roster.Players.Player, teamKey, teamName, statModifiers), nil
+}
+
 // =============================================================================
 // Serializers — convert XML structs to JSON maps for Postgres JSONB columns.
 // Produces the EXACT same shapes as the Python serializers.py.
@@ -620,9 +1054,12 @@ func computeIsFinished(raw *string, season int) bool {
 }
 
 // serializeStandings converts XML team standings to the JSON array stored
-// in yahoo_standings.data.
+// in yahoo_standings.data, ordered by rankStandings.
 func serializeStandings(teams []XMLTeamStanding) []map[string]any {
 	result := make([]map[string]any, 0, len(teams))
+	// Sort keys, parsed once per team rather than on every comparison.
+	pcts := make([]float64, 0, len(teams))
+	pfs := make([]float64, 0, len(teams))
 
 	for _, t := range teams {
 		ts := t.TeamStandingsData
@@ -658,37 +1095,77 @@ func serializeStandings(teams []XMLTeamStanding) []map[string]any {
 			pointsAgainst = "0"
 		}
 
+		// Numeric twins of the raw strings, so clients can sort and format
+		// without re-parsing Yahoo's ".857"-style values.
+		pctValue := parseStandingsNumber(t.TeamKey, "percentage", percentage)
+		gamesBackValue := parseStandingsNumber(t.TeamKey, "games_back", gamesBack)
+		pointsForValue := parseStandingsNumber(t.TeamKey, "points_for", pointsFor)
+		pointsAgainstValue := parseStandingsNumber(t.TeamKey, "points_against", pointsAgainst)
+
 		clinched := false
 		if t.ClinchPlayoffs != nil && *t.ClinchPlayoffs == "1" {
 			clinched = true
 		}
 
 		result = append(result, map[string]any{
-			"team_key":          t.TeamKey,
-			"team_id":           safeAtoi(t.TeamID),
-			"name":              t.Name,
-			"url":               t.URL,
-			"team_logo":         extractTeamLogo(t.TeamLogos, t.TeamLogo),
-			"manager_name":      extractManagerName(t.Managers),
-			"rank":              rank,
-			"wins":              wins,
-			"losses":            losses,
-			"ties":              ties,
-			"percentage":        percentage,
-			"games_back":        gamesBack,
-			"points_for":        pointsFor,
-			"points_against":    pointsAgainst,
-			"streak_type":       streakType,
-			"streak_value":      streakValue,
-			"playoff_seed":      playoffSeed,
-			"clinched_playoffs": clinched,
-			"waiver_priority":   safeAtoiPtr(t.WaiverPriority),
+			"team_key":             t.TeamKey,
+			"team_id":              safeAtoi(t.TeamID),
+			"name":                 t.Name,
+			"url":                  t.URL,
+			"team_logo":            extractTeamLogo(t.TeamLogos, t.TeamLogo),
+			"manager_name":         extractManagerName(t.Managers),
+			"rank":                 rank,
+			"wins":                 wins,
+			"losses":               losses,
+			"ties":                 ties,
+			"percentage":           percentage,
+			"percentage_value":     pctValue,
+			"games_back":           gamesBack,
+			"games_back_value":     gamesBackValue,
+			"points_for":           pointsFor,
+			"points_for_value":     pointsForValue,
+			"points_against":       pointsAgainst,
+			"points_against_value": pointsAgainstValue,
+			"streak_type":          streakType,
+			"streak_value":         streakValue,
+			"playoff_seed":         playoffSeed,
+			"clinched_playoffs":    clinched,
+			"waiver_priority":      safeAtoiPtr(t.WaiverPriority),
 		})
+		pcts = append(pcts, pctValue)
+		pfs = append(pfs, pointsForValue)
 	}
 
+	rankStandings(result, pcts, pfs)
 	return result
 }
 
+// rankStandings sorts serialized standings by win percentage, then
+// points_for, and numbers them 1..n in "computed_rank". Yahoo's own "rank"
+// is left as sent: it is null before the season and follows each league's
+// tiebreak settings, so clients that want a total order use computed_rank.
+// The sort is stable, so teams tied on both keys keep Yahoo's order.
+func rankStandings(standings []map[string]any, pcts, pfs []float64) {
+	order := make([]int, len(standings))
+	for i := range order {
+		order[i] = i
+	}
+	sort.SliceStable(order, func(a, b int) bool {
+		i, j := order[a], order[b]
+		if pcts[i] != pcts[j] {
+			return pcts[i] > pcts[j]
+		}
+		return pfs[i] > pfs[j]
+	})
+
+	sorted := make([]map[string]any, len(standings))
+	for pos, i := range order {
+		standings[i]["computed_rank"] = pos + 1
+		sorted[pos] = standings[i]
+	}
+	copy(standings, sorted)
+}
+
 // serializeScoreboard converts XML scoreboard data to (weekNum, matchups).
 func serializeScoreboard(sb *XMLScoreboard, fallbackWeek int) (int, []map[string]any) {
 	result := make([]map[string]any, 0, len(sb.Matchups.Matchup))
@@ -821,6 +1298,7 @@ func serializeRoster(
 			for statID, raw := range playerStats {
 				mod, ok := statModifiers[statID]
 				if !ok {
+					unmappedStats.add(gameCodeForKey(teamKey), statID)
 					continue
 				}
 				v, err := strconv.ParseFloat(raw, 64)
@@ -880,9 +1358,17 @@ func serializeRoster(
 // =============================================================================
 
 // extractTeamLogo gets the logo URL from XML structures (handles multiple nesting patterns).
+// New or unconfigured teams can come back with no <team_logos>, an empty
+// one, or a <team_logo> whose <url/> is blank; all of those use the
+// fallback (usually "") rather than failing the whole standings parse.
 func extractTeamLogo(logos *XMLTeamLogos, fallback string) string {
-	if logos != nil && len(logos.TeamLogo) > 0 {
-		return logos.TeamLogo[0].URL
+	if logos == nil {
+		return fallback
+	}
+	for _, l := range logos.TeamLogo {
+		if l.URL != "" {
+			return l.URL
+		}
 	}
 	return fallback
 }
@@ -931,6 +1417,23 @@ func safeAtoiPtr(s *string) *int {
 	return &v
 }
 
+// parseStandingsNumber parses a standings value such as Yahoo's ".857" or
+// "842.36". Blank and "-" (Yahoo's games_back for the leader) are 0;
+// anything else unparseable is 0 with a warning naming the team and field,
+// so one malformed value can't break the table.
+func parseStandingsNumber(teamKey, field, raw string) float64 {
+	raw = strings.TrimSpace(raw)
+	if raw == "" || raw == "-" {
+		return 0
+	}
+	v, err := strconv.ParseFloat(raw, 64)
+	if err != nil || math.IsNaN(v) || math.IsInf(v, 0) {
+		log.Printf("[Standings] %s: unparseable %s %q, using 0", teamKey, field, raw)
+		return 0
+	}
+	return v
+}
+
 // ptrOrDefault dereferences a *string, returning a default if nil.
 func ptrOrDefault(s *string, def string) string {
 	if s == nil {
@@ -0,0 +1,175 @@
+package main
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"log"
+)
+
+// errYahooAccountNotLinked is returned when a user has no linked Yahoo
+// account, or none with the requested guid.
+var errYahooAccountNotLinked = errors.New("yahoo account not linked")
+
+// linkedAccount is one Yahoo account linked to a Scrollr user. A user may
+// link several; each keeps its own refresh token and imported leagues.
+type linkedAccount struct {
+	guid           string
+	encryptedToken string
+	lastSync       sql.NullTime
+}
+
+// linkedAccounts returns every Yahoo account linked to logtoSub, oldest link
+// first. An unlinked user gets an empty slice, not an error.
+func (a *App) linkedAccounts(ctx context.Context, logtoSub string) ([]linkedAccount, error) {
+	rows, err := a.db.Query(ctx, `
+		SELECT guid, refresh_token, last_sync
+		FROM yahoo_users
+		WHERE logto_sub = $1
+		ORDER BY created_at, guid
+	`, logtoSub)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	var accounts []linkedAccount
+	for rows.Next() {
+		var acct linkedAccount
+		if err := rows.Scan(&acct.guid, &acct.encryptedToken, &acct.lastSync); err != nil {
+			return nil, err
+		}
+		accounts = append(accounts, acct)
+	}
+	return accounts, rows.Err()
+}
+
+// pickAccount returns the account with the given guid, or the oldest link
+// when guid is empty. Requests that act on a single Yahoo account use it so
+// a caller can only ever name one of their own accounts.
+func pickAccount(accounts []linkedAccount, guid string) (linkedAccount, error) {
+	for _, acct := range accounts {
+		if guid == "" || acct.guid == guid {
+			return acct, nil
+		}
+	}
+	return linkedAccount{}, errYahooAccountNotLinked
+}
+
+// allSynced reports whether every account has completed at least one sync.
+func allSynced(accounts []linkedAccount) bool {
+	for _, acct := range accounts {
+		if !acct.lastSync.Valid {
+			return false
+		}
+	}
+	return len(accounts) > 0
+}
+
+// leagueOwners maps each of leagueKeys the user has already imported to the
+// linked account it was imported under (the oldest, if several).
+func (a *App) leagueOwners(ctx context.Context, logtoSub string, leagueKeys []string) (map[string]string, error) {
+	rows, err := a.db.Query(ctx, `
+		SELECT ul.league_key, ul.guid
+		FROM yahoo_user_leagues ul
+		JOIN yahoo_users u ON u.guid = ul.guid
+		WHERE u.logto_sub = $1 AND ul.league_key = ANY($2)
+		ORDER BY u.created_at DESC, u.guid DESC
+	`, logtoSub, leagueKeys)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	owners := make(map[string]string, len(leagueKeys))
+	for rows.Next() {
+		var leagueKey, guid string
+		if err := rows.Scan(&leagueKey, &guid); err != nil {
+			return nil, err
+		}
+		owners[leagueKey] = guid // later rows are older links and win
+	}
+	return owners, rows.Err()
+}
+
+// groupLeaguesByAccount splits leagueKeys by the account that should fetch
+// them: the owner from leagueOwners, else fallbackGUID. Keys keep their
+// order within each group.
+func groupLeaguesByAccount(leagueKeys []string, owners map[string]string, fallbackGUID string) map[string][]string {
+	groups := make(map[string][]string)
+	for _, lk := range leagueKeys {
+		guid, ok := owners[lk]
+		if !ok {
+			guid = fallbackGUID
+		}
+		groups[guid] = append(groups[guid], lk)
+	}
+	return groups
+}
+
+// userLeagueCount counts the distinct leagues imported across all of the
+// user's linked accounts, which is what the tier cap limits.
+func (a *App) userLeagueCount(ctx context.Context, logtoSub string) (int, error) {
+	var count int
+	err := a.db.QueryRow(ctx, `
+		SELECT count(DISTINCT ul.league_key)
+		FROM yahoo_user_leagues ul
+		JOIN yahoo_users u ON u.guid = ul.guid
+		WHERE u.logto_sub = $1
+	`, logtoSub).Scan(&count)
+	return count, err
+}
+
+// fetchUserLeagues returns the league bundles of every account linked to
+// logtoSub, or nil when none is linked. A league two accounts belong to is
+// listed once, from the oldest link.
+func (a *App) fetchUserLeagues(ctx context.Context, logtoSub string) ([]LeagueResponse, error) {
+	accounts, err := a.linkedAccounts(ctx, logtoSub)
+	if err != nil {
+		return nil, fmt.Errorf("query linked accounts: %w", err)
+	}
+	if len(accounts) == 0 {
+		return nil, nil
+	}
+
+	bundles := make([][]LeagueResponse, 0, len(accounts))
+	for _, acct := range accounts {
+		leagues, err := a.fetchLeagueBundleCached(ctx, acct.guid)
+		if err != nil {
+			return nil, fmt.Errorf("league bundle for guid=%s: %w", acct.guid, err)
+		}
+		bundles = append(bundles, leagues)
+	}
+	return mergeLeagueBundles(bundles), nil
+}
+
+// mergeLeagueBundles concatenates per-account bundles, keeping the first
+// occurrence of each league key.
+func mergeLeagueBundles(bundles [][]LeagueResponse) []LeagueResponse {
+	seen := make(map[string]bool)
+	merged := make([]LeagueResponse, 0)
+	for _, leagues := range bundles {
+		for _, lr := range leagues {
+			if seen[lr.LeagueKey] {
+				continue
+			}
+			seen[lr.LeagueKey] = true
+			merged = append(merged, lr)
+		}
+	}
+	return merged
+}
+
+// invalidateUserLeagueCaches drops the cached bundle of every account
+// linked to logtoSub (best-effort).
+func (a *App) invalidateUserLeagueCaches(ctx context.Context, logtoSub string) {
+	accounts, err := a.linkedAccounts(ctx, logtoSub)
+	if err != nil {
+		log.Printf("[Fantasy] Failed to list linked accounts for cache invalidation: %v", err)
+		return
+	}
+	for _, acct := range accounts {
+		a.invalidateLeagueCache(ctx, acct.guid)
+	}
+}
@@ -0,0 +1,74 @@
+package main
+
+import (
+	"database/sql"
+	"errors"
+	"reflect"
+	"testing"
+	"time"
+)
+
+func TestPickAccount(t *testing.T) {
+	accounts := []linkedAccount{{guid: "OLDEST"}, {guid: "NEWER"}}
+
+	if acct, err := pickAccount(accounts, ""); err != nil || acct.guid != "OLDEST" {
+		t.Errorf("pickAccount(\"\") = %q, %v; want the oldest link", acct.guid, err)
+	}
+	if acct, err := pickAccount(accounts, "NEWER"); err != nil || acct.guid != "NEWER" {
+		t.Errorf("pickAccount(NEWER) = %q, %v", acct.guid, err)
+	}
+	if _, err := pickAccount(accounts, "SOMEONE_ELSE"); !errors.Is(err, errYahooAccountNotLinked) {
+		t.Errorf("pickAccount(unlinked guid) err = %v, want errYahooAccountNotLinked", err)
+	}
+	if _, err := pickAccount(nil, ""); !errors.Is(err, errYahooAccountNotLinked) {
+		t.Errorf("pickAccount with no accounts err = %v, want errYahooAccountNotLinked", err)
+	}
+}
+
+func TestAllSynced(t *testing.T) {
+	synced := linkedAccount{guid: "A", lastSync: sql.NullTime{Time: time.Now(), Valid: true}}
+	pending := linkedAccount{guid: "B"}
+
+	if allSynced(nil) {
+		t.Error("no accounts should not count as synced")
+	}
+	if !allSynced([]linkedAccount{synced}) {
+		t.Error("a single synced account should count as synced")
+	}
+	if allSynced([]linkedAccount{synced, pending}) {
+		t.Error("a newly linked, unsynced account should make the user unsynced")
+	}
+}
+
+func TestGroupLeaguesByAccount(t *testing.T) {
+	owners := map[string]string{"nfl.l.1": "WORK", "nba.l.2": "HOME"}
+	got := groupLeaguesByAccount([]string{"nfl.l.1", "nhl.l.3", "nba.l.2", "mlb.l.4"}, owners, "HOME")
+	want := map[string][]string{
+		"WORK": {"nfl.l.1"},
+		"HOME": {"nhl.l.3", "nba.l.2", "mlb.l.4"},
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("groupLeaguesByAccount = %v, want %v", got, want)
+	}
+}
+
+func TestMergeLeagueBundles(t *testing.T) {
+	home := []LeagueResponse{{LeagueKey: "nfl.l.1", Name: "Home league"}, {LeagueKey: "nba.l.2"}}
+	work := []LeagueResponse{{LeagueKey: "nfl.l.1", Name: "Work copy"}, {LeagueKey: "mlb.l.3"}}
+
+	merged := mergeLeagueBundles([][]LeagueResponse{home, work})
+	var keys []string
+	for _, lr := range merged {
+		keys = append(keys, lr.LeagueKey)
+	}
+	if want := []string{"nfl.l.1", "nba.l.2", "mlb.l.3"}; !reflect.DeepEqual(keys, want) {
+		t.Fatalf("merged keys = %v, want %v", keys, want)
+	}
+	if merged[0].Name != "Home league" {
+		t.Errorf("shared league came from %q, want the first account's copy", merged[0].Name)
+	}
+
+	if empty := mergeLeagueBundles(nil); empty == nil || len(empty) != 0 {
+		t.Errorf("mergeLeagueBundles(nil) = %#v, want an empty non-nil slice", empty)
+	}
+}
@@ -0,0 +1,410 @@
+package main
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"io"
+	"net/http"
+	"net/http/httptest"
+	"sync"
+	"sync/atomic"
+	"testing"
+	"time"
+)
+
+// cannedTransport is an http.RoundTripper that replays queued responses in
+// order and records every request, so YahooClient can be driven without a
+// server or env overrides.
+type cannedTransport struct {
+	mu        sync.Mutex
+	responses []cannedResponse
+	requests  []*http.Request
+}
+
+type cannedResponse struct {
+	status int
+	body   []byte
+}
+
+func (ct *cannedTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	ct.mu.Lock()
+	defer ct.mu.Unlock()
+	ct.requests = append(ct.requests, req)
+	if len(ct.responses) == 0 {
+		return nil, fmt.Errorf("cannedTransport: unexpected request %s", req.URL)
+	}
+	r := ct.responses[0]
+	ct.responses = ct.responses[1:]
+	return &http.Response{
+		StatusCode: r.status,
+		Header:     http.Header{},
+		Body:       io.NopCloser(bytes.NewReader(r.body)),
+		Request:    req,
+	}, nil
+}
+
+// newCannedYahooClient returns a client with a valid access token whose
+// requests are answered by `responses` in order.
+func newCannedYahooClient(responses ...cannedResponse) (*YahooClient, *cannedTransport) {
+	ct := &cannedTransport{responses: responses}
+	yc := newTestYahooClient(defaultMaxRetries)
+	yc.httpClient = &http.Client{Transport: ct}
+	yc.accessToken = "canned-access"
+	yc.tokenExpiry = time.Now().Add(time.Hour)
+	return yc, ct
+}
+
+// newTestYahooServer serves both the token endpoint and the Fantasy API.
+// The API answers 401 token_expired for the first `expiredResponses`
+// requests and 200 afterwards.
+func newTestYahooServer(t *testing.T, expiredResponses int32) (tokenCalls, apiCalls *atomic.Int32) {
+	t.Helper()
+	tokenCalls, apiCalls = &atomic.Int32{}, &atomic.Int32{}
+
+	mux := http.NewServeMux()
+	mux.HandleFunc("/oauth2/get_token", func(w http.ResponseWriter, r *http.Request) {
+		n := tokenCalls.Add(1)
+		w.Header().Set("Content-Type", "application/json")
+		fmt.Fprintf(w, `{"access_token":"access-%d","refresh_token":"refresh-%d","expires_in":3600}`, n, n)
+	})
+	mux.HandleFunc("/fantasy/v2/", func(w http.ResponseWriter, r *http.Request) {
+		if apiCalls.Add(1) <= expiredResponses {
+			w.Header().Set("WWW-Authenticate", `OAuth oauth_problem="token_expired", realm="yahooapis.com"`)
+			w.WriteHeader(http.StatusUnauthorized)
+			return
+		}
+		fmt.Fprint(w, `<fantasy_content/>`)
+	})
+
+	srv := httptest.NewServer(mux)
+	t.Cleanup(srv.Close)
+	t.Setenv("YAHOO_TOKEN_URL", srv.URL+"/oauth2/get_token")
+	t.Setenv("YAHOO_API_BASE_URL", srv.URL+"/fantasy/v2")
+	return tokenCalls, apiCalls
+}
+
+func newTestYahooClient(maxRetries int) *YahooClient {
+	yc := NewYahooClient("id", "secret", "refresh-0")
+	yc.apiDelay = 0
+	yc.maxRetries = maxRetries
+	return yc
+}
+
+// A single token_expired followed by success must not use up a retry:
+// with a budget of one attempt the call still succeeds.
+func TestMakeRequestTokenExpiredDoesNotConsumeRetry(t *testing.T) {
+	tokenCalls, apiCalls := newTestYahooServer(t, 1)
+	yc := newTestYahooClient(1)
+
+	err := yc.withRetry(context.Background(), "test", func() error {
+		_, err := yc.makeRequest(context.Background(), "league/449.l.1/standings")
+		return err
+	})
+	if err != nil {
+		t.Fatalf("withRetry: %v", err)
+	}
+	if got := apiCalls.Load(); got != 2 {
+		t.Errorf("API calls = %d; want 2 (expired, then retried after refresh)", got)
+	}
+	if got := tokenCalls.Load(); got != 2 {
+		t.Errorf("token calls = %d; want 2 (initial, then refresh)", got)
+	}
+	if got := yc.RefreshedToken(); got != "refresh-2" {
+		t.Errorf("RefreshedToken() = %q; want the rotated refresh-2", got)
+	}
+}
+
+// A token that is still rejected right after a refresh is a real error,
+// not a loop: makeRequest refreshes at most once per call.
+func TestMakeRequestTokenExpiredRefreshesOnce(t *testing.T) {
+	tokenCalls, apiCalls := newTestYahooServer(t, 100)
+	yc := newTestYahooClient(1)
+
+	if _, err := yc.makeRequest(context.Background(), "league/449.l.1/standings"); err == nil {
+		t.Fatal("makeRequest succeeded; want 401 error")
+	}
+	if got := apiCalls.Load(); got != 2 {
+		t.Errorf("API calls = %d; want 2", got)
+	}
+	if got := tokenCalls.Load(); got != 2 {
+		t.Errorf("token calls = %d; want 2", got)
+	}
+}
+
+// Separate clients built from the same stored refresh token (the sync
+// worker and a user request) that refresh at the same moment must share a
+// single exchange; a second one would rotate the token out from under the
+// first.
+func TestConcurrentRefreshSharesOneExchange(t *testing.T) {
+	var tokenCalls atomic.Int32
+	entered := make(chan struct{}, 1)
+	release := make(chan struct{})
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		n := tokenCalls.Add(1)
+		select {
+		case entered <- struct{}{}:
+		default:
+		}
+		<-release
+		fmt.Fprintf(w, `{"access_token":"access-%d","refresh_token":"refresh-%d","expires_in":3600}`, n, n)
+	}))
+	t.Cleanup(srv.Close)
+	t.Setenv("YAHOO_TOKEN_URL", srv.URL)
+
+	const clients = 8
+	ycs := make([]*YahooClient, clients)
+	errs := make([]error, clients)
+	var wg sync.WaitGroup
+	for i := range ycs {
+		ycs[i] = newTestYahooClient(1)
+		wg.Add(1)
+		go func(i int) {
+			defer wg.Done()
+			errs[i] = ycs[i].ensureToken(context.Background())
+		}(i)
+	}
+
+	// Hold the first exchange open long enough for the rest to join it.
+	<-entered
+	time.Sleep(100 * time.Millisecond)
+	close(release)
+	wg.Wait()
+
+	if got := tokenCalls.Load(); got != 1 {
+		t.Errorf("token calls = %d; want 1 shared exchange", got)
+	}
+	for i, yc := range ycs {
+		if errs[i] != nil {
+			t.Errorf("client %d: %v", i, errs[i])
+			continue
+		}
+		if yc.accessToken != "access-1" || yc.RefreshedToken() != "refresh-1" {
+			t.Errorf("client %d holds %q/%q; want access-1/refresh-1", i, yc.accessToken, yc.RefreshedToken())
+		}
+	}
+
+	// Once the shared exchange finishes, the rotated token refreshes on its own.
+	next := NewYahooClient("id", "secret", "refresh-1")
+	if err := next.ensureToken(context.Background()); err != nil {
+		t.Fatalf("ensureToken after rotation: %v", err)
+	}
+	if got := tokenCalls.Load(); got != 2 {
+		t.Errorf("token calls = %d; want 2 after a refresh with the rotated token", got)
+	}
+}
+
+// A batch shares one token refresh across all its leagues, and a league
+// Yahoo fails on gets its own error without sinking the others.
+func TestFetchStandingsBatchSharesRefresh(t *testing.T) {
+	tokenCalls, _ := newTestYahooServer(t, 0)
+	var apiCalls atomic.Int32
+	mux := http.NewServeMux()
+	mux.HandleFunc("/fantasy/v2/", func(w http.ResponseWriter, r *http.Request) {
+		apiCalls.Add(1)
+		if r.URL.Path == "/fantasy/v2/league/449.l.bad/standings" {
+			w.WriteHeader(http.StatusInternalServerError)
+			return
+		}
+		fmt.Fprint(w, `<fantasy_content/>`)
+	})
+	api := httptest.NewServer(mux)
+	t.Cleanup(api.Close)
+	t.Setenv("YAHOO_API_BASE_URL", api.URL+"/fantasy/v2")
+
+	keys := []string{"449.l.1", "449.l.2", "449.l.bad", "449.l.3", "449.l.4"}
+	results := (&App{}).fetchStandingsBatch(context.Background(), newTestYahooClient(1), "guid-1", keys, 2)
+
+	if got := tokenCalls.Load(); got != 1 {
+		t.Errorf("token calls = %d; want 1 for the whole batch", got)
+	}
+	if got := apiCalls.Load(); got != int32(len(keys)) {
+		t.Errorf("API calls = %d; want %d", got, len(keys))
+	}
+	if len(results) != len(keys) {
+		t.Fatalf("got %d results; want %d", len(results), len(keys))
+	}
+	for _, lk := range keys {
+		r := results[lk]
+		if lk == "449.l.bad" {
+			if r.Error == "" {
+				t.Errorf("%s: want an error", lk)
+			}
+		} else if r.Error != "" {
+			t.Errorf("%s: unexpected error %q", lk, r.Error)
+		}
+	}
+}
+
+func TestDedupeLeagueKeys(t *testing.T) {
+	got := dedupeLeagueKeys([]string{" 449.l.1", "", "449.l.2", "449.l.1 ", "  "})
+	want := []string{"449.l.1", "449.l.2"}
+	if fmt.Sprint(got) != fmt.Sprint(want) {
+		t.Errorf("dedupeLeagueKeys = %v; want %v", got, want)
+	}
+}
+
+// A transient 5xx is retried and the second, canned, standings body is
+// parsed — no network or Yahoo credentials involved.
+func TestGetStandingsRetriesCannedTransport(t *testing.T) {
+	yc, ct := newCannedYahooClient(
+		cannedResponse{status: http.StatusServiceUnavailable, body: []byte("upstream busy")},
+		cannedResponse{status: http.StatusOK, body: loadFixture(t, "standings.xml")},
+	)
+
+	standings, err := yc.GetStandings(context.Background(), "461.l.12345")
+	if err != nil {
+		t.Fatalf("GetStandings: %v", err)
+	}
+	if len(standings) != 2 {
+		t.Errorf("got %d teams; want 2", len(standings))
+	}
+	if len(ct.requests) != 2 {
+		t.Fatalf("transport saw %d requests; want 2", len(ct.requests))
+	}
+	req := ct.requests[1]
+	if got := req.Header.Get("Authorization"); got != "Bearer canned-access" {
+		t.Errorf("Authorization = %q", got)
+	}
+	if got := req.URL.Path; got != "/fantasy/v2/league/461.l.12345/standings" {
+		t.Errorf("path = %q", got)
+	}
+}
+
+func TestGetYahooMaxRetries(t *testing.T) {
+	tests := []struct {
+		raw  string
+		want int
+	}{
+		{"", defaultMaxRetries},
+		{"5", 5},
+		{"0", defaultMaxRetries},
+		{"-1", defaultMaxRetries},
+		{"many", defaultMaxRetries},
+	}
+	for _, tt := range tests {
+		t.Setenv("YAHOO_MAX_RETRIES", tt.raw)
+		if got := getYahooMaxRetries(); got != tt.want {
+			t.Errorf("YAHOO_MAX_RETRIES=%q: got %d; want %d", tt.raw, got, tt.want)
+		}
+	}
+}
+
+// withRetry and the refresh path feed the process-wide counters in
+// /health. The counters are shared, so the test checks deltas.
+func TestYahooStatsCountRefreshesAndRetries(t *testing.T) {
+	before := yahooStats.snapshot()
+
+	newTestYahooServer(t, 0)
+	yc := newTestYahooClient(3)
+	if err := yc.withRetry(context.Background(), "test", func() error {
+		_, err := yc.makeRequest(context.Background(), "league/449.l.1/standings")
+		return err
+	}); err != nil {
+		t.Fatalf("withRetry: %v", err)
+	}
+
+	failures := 1
+	if err := yc.withRetry(context.Background(), "test", func() error {
+		if failures > 0 {
+			failures--
+			return fmt.Errorf("transient")
+		}
+		return nil
+	}); err != nil {
+		t.Fatalf("withRetry: %v", err)
+	}
+
+	after := yahooStats.snapshot()
+	want := map[string]int64{
+		"token_refreshes":                   1, // the first makeRequest had no token yet
+		"retries_used":                      1,
+		"requests_that_succeeded_first_try": 1,
+	}
+	for k, d := range want {
+		if got := after[k] - before[k]; got != d {
+			t.Errorf("%s grew by %d; want %d", k, got, d)
+		}
+	}
+}
+
+// Identical concurrent standings reads for one user share a single Yahoo
+// call; a different user's read for the same league does not join it.
+func TestCachedYahooStandingsCoalesces(t *testing.T) {
+	newTestYahooServer(t, 0)
+	var apiCalls atomic.Int32
+	release := make(chan struct{})
+	mux := http.NewServeMux()
+	mux.HandleFunc("/fantasy/v2/", func(w http.ResponseWriter, r *http.Request) {
+		apiCalls.Add(1)
+		<-release
+		fmt.Fprint(w, `<fantasy_content/>`)
+	})
+	api := httptest.NewServer(mux)
+	t.Cleanup(api.Close)
+	t.Setenv("YAHOO_API_BASE_URL", api.URL+"/fantasy/v2")
+
+	a := &App{}
+	const callers = 10
+	var wg sync.WaitGroup
+	errs := make(chan error, callers+1)
+	for i := 0; i < callers; i++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			_, err := a.cachedYahooStandings(context.Background(), newTestYahooClient(1), "guid-1", "449.l.1")
+			errs <- err
+		}()
+	}
+	wg.Add(1)
+	go func() {
+		defer wg.Done()
+		_, err := a.cachedYahooStandings(context.Background(), newTestYahooClient(1), "guid-2", "449.l.1")
+		errs <- err
+	}()
+
+	// Let every caller reach the flight before the upstream answers.
+	deadline := time.Now().Add(2 * time.Second)
+	for apiCalls.Load() < 2 && time.Now().Before(deadline) {
+		time.Sleep(5 * time.Millisecond)
+	}
+	time.Sleep(50 * time.Millisecond)
+	close(release)
+	wg.Wait()
+	close(errs)
+
+	for err := range errs {
+		if err != nil {
+			t.Fatalf("cachedYahooStandings: %v", err)
+		}
+	}
+	if got := apiCalls.Load(); got != 2 {
+		t.Errorf("API calls = %d; want 2 (one per user)", got)
+	}
+}
+
+func TestLatencyWindowSummary(t *testing.T) {
+	var w latencyWindow
+	if got := w.summary(); got["latency_samples"] != 0 || got["latency_max_ms"] != 0 {
+		t.Fatalf("empty window summary = %v", got)
+	}
+
+	w.record(30 * time.Second) // pushed out once the window wraps
+	for ms := yahooLatencyWindow; ms >= 1; ms-- {
+		w.record(time.Duration(ms) * time.Millisecond)
+	}
+	got := w.summary()
+	want := map[string]int64{
+		"latency_samples": yahooLatencyWindow,
+		"latency_min_ms":  1,
+		"latency_avg_ms":  128,
+		"latency_p50_ms":  128,
+		"latency_p95_ms":  244,
+		"latency_max_ms":  yahooLatencyWindow,
+	}
+	for k, v := range want {
+		if got[k] != v {
+			t.Errorf("%s = %d; want %d", k, got[k], v)
+		}
+	}
+}
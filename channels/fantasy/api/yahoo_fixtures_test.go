@@ -0,0 +1,217 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+// Fixture tests run the parse*XML functions against captured Yahoo
+// responses in testdata/. They pin the XML shapes we depend on, so a Yahoo
+// schema change shows up here instead of as empty widgets in production.
+
+func loadFixture(t *testing.T, name string) []byte {
+	t.Helper()
+	body, err := os.ReadFile(filepath.Join("testdata", name))
+	if err != nil {
+		t.Fatalf("read fixture %s: %v", name, err)
+	}
+	return body
+}
+
+func TestParseLeaguesXMLFixture(t *testing.T) {
+	leagues, err := parseLeaguesXML(loadFixture(t, "leagues.xml"), "nfl")
+	if err != nil {
+		t.Fatalf("parseLeaguesXML: %v", err)
+	}
+	if len(leagues) != 2 {
+		t.Fatalf("got %d leagues; want 2", len(leagues))
+	}
+
+	l := leagues[0]
+	if l["league_key"] != "461.l.12345" || l["name"] != "Sunday Scaries" {
+		t.Errorf("league = %v/%v; want 461.l.12345/Sunday Scaries", l["league_key"], l["name"])
+	}
+	if l["num_teams"] != 10 || l["season"] != 2025 || l["game_code"] != "nfl" {
+		t.Errorf("num_teams/season/game_code = %v/%v/%v", l["num_teams"], l["season"], l["game_code"])
+	}
+	if cw, _ := l["current_week"].(*int); cw == nil || *cw != 8 {
+		t.Errorf("current_week = %v; want 8", l["current_week"])
+	}
+	if finished, _ := leagues[1]["is_finished"].(bool); !finished {
+		t.Errorf("second league is_finished = false; want true from <is_finished>1</is_finished>")
+	}
+}
+
+func TestParseStandingsXMLFixture(t *testing.T) {
+	standings, err := parseStandingsXML(loadFixture(t, "standings.xml"))
+	if err != nil {
+		t.Fatalf("parseStandingsXML: %v", err)
+	}
+	if len(standings) != 2 {
+		t.Fatalf("got %d teams; want 2", len(standings))
+	}
+
+	first := standings[0]
+	if first["team_key"] != "461.l.12345.t.1" || first["manager_name"] != "Brandon" {
+		t.Errorf("team/manager = %v/%v", first["team_key"], first["manager_name"])
+	}
+	if first["team_logo"] != "https://s.yimg.com/cv/apiv2/default/nfl/nfl_1.png" {
+		t.Errorf("team_logo = %v", first["team_logo"])
+	}
+	if first["wins"] != 6 || first["losses"] != 1 || first["percentage"] != ".857" {
+		t.Errorf("record = %v-%v (%v); want 6-1 (.857)", first["wins"], first["losses"], first["percentage"])
+	}
+	if first["streak_type"] != "win" || first["streak_value"] != 3 || first["clinched_playoffs"] != true {
+		t.Errorf("streak/clinched = %v %v/%v", first["streak_type"], first["streak_value"], first["clinched_playoffs"])
+	}
+	if seed, _ := standings[1]["playoff_seed"].(*int); seed != nil {
+		t.Errorf("second team playoff_seed = %d; want nil when absent", *seed)
+	}
+}
+
+// New teams that never set a logo come back without <team_logos>, with an
+// empty <team_logos/>, or with a blank <url/>. None of them may break the
+// rest of the standings.
+func TestParseStandingsXMLMissingLogos(t *testing.T) {
+	standings, err := parseStandingsXML(loadFixture(t, "standings_missing_logos.xml"))
+	if err != nil {
+		t.Fatalf("parseStandingsXML: %v", err)
+	}
+	if len(standings) != 3 {
+		t.Fatalf("got %d teams; want 3", len(standings))
+	}
+	for _, team := range standings {
+		if team["team_logo"] != "" {
+			t.Errorf("%v team_logo = %q; want empty", team["team_key"], team["team_logo"])
+		}
+	}
+	// t.3 follows the logo-less teams in the feed; its 1.000 record sorts it first.
+	if standings[0]["team_key"] != "461.l.55555.t.3" || standings[0]["wins"] != 2 || standings[0]["manager_name"] != "Sam" {
+		t.Errorf("team after logo-less ones = %v", standings[0])
+	}
+}
+
+func TestParseScoreboardXMLFixture(t *testing.T) {
+	week, matchups, err := parseScoreboardXML(loadFixture(t, "scoreboard.xml"), 1)
+	if err != nil {
+		t.Fatalf("parseScoreboardXML: %v", err)
+	}
+	if week != 8 {
+		t.Errorf("week = %d; want 8 from the body, not the requested fallback", week)
+	}
+	if len(matchups) != 1 {
+		t.Fatalf("got %d matchups; want 1", len(matchups))
+	}
+
+	m := matchups[0]
+	if m["status"] != "postevent" || m["is_playoffs"] != false {
+		t.Errorf("status/is_playoffs = %v/%v", m["status"], m["is_playoffs"])
+	}
+	if winner, _ := m["winner_team_key"].(*string); winner == nil || *winner != "461.l.12345.t.1" {
+		t.Errorf("winner_team_key = %v", m["winner_team_key"])
+	}
+	teams, _ := m["teams"].([]map[string]any)
+	if len(teams) != 2 {
+		t.Fatalf("got %d matchup teams; want 2", len(teams))
+	}
+	if pts, _ := teams[0]["points"].(*float64); pts == nil || *pts != 121.44 {
+		t.Errorf("points = %v; want 121.44", teams[0]["points"])
+	}
+	if teams[1]["team_logo"] != "" {
+		t.Errorf("logo-less matchup team team_logo = %q; want empty", teams[1]["team_logo"])
+	}
+}
+
+func TestParseLeagueSettingsXMLFixture(t *testing.T) {
+	catalog, err := parseLeagueSettingsXML(loadFixture(t, "settings.xml"))
+	if err != nil {
+		t.Fatalf("parseLeagueSettingsXML: %v", err)
+	}
+
+	// stat 6 is disabled; stat 9 has no <enabled> and counts as enabled.
+	want := []string{"4", "5", "9"}
+	if len(catalog.Stats) != len(want) {
+		t.Fatalf("got %d stats; want %d (%v)", len(catalog.Stats), len(want), catalog.Stats)
+	}
+	for i, id := range want {
+		if catalog.Stats[i].StatID != id {
+			t.Errorf("Stats[%d].StatID = %q; want %q", i, catalog.Stats[i].StatID, id)
+		}
+	}
+	if catalog.Stats[0].DisplayName != "Pass Yds" || catalog.Stats[0].PositionType != "O" {
+		t.Errorf("Stats[0] = %+v", catalog.Stats[0])
+	}
+	if !catalog.Stats[2].DisplayOnly {
+		t.Errorf("stat 9 DisplayOnly = false; want true")
+	}
+	if catalog.Modifiers["4"] != 0.04 || catalog.Modifiers["5"] != 4 || len(catalog.Modifiers) != 2 {
+		t.Errorf("Modifiers = %v", catalog.Modifiers)
+	}
+}
+
+func TestParseLeagueSettingsSummaryXMLFixture(t *testing.T) {
+	settings, err := parseLeagueSettingsSummaryXML(loadFixture(t, "settings.xml"), "ignored")
+	if err != nil {
+		t.Fatalf("parseLeagueSettingsSummaryXML: %v", err)
+	}
+
+	if settings.LeagueKey != "461.l.12345" || settings.Name != "Sunday Scaries" || settings.ScoringType != "head" {
+		t.Errorf("settings = %+v", settings)
+	}
+	if settings.NumTeams == nil || *settings.NumTeams != 10 {
+		t.Errorf("NumTeams = %v; want 10", settings.NumTeams)
+	}
+	if settings.PlayoffStartWeek == nil || *settings.PlayoffStartWeek != 15 {
+		t.Errorf("PlayoffStartWeek = %v; want 15", settings.PlayoffStartWeek)
+	}
+	wantPositions := []RosterPositionSetting{
+		{Position: "QB", PositionType: "O", Count: 1, IsStarting: true},
+		{Position: "WR", PositionType: "O", Count: 3, IsStarting: true},
+		{Position: "BN", Count: 6},
+	}
+	if len(settings.RosterPositions) != len(wantPositions) {
+		t.Fatalf("RosterPositions = %+v", settings.RosterPositions)
+	}
+	for i, want := range wantPositions {
+		if settings.RosterPositions[i] != want {
+			t.Errorf("RosterPositions[%d] = %+v; want %+v", i, settings.RosterPositions[i], want)
+		}
+	}
+	if len(settings.StatCategories) != 3 {
+		t.Errorf("got %d stat categories; want 3 (disabled stat dropped)", len(settings.StatCategories))
+	}
+}
+
+func TestParseRosterXMLFixture(t *testing.T) {
+	roster, err := parseRosterXML(loadFixture(t, "roster.xml"), "461.l.12345.t.1", "Gridiron Gurus", nil)
+	if err != nil {
+		t.Fatalf("parseRosterXML: %v", err)
+	}
+	players, _ := roster["players"].([]map[string]any)
+	if len(players) != 2 {
+		t.Fatalf("got %d players; want 2", len(players))
+	}
+
+	qb := players[0]
+	if qb["player_key"] != "461.p.33389" || qb["selected_position"] != "QB" {
+		t.Errorf("player_key/selected_position = %v/%v", qb["player_key"], qb["selected_position"])
+	}
+	if pts, _ := qb["player_points"].(*float64); pts == nil || *pts != 24.5 {
+		t.Errorf("player_points = %v; want 24.5", qb["player_points"])
+	}
+	if stats, _ := qb["player_stats"].(map[string]string); stats["4"] != "250" {
+		t.Errorf("player_stats = %v; want stat 4 = 250", qb["player_stats"])
+	}
+
+	wr := players[1]
+	if wr["status"] != "Q" || wr["injury_note"] != "Knee" {
+		t.Errorf("status/injury_note = %v/%v", wr["status"], wr["injury_note"])
+	}
+	if pos, _ := wr["eligible_positions"].([]string); len(pos) != 2 || pos[1] != "W/R/T" {
+		t.Errorf("eligible_positions = %v", wr["eligible_positions"])
+	}
+	if stats, _ := wr["player_stats"].(map[string]string); stats["12"] != "-" {
+		t.Errorf("player_stats = %v; want the no-data dash kept verbatim", wr["player_stats"])
+	}
+}
@@ -2,9 +2,7 @@ package main
 
 import (
 	"context"
-	"database/sql"
 	"log"
-	"strings"
 	"time"
 
 	"github.com/gofiber/fiber/v2"
@@ -35,44 +33,35 @@ func (a *App) GetYahooSummary(c *fiber.Ctx) error {
 	ctx, cancel := context.WithTimeout(c.Context(), 5*time.Second)
 	defer cancel()
 
-	// 1. Resolve guid + sync state for this logto_sub. yahoo_users does not
-	//    have a boolean `synced` column; sync is recorded as a non-NULL
-	//    `last_sync` timestamp, matching GetYahooStatus's derivation.
-	var (
-		guid     string
-		lastSync sql.NullTime
-	)
-	err := a.db.QueryRow(ctx, `
-		SELECT guid, last_sync
-		FROM yahoo_users
-		WHERE logto_sub = $1
-	`, userID).Scan(&guid, &lastSync)
+	// 1. Resolve the linked accounts + sync state for this logto_sub.
+	//    yahoo_users does not have a boolean `synced` column; sync is
+	//    recorded as a non-NULL `last_sync` timestamp, matching
+	//    GetYahooStatus's derivation across all linked accounts.
+	accounts, err := a.linkedAccounts(ctx, userID)
 	if err != nil {
-		if err == sql.ErrNoRows || strings.Contains(err.Error(), "no rows") {
-			// User has never connected Yahoo — happy path, not an error.
-			return c.JSON(YahooSummaryResponse{})
-		}
 		log.Printf("[GetYahooSummary] DB error for logto_sub=%s: %v", userID, err)
 		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
 			Status: "error",
 			Error:  "Failed to load Yahoo summary",
 		})
 	}
+	if len(accounts) == 0 {
+		// User has never connected Yahoo — happy path, not an error.
+		return c.JSON(YahooSummaryResponse{})
+	}
 
 	// 2. Count imported leagues. Don't fail the request on a count error —
 	//    connection state is more valuable than the count, so we degrade
 	//    gracefully to 0 and log.
-	var leagueCount int
-	if err := a.db.QueryRow(ctx, `
-		SELECT count(*) FROM yahoo_user_leagues WHERE guid = $1
-	`, guid).Scan(&leagueCount); err != nil {
-		log.Printf("[GetYahooSummary] count leagues failed for guid=%s: %v", guid, err)
+	leagueCount, err := a.userLeagueCount(ctx, userID)
+	if err != nil {
+		log.Printf("[GetYahooSummary] count leagues failed for logto_sub=%s: %v", userID, err)
 		leagueCount = 0
 	}
 
 	return c.JSON(YahooSummaryResponse{
-		YahooConnected: guid != "",
-		YahooSynced:    lastSync.Valid,
+		YahooConnected: true,
+		YahooSynced:    allSynced(accounts),
 		LeagueCount:    leagueCount,
 	})
 }
@@ -1,6 +1,8 @@
 package main
 
 import (
+	"errors"
+	"strings"
 	"testing"
 )
 
@@ -129,6 +131,18 @@ func TestExtractTeamLogo(t *testing.T) {
 			fallback: fallback,
 			want:     fallback,
 		},
+		{
+			name:     "blank first url skips to next",
+			logos:    &XMLTeamLogos{TeamLogo: []XMLTeamLogoEntry{{Size: "large"}, {URL: logo2}}},
+			fallback: fallback,
+			want:     logo2,
+		},
+		{
+			name:     "all blank urls returns fallback",
+			logos:    &XMLTeamLogos{TeamLogo: []XMLTeamLogoEntry{{Size: "large"}}},
+			fallback: "",
+			want:     "",
+		},
 	}
 
 	for _, tc := range tests {
@@ -485,6 +499,90 @@ func TestSerializeStandings(t *testing.T) {
 	}
 }
 
+func TestSerializeStandingsComputedRank(t *testing.T) {
+	team := func(key, pct, pf string) XMLTeamStanding {
+		return XMLTeamStanding{
+			TeamKey: key,
+			TeamStandingsData: &XMLTeamStats{
+				OutcomeTotals: &XMLOutcome{Percentage: pct},
+				PointsFor:     strPtr(pf),
+			},
+		}
+	}
+	// Yahoo's order, deliberately not by record.
+	got := serializeStandings([]XMLTeamStanding{
+		team("key.c", ".500", "900.5"),
+		team("key.a", ".750", "800"),
+		team("key.d", ".250", "1200"),
+		team("key.b", ".500", "950.25"),
+		team("key.e", ".250", "1200"),
+	})
+
+	want := []string{"key.a", "key.b", "key.c", "key.d", "key.e"}
+	if len(got) != len(want) {
+		t.Fatalf("len(serializeStandings) = %d, want %d", len(got), len(want))
+	}
+	for i, key := range want {
+		if got[i]["team_key"] != key {
+			t.Errorf("position %d = %v, want %s", i, got[i]["team_key"], key)
+		}
+		if got[i]["computed_rank"] != i+1 {
+			t.Errorf("%s computed_rank = %v, want %d", key, got[i]["computed_rank"], i+1)
+		}
+	}
+}
+
+func TestParseStandingsNumber(t *testing.T) {
+	tests := []struct {
+		raw  string
+		want float64
+	}{
+		{".857", 0.857},
+		{"1.000", 1},
+		{"842.36", 842.36},
+		{" 12 ", 12},
+		{"2.5", 2.5},
+		{"", 0},
+		{"-", 0},
+		{"n/a", 0},
+		{"1,204.5", 0},
+		{"NaN", 0},
+		{"Inf", 0},
+	}
+	for _, tt := range tests {
+		if got := parseStandingsNumber("key.1", "points_for", tt.raw); got != tt.want {
+			t.Errorf("parseStandingsNumber(%q) = %v, want %v", tt.raw, got, tt.want)
+		}
+	}
+}
+
+func TestSerializeStandingsNumericValues(t *testing.T) {
+	got := serializeStandings([]XMLTeamStanding{{
+		TeamKey: "key.1",
+		TeamStandingsData: &XMLTeamStats{
+			OutcomeTotals: &XMLOutcome{Percentage: ".625"},
+			GamesBack:     strPtr("1.5"),
+			PointsFor:     strPtr("1012.44"),
+			PointsAgainst: strPtr("garbage"),
+		},
+	}})
+
+	team := got[0]
+	if team["percentage"] != ".625" || team["percentage_value"] != 0.625 {
+		t.Errorf("percentage = %v / %v, want .625 / 0.625", team["percentage"], team["percentage_value"])
+	}
+	if team["games_back_value"] != 1.5 {
+		t.Errorf("games_back_value = %v, want 1.5", team["games_back_value"])
+	}
+	if team["points_for_value"] != 1012.44 {
+		t.Errorf("points_for_value = %v, want 1012.44", team["points_for_value"])
+	}
+	// The raw string survives; only the typed value falls back.
+	if team["points_against"] != "garbage" || team["points_against_value"] != 0.0 {
+		t.Errorf("points_against = %v / %v, want garbage / 0", team["points_against"], team["points_against_value"])
+	}
+}
+
 func TestSerializeScoreboard(t *testing.T) {
 	winnerKey := "key.1"
 	sb := &XMLScoreboard{
@@ -620,6 +718,123 @@ func TestSerializeRoster(t *testing.T) {
 	}
 }
 
+func TestSerializeRosterEmptyPage(t *testing.T) {
+	// A page past the end of a player pool comes back as an empty
+	// <players count="0"/> element.
+	body := []byte(`<fantasy_content><team><team_key>449.l.1.t.1</team_key>` +
+		`<roster><coverage_type>week</coverage_type><players count="0"/></roster></team></fantasy_content>`)
+
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "roster", &fc); err != nil {
+		t.Fatalf("unmarshalYahooXML: %v", err)
+	}
+	if fc.Team == nil || fc.Team.Roster == nil {
+		t.Fatal("roster not decoded")
+	}
+
+	got := serializeRoster(fc.Team.Roster.Players.Player, "449.l.1.t.1", "Team", nil)
+	players, ok := got["players"].([]map[string]any)
+	if !ok || players == nil {
+		t.Fatalf("players = %#v; want a non-nil empty slice", got["players"])
+	}
+	if len(players) != 0 {
+		t.Errorf("len(players) = %d; want 0", len(players))
+	}
+}
+
+func TestParseLeagueSettingsSummaryMissingFields(t *testing.T) {
+	// A league without playoffs or roster_positions, and no num_teams.
+	body := []byte(`<fantasy_content><league><name>Keepers</name>` +
+		`<settings><scoring_type>point</scoring_type><uses_playoff>0</uses_playoff></settings></league></fantasy_content>`)
+
+	got, err := parseLeagueSettingsSummaryXML(body, "449.l.7")
+	if err != nil {
+		t.Fatalf("parseLeagueSettingsSummaryXML: %v", err)
+	}
+	if got.LeagueKey != "449.l.7" || got.Name != "Keepers" || got.ScoringType != "point" {
+		t.Errorf("settings = %+v", got)
+	}
+	if got.NumTeams != nil || got.PlayoffStartWeek != nil {
+		t.Errorf("NumTeams = %v, PlayoffStartWeek = %v; want nil", got.NumTeams, got.PlayoffStartWeek)
+	}
+	if got.RosterPositions == nil || len(got.RosterPositions) != 0 {
+		t.Errorf("RosterPositions = %#v; want a non-nil empty slice", got.RosterPositions)
+	}
+	if got.StatCategories == nil || len(got.StatCategories) != 0 {
+		t.Errorf("StatCategories = %#v; want a non-nil empty slice", got.StatCategories)
+	}
+
+	// No <settings> at all still yields the league metadata.
+	got, err = parseLeagueSettingsSummaryXML([]byte(`<fantasy_content><league><name>Bare</name></league></fantasy_content>`), "449.l.8")
+	if err != nil {
+		t.Fatalf("parseLeagueSettingsSummaryXML: %v", err)
+	}
+	if got.Name != "Bare" || got.LeagueKey != "449.l.8" || got.RosterPositions == nil {
+		t.Errorf("settings = %+v", got)
+	}
+}
+
+func TestRosterURLPath(t *testing.T) {
+	tests := []struct {
+		name               string
+		week, start, count int
+		want               string
+	}{
+		{"plain roster", 0, 0, 0, "team/t.1/roster;"},
+		{"weekly stats", 3, 0, 0, "team/t.1/roster;week=3/players/stats;type=week;week=3"},
+		{"paged", 0, 25, 25, "team/t.1/roster/players;start=25;count=25"},
+		{"paged weekly stats", 3, 0, 25, "team/t.1/roster;week=3/players;start=0;count=25/stats;type=week;week=3"},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			if got := rosterURLPath("t.1", tc.week, tc.start, tc.count); got != tc.want {
+				t.Errorf("rosterURLPath = %q, want %q", got, tc.want)
+			}
+		})
+	}
+}
+
+func TestUnmappedStatsRecordedPerSport(t *testing.T) {
+	unmappedStats.clear()
+	t.Cleanup(unmappedStats.clear)
+
+	players := []XMLPlayer{{
+		PlayerKey: "449.p.1",
+		PlayerStats: &XMLPlayerStats{Stats: XMLStats{Stat: []XMLStat{
+			{StatID: "4", Value: "250"},
+			{StatID: "10", Value: "1"},
+			{StatID: "9", Value: "3"},
+		}}},
+	}}
+	// 449 is the 2024 NFL game key.
+	serializeRoster(players, "449.l.1.t.1", "Team", map[string]float64{"4": 0.04})
+
+	got := unmappedStats.snapshot()
+	if len(got) != 1 || strings.Join(got["nfl"], ",") != "9,10" {
+		t.Fatalf("unmapped stats = %v, want map[nfl:[9 10]]", got)
+	}
+
+	unmappedStats.clear()
+	if got := unmappedStats.snapshot(); len(got) != 0 {
+		t.Errorf("after clear = %v, want empty", got)
+	}
+}
+
+func TestGameCodeForKey(t *testing.T) {
+	tests := map[string]string{
+		"449.l.1.t.1": "nfl",
+		"458.l.7":     "mlb",
+		"999.l.1":     "unknown",
+		"team.1":      "unknown",
+	}
+	for key, want := range tests {
+		if got := gameCodeForKey(key); got != want {
+			t.Errorf("gameCodeForKey(%q) = %q, want %q", key, got, want)
+		}
+	}
+}
+
 func TestTruncate(t *testing.T) {
 	tests := []struct {
 		name  string
@@ -645,6 +860,64 @@ func TestTruncate(t *testing.T) {
 	}
 }
 
+func TestUnmarshalYahooXMLParseError(t *testing.T) {
+	body := []byte("  <html><body>Yahoo! is temporarily unavailable</body>" + strings.Repeat("x", 1000))
+
+	var fc FantasyContent
+	err := unmarshalYahooXML(body, "standings", &fc)
+
+	var parseErr *YahooParseError
+	if !errors.As(err, &parseErr) {
+		t.Fatalf("unmarshalYahooXML error = %v; want *YahooParseError", err)
+	}
+	if parseErr.Resource != "standings" {
+		t.Errorf("Resource = %q, want %q", parseErr.Resource, "standings")
+	}
+	if !strings.HasPrefix(parseErr.Snippet, "<html><body>Yahoo! is temporarily unavailable") {
+		t.Errorf("Snippet = %q; want the leading, trimmed body", parseErr.Snippet)
+	}
+	if len(parseErr.Snippet) > parseErrorSnippetLen+len("...") {
+		t.Errorf("Snippet length = %d; want <= %d", len(parseErr.Snippet), parseErrorSnippetLen+3)
+	}
+	if !strings.Contains(err.Error(), "parse standings XML") {
+		t.Errorf("Error() = %q; want it to name the resource", err.Error())
+	}
+}
+
+func TestUnmarshalYahooXMLSuccess(t *testing.T) {
+	body := []byte(`<fantasy_content><league><league_key>449.l.1</league_key></league></fantasy_content>`)
+	var fc FantasyContent
+	if err := unmarshalYahooXML(body, "league", &fc); err != nil {
+		t.Fatalf("unmarshalYahooXML: %v", err)
+	}
+	if fc.League == nil {
+		t.Fatal("League not decoded")
+	}
+}
+
+func TestIsInvalidGrant(t *testing.T) {
+	tests := []struct {
+		name   string
+		status int
+		body   string
+		want   bool
+	}{
+		{"json invalid_grant", 400, `{"error":"invalid_grant","error_description":"token revoked"}`, true},
+		{"401 invalid_grant", 401, `{"error":"invalid_grant"}`, true},
+		{"plain text", 400, `invalid_grant: Invalid refresh token`, true},
+		{"other oauth error", 400, `{"error":"invalid_client"}`, false},
+		{"server error", 503, `invalid_grant`, false},
+		{"empty body", 400, ``, false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := isInvalidGrant(tt.status, []byte(tt.body)); got != tt.want {
+				t.Errorf("isInvalidGrant(%d, %q) = %v; want %v", tt.status, tt.body, got, tt.want)
+			}
+		})
+	}
+}
+
 func strPtr(s string) *string { return &s }
 func ptrInt(i int) *int       { return &i }
 func ptrStr(s *string) string {
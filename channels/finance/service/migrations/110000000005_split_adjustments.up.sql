@@ -0,0 +1,12 @@
+-- Stock splits recorded by operators. On a split's effective date the
+-- previous close TwelveData reports is still the pre-split price, so the
+-- daily previous-close refresh divides it by `ratio` (2 for a 2:1 split,
+-- 0.1 for a 1:10 reverse split).
+CREATE TABLE IF NOT EXISTS split_adjustments (
+    id             SERIAL PRIMARY KEY,
+    symbol         VARCHAR(30) NOT NULL,
+    ratio          NUMERIC(18, 8) NOT NULL CHECK (ratio > 0),
+    effective_date DATE NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (symbol, effective_date)
+);
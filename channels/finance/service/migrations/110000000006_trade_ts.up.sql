@@ -0,0 +1,5 @@
+-- Unix timestamp (seconds) of the TwelveData price event behind the stored
+-- price. The websocket client loads it on connect so a trade replayed out
+-- of order after a restart can't overwrite a newer price. NULL for rows
+-- only ever written by the REST quote refresh.
+ALTER TABLE trades ADD COLUMN IF NOT EXISTS trade_ts BIGINT;
@@ -0,0 +1,5 @@
+-- US equity session (pre / regular / post / closed) of the latest stored
+-- price, classified from its event timestamp so clients can label
+-- extended-hours moves. NULL for crypto/forex pairs and for rows not
+-- updated since this column was added.
+ALTER TABLE trades ADD COLUMN IF NOT EXISTS session TEXT;
@@ -1,8 +1,12 @@
-use std::{env, time::Duration, sync::Arc};
+use std::{collections::{HashMap, HashSet}, env, time::Duration, sync::Arc};
 use anyhow::{Context, Result};
+use crate::types::{FinanceError, MoverDirection, SeedFailure, SeedSummary, TrackedSymbolConfig};
+use crate::retry::with_db_retry;
+use crate::text::truncate_bytes_safe;
 use sqlx::postgres::PgPoolOptions;
+use tokio::sync::mpsc;
 pub use sqlx::PgPool;
-use sqlx::{FromRow, query, query_as};
+use sqlx::{FromRow, PgConnection, query, query_as, query_scalar};
 pub use chrono::Utc;
 
 /// Build the sqlx migrator for this service.
@@ -42,16 +46,17 @@ pub const FINANCE_MIGRATION_MIN: i64 = 110_000_000_000;
 pub const FINANCE_MIGRATION_MAX: i64 = 119_999_999_999;
 
 pub async fn initialize_pool() -> Result<PgPool> {
+    let size = crate::db_pool_size();
     let pool_options = PgPoolOptions::new()
         // Pool sizing rationale: on a busy minute finance can run 500+ WS
         // price events through `process_single_trade` which each open a
         // connection to update `trades`. Ten connections was creating
-        // `acquire_timeout` pressure. Twenty is still well within Postgres'
-        // per-database connection budget and leaves headroom.
-        .max_connections(20)
-        // Keep one warm connection so the first query after an idle period
-        // doesn't eat the 200-500ms TLS/auth handshake latency.
-        .min_connections(1)
+        // `acquire_timeout` pressure. The default of twenty is still well
+        // within Postgres' per-database connection budget and leaves headroom.
+        .max_connections(size.max_connections)
+        // By default keep one warm connection so the first query after an
+        // idle period doesn't eat the 200-500ms TLS/auth handshake latency.
+        .min_connections(size.min_connections)
         .acquire_timeout(Duration::from_secs(10))
         .idle_timeout(Duration::from_secs(30));
 
@@ -84,6 +89,10 @@ pub async fn initialize_pool() -> Result<PgPool> {
         format!("postgres://{}:{}@{}:{}/{}", user, password, raw_host, port, database)
     };
 
+    eprintln!(
+        "[DB] Pool: max {} connections, min {}",
+        size.max_connections, size.min_connections
+    );
     eprintln!("[DB] Connecting to database...");
     let pool = tokio::time::timeout(
         Duration::from_secs(15),
@@ -163,7 +172,7 @@ pub async fn initialize_pool() -> Result<PgPool> {
     Ok(pool)
 }
 
-#[derive(FromRow, Clone, Debug)]
+#[derive(FromRow, serde::Serialize, Clone, Debug)]
 pub struct DatabaseTradeData {
     pub symbol: String, 
     pub price: f64, 
@@ -171,11 +180,16 @@ pub struct DatabaseTradeData {
     pub price_change: f64,
     pub percentage_change: f64,
     pub direction: String,
-    pub last_updated: chrono::DateTime<Utc>
+    pub last_updated: chrono::DateTime<Utc>,
+    /// `pre`, `regular`, `post` or `closed` for the latest price; NULL for
+    /// crypto/forex pairs and rows not updated since the column was added.
+    pub session: Option<String>,
 }
 
 pub async fn get_tracked_symbols(pool: Arc<PgPool>) -> Vec<String> {
-    let statement = "SELECT symbol FROM tracked_symbols WHERE is_enabled = TRUE";
+    // Newest first so that, under a subscription cap, recently requested
+    // symbols win over older ones.
+    let statement = "SELECT symbol FROM tracked_symbols WHERE is_enabled = TRUE ORDER BY created_at DESC, id DESC";
     let res: Result<Vec<(String,)>, sqlx::Error> = async {
         let mut connection = pool.acquire().await?;
         let data = query_as(statement).fetch_all(&mut *connection).await?;
@@ -191,42 +205,245 @@ pub async fn get_tracked_symbols(pool: Arc<PgPool>) -> Vec<String> {
     }
 }
 
-pub async fn seed_tracked_symbols(pool: Arc<PgPool>, symbols: Vec<crate::types::TrackedSymbolConfig>) -> Result<()> {
+/// Upserts every config symbol in a single transaction, so a crash midway
+/// leaves no partial rows. Each symbol runs under its own savepoint, which
+/// lets a row the database rejects be rolled back, logged and reported
+/// without stopping the rest. Repeated symbols after the first are skipped.
+///
+/// Skips the write entirely (and marks the summary `unchanged`) when every
+/// symbol already has a row the upsert wouldn't change.
+pub async fn seed_tracked_symbols(pool: Arc<PgPool>, symbols: Vec<TrackedSymbolConfig>) -> SeedSummary {
+    let (unique, skipped) = dedup_symbols(&symbols);
+    if symbols_already_seeded(&pool, &unique).await {
+        return SeedSummary { skipped, unchanged: true, ..Default::default() };
+    }
+
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => {
+            log::error!("Failed to start symbol seed transaction: {}", e);
+            return all_failed(&unique, skipped, &e);
+        }
+    };
+    let summary = seed_symbols_in(&mut tx, &symbols).await;
+    if let Err(e) = tx.commit().await {
+        log::error!("Failed to commit symbol seed: {}", e);
+        return all_failed(&unique, skipped, &e);
+    }
+    summary
+}
+
+/// The upserts behind [`seed_tracked_symbols`], on a connection the caller
+/// owns; they only become visible once the caller commits.
+pub async fn seed_symbols_in(conn: &mut PgConnection, symbols: &[TrackedSymbolConfig]) -> SeedSummary {
     let statement = "INSERT INTO tracked_symbols (symbol, name, category, exchange) VALUES ($1, $2, $3, $4) ON CONFLICT (symbol) DO UPDATE SET name = EXCLUDED.name, category = EXCLUDED.category, exchange = COALESCE(EXCLUDED.exchange, tracked_symbols.exchange)";
+    let (unique, skipped) = dedup_symbols(symbols);
+    let mut summary = SeedSummary { skipped, ..Default::default() };
+    for entry in unique {
+        // Without the savepoint one rejected row would abort the transaction
+        // and fail every symbol after it.
+        let res = async {
+            query("SAVEPOINT seed_row").execute(&mut *conn).await?;
+            let res = query(statement)
+                .bind(&entry.symbol)
+                .bind(&entry.name)
+                .bind(&entry.category)
+                .bind(&entry.exchange)
+                .execute(&mut *conn)
+                .await;
+            let release = if res.is_ok() { "RELEASE SAVEPOINT seed_row" } else { "ROLLBACK TO SAVEPOINT seed_row" };
+            query(release).execute(&mut *conn).await?;
+            res
+        }
+        .await;
+        match res {
+            Ok(_) => summary.seeded += 1,
+            Err(e) => {
+                log::error!("Failed to seed symbol {}: {}", entry.symbol, e);
+                summary.failed.push(SeedFailure { entry: entry.symbol.clone(), reason: e.to_string() });
+            }
+        }
+    }
+    summary
+}
+
+fn dedup_symbols(symbols: &[TrackedSymbolConfig]) -> (Vec<&TrackedSymbolConfig>, u64) {
+    let mut seen = HashSet::new();
+    let unique: Vec<&TrackedSymbolConfig> = symbols.iter().filter(|s| seen.insert(s.symbol.as_str())).collect();
+    let skipped = (symbols.len() - unique.len()) as u64;
+    (unique, skipped)
+}
+
+/// True when each symbol's row already carries its config name and
+/// category, and its exchange unless the config leaves that blank. A failed
+/// check returns false so the full seed runs and reports the error.
+async fn symbols_already_seeded(pool: &PgPool, symbols: &[&TrackedSymbolConfig]) -> bool {
+    let tickers: Vec<&str> = symbols.iter().map(|s| s.symbol.as_str()).collect();
+    let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+    let categories: Vec<&str> = symbols.iter().map(|s| s.category.as_str()).collect();
+    let exchanges: Vec<Option<&str>> = symbols.iter().map(|s| s.exchange.as_deref()).collect();
+
+    let statement = "
+        SELECT COUNT(*) FROM tracked_symbols t
+        JOIN UNNEST($1::text[], $2::text[], $3::text[], $4::text[]) AS c(symbol, name, category, exchange)
+            ON t.symbol = c.symbol
+        WHERE t.name = c.name
+          AND t.category = c.category
+          AND (c.exchange IS NULL OR t.exchange = c.exchange)
+    ";
+    let matching: Result<i64, sqlx::Error> = query_scalar(statement)
+        .bind(&tickers)
+        .bind(&names)
+        .bind(&categories)
+        .bind(&exchanges)
+        .fetch_one(pool)
+        .await;
+    matches!(matching, Ok(n) if n == symbols.len() as i64)
+}
+
+/// Every symbol reported as failed, for when the transaction itself
+/// couldn't be opened or committed.
+fn all_failed(symbols: &[&TrackedSymbolConfig], skipped: u64, e: &sqlx::Error) -> SeedSummary {
+    let failed = symbols
+        .iter()
+        .map(|s| SeedFailure { entry: s.symbol.clone(), reason: e.to_string() })
+        .collect();
+    SeedSummary { skipped, failed, ..Default::default() }
+}
+
+/// Inserts config symbols that aren't tracked yet; existing rows keep their
+/// metadata and `is_enabled` flag. Returns how many were added.
+pub async fn reseed_tracked_symbols(pool: Arc<PgPool>, symbols: &[crate::types::TrackedSymbolConfig]) -> Result<u64, FinanceError> {
+    let statement = "INSERT INTO tracked_symbols (symbol, name, category, exchange) VALUES ($1, $2, $3, $4) ON CONFLICT (symbol) DO NOTHING";
     let mut connection = pool.acquire().await?;
+    let mut added = 0;
     for entry in symbols {
-        query(statement)
+        added += query(statement)
             .bind(&entry.symbol)
             .bind(&entry.name)
             .bind(&entry.category)
             .bind(&entry.exchange)
             .execute(&mut *connection)
-            .await?;
+            .await?
+            .rows_affected();
     }
-    Ok(())
+    Ok(added)
+}
+
+/// Pauses or resumes `symbol` without touching its `trades` row. Returns
+/// `false` when the symbol isn't tracked.
+pub async fn set_symbol_enabled(pool: Arc<PgPool>, symbol: &str, enabled: bool) -> Result<bool, FinanceError> {
+    let statement = "UPDATE tracked_symbols SET is_enabled = $1 WHERE symbol = $2";
+    let mut connection = pool.acquire().await?;
+    let result = query(statement).bind(enabled).bind(symbol).execute(&mut *connection).await?;
+    Ok(result.rows_affected() > 0)
 }
 
-pub async fn insert_symbol(pool: Arc<PgPool>, symbol: String) -> Result<()> {
+pub async fn insert_symbol(pool: Arc<PgPool>, symbol: String) -> Result<(), FinanceError> {
     let statement = "INSERT INTO trades (symbol, price, previous_close, price_change, percentage_change, direction) VALUES ($1, 0, 0, 0, 0, 'flat') ON CONFLICT (symbol) DO NOTHING";
     let mut connection = pool.acquire().await?;
     query(statement).bind(symbol).execute(&mut *connection).await?;
     Ok(())
 }
 
-pub async fn update_previous_close(pool: Arc<PgPool>, symbol: String, prev_close: f64) -> Result<()> {
+/// Ensures a `trades` row exists for every symbol, `chunk_size` symbols per
+/// statement so a long list doesn't become one huge insert. Existing rows
+/// are left alone. A failed chunk is logged and skipped; returns how many
+/// rows were added.
+pub async fn insert_symbols(pool: &PgPool, symbols: &[String], chunk_size: usize) -> u64 {
+    let statement = "INSERT INTO trades (symbol, price, previous_close, price_change, percentage_change, direction)
+        SELECT symbol, 0, 0, 0, 0, 'flat' FROM UNNEST($1::text[]) AS s(symbol)
+        ON CONFLICT (symbol) DO NOTHING";
+    let mut added = 0;
+    for chunk in symbols.chunks(chunk_size.max(1)) {
+        match query(statement).bind(chunk).execute(pool).await {
+            Ok(result) => added += result.rows_affected(),
+            Err(e) => log::error!("Failed to insert {} symbols starting at {}: {}", chunk.len(), chunk[0], e),
+        }
+    }
+    added
+}
+
+/// Stores `prev_close` for `symbol`. Zero, negative and non-finite values
+/// are ignored so a blank quote can't wipe out a good close; returns
+/// whether anything was written.
+pub async fn update_previous_close(pool: Arc<PgPool>, symbol: String, prev_close: f64) -> Result<bool, FinanceError> {
+    if !(prev_close.is_finite() && prev_close > 0.0) {
+        return Ok(false);
+    }
     let statement = "UPDATE trades SET previous_close = $1 WHERE symbol = $2";
     let mut connection = pool.acquire().await?;
-    query(statement).bind(prev_close).bind(symbol).execute(&mut *connection).await?;
-    Ok(())
+    let result = query(statement).bind(prev_close).bind(symbol).execute(&mut *connection).await?;
+    Ok(result.rows_affected() > 0)
 }
 
-pub async fn update_trade(pool: Arc<PgPool>, symbol: String, price: f64, price_change: f64, percentage_change: f64, direction: &str) -> Result<()> {
-    let statement = "UPDATE trades SET price = $1, price_change = $2, percentage_change = $3, direction = $4, last_updated = CURRENT_TIMESTAMP WHERE symbol = $5";
-    let mut connection = pool.acquire().await?;
-    query(statement).bind(price).bind(price_change).bind(percentage_change).bind(direction).bind(symbol).execute(&mut *connection).await?;
+/// Stores a new price for `symbol`. With a `trade_ts` (a price event's
+/// timestamp) the row is only written when that is not older than the
+/// stored one; `None` is the REST refresh, which leaves `trade_ts` alone.
+/// The session is classified from `trade_ts`, or from now for the refresh.
+pub async fn update_trade(pool: Arc<PgPool>, symbol: String, price: f64, price_change: f64, percentage_change: f64, direction: &str, trade_ts: Option<u64>) -> Result<(), FinanceError> {
+    let statement = "UPDATE trades SET price = $1, price_change = $2, percentage_change = $3, direction = $4, trade_ts = COALESCE($6, trade_ts), session = $7, last_updated = CURRENT_TIMESTAMP WHERE symbol = $5 AND ($6 IS NULL OR trade_ts IS NULL OR trade_ts <= $6)";
+    let trade_ts = trade_ts.and_then(|ts| i64::try_from(ts).ok());
+    let priced_at = trade_ts.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)).unwrap_or_else(Utc::now);
+    let session = crate::session_for(&symbol, priced_at).map(|s| s.as_str());
+    let (pool, symbol) = (&pool, &symbol);
+    with_db_retry("update_trade", || async move {
+        let mut connection = pool.acquire().await?;
+        query(statement).bind(price).bind(price_change).bind(percentage_change).bind(direction).bind(symbol).bind(trade_ts).bind(session).execute(&mut *connection).await
+    }).await?;
     Ok(())
 }
 
+/// Sets `previous_close` for `symbol` and recomputes `price_change`,
+/// `percentage_change` and `direction` from the stored price, in one
+/// transaction. A row that has no price yet only gets the new previous
+/// close. Returns the updated row, or `None` when `symbol` has no row.
+pub async fn recompute_trade(pool: Arc<PgPool>, symbol: &str, previous_close: f64) -> Result<Option<DatabaseTradeData>, FinanceError> {
+    let mut tx = pool.begin().await?;
+    let price: Option<(f64,)> = query_as("SELECT price::FLOAT8 FROM trades WHERE symbol = $1 FOR UPDATE")
+        .bind(symbol)
+        .fetch_optional(&mut *tx)
+        .await?;
+    let Some((price,)) = price else {
+        return Ok(None);
+    };
+
+    if price > 0.0 {
+        let (price_change, percentage_change, direction) = crate::price_change(price, previous_close);
+        query("UPDATE trades SET previous_close = $1, price_change = $2, percentage_change = $3, direction = $4 WHERE symbol = $5")
+            .bind(previous_close)
+            .bind(price_change)
+            .bind(percentage_change)
+            .bind(direction)
+            .bind(symbol)
+            .execute(&mut *tx)
+            .await?;
+    } else {
+        query("UPDATE trades SET previous_close = $1 WHERE symbol = $2")
+            .bind(previous_close)
+            .bind(symbol)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let statement = "
+        SELECT
+            symbol,
+            price::FLOAT8 as price,
+            previous_close::FLOAT8 as previous_close,
+            price_change::FLOAT8 as price_change,
+            percentage_change::FLOAT8 as percentage_change,
+            direction,
+            last_updated,
+            session
+        FROM trades
+        WHERE symbol = $1
+    ";
+    let row = query_as(statement).bind(symbol).fetch_one(&mut *tx).await?;
+    tx.commit().await?;
+    Ok(Some(row))
+}
+
 pub async fn get_trades(pool: Arc<PgPool>) -> Vec<DatabaseTradeData> {
     let statement = "
         SELECT
@@ -236,7 +453,8 @@ pub async fn get_trades(pool: Arc<PgPool>) -> Vec<DatabaseTradeData> {
             price_change::FLOAT8 as price_change,
             percentage_change::FLOAT8 as percentage_change,
             direction,
-            last_updated
+            last_updated,
+            session
         FROM trades
         ORDER BY symbol ASC
     ";
@@ -256,6 +474,110 @@ pub async fn get_trades(pool: Arc<PgPool>) -> Vec<DatabaseTradeData> {
     }
 }
 
+/// `trades` rows updated after `since`, for pollers that only redraw what
+/// changed. `None` returns every row, like [`get_trades`].
+pub async fn get_trades_since(pool: Arc<PgPool>, since: Option<chrono::DateTime<Utc>>) -> Result<Vec<DatabaseTradeData>, FinanceError> {
+    let statement = "
+        SELECT
+            symbol,
+            price::FLOAT8 as price,
+            previous_close::FLOAT8 as previous_close,
+            price_change::FLOAT8 as price_change,
+            percentage_change::FLOAT8 as percentage_change,
+            direction,
+            last_updated,
+            session
+        FROM trades
+        WHERE $1::TIMESTAMPTZ IS NULL OR last_updated > $1
+        ORDER BY symbol ASC
+    ";
+    let mut connection = pool.acquire().await?;
+    let rows = query_as(statement).bind(since).fetch_all(&mut *connection).await?;
+    Ok(rows)
+}
+
+/// Timestamp of the last price event stored for each symbol, for seeding
+/// the websocket client's dedup state. Empty on error — dedup then falls
+/// back to what the connection itself has seen.
+pub async fn get_last_trade_timestamps(pool: Arc<PgPool>) -> HashMap<String, u64> {
+    let statement = "SELECT symbol, trade_ts FROM trades WHERE trade_ts IS NOT NULL";
+    let res: Result<Vec<(String, i64)>, sqlx::Error> = async {
+        let mut connection = pool.acquire().await?;
+        let data = query_as(statement).fetch_all(&mut *connection).await?;
+        Ok(data)
+    }.await;
+
+    match res {
+        Ok(data) => data
+            .into_iter()
+            .filter_map(|(symbol, ts)| u64::try_from(ts).ok().map(|ts| (symbol, ts)))
+            .collect(),
+        Err(e) => {
+            log::error!("Failed to get last trade timestamps: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
+/// The `limit` enabled symbols with the largest move since the previous
+/// close: highest `percentage_change` first for [`MoverDirection::Up`],
+/// lowest first for [`MoverDirection::Down`]. Rows without a usable
+/// previous close are left out, since their percentage is meaningless.
+pub async fn get_movers(pool: Arc<PgPool>, direction: MoverDirection, limit: i64) -> Result<Vec<DatabaseTradeData>, FinanceError> {
+    // The sort keyword comes from the enum, never from the request.
+    let statement = format!("{MOVERS_QUERY} ORDER BY t.percentage_change {}, t.symbol ASC LIMIT $1", direction.sql_order());
+    let mut connection = pool.acquire().await?;
+    let rows = query_as(&statement).bind(limit).fetch_all(&mut *connection).await?;
+    Ok(rows)
+}
+
+/// `get_movers` without its ORDER BY. Ties go to the symbol so the order is
+/// stable between requests.
+const MOVERS_QUERY: &str = "
+    SELECT
+        t.symbol,
+        t.price::FLOAT8 as price,
+        t.previous_close::FLOAT8 as previous_close,
+        t.price_change::FLOAT8 as price_change,
+        t.percentage_change::FLOAT8 as percentage_change,
+        t.direction,
+        t.last_updated,
+        t.session
+    FROM trades t
+    JOIN tracked_symbols ts ON ts.symbol = t.symbol
+    WHERE ts.is_enabled = TRUE
+      AND t.previous_close IS NOT NULL AND t.previous_close <> 0
+      AND t.price IS NOT NULL AND t.percentage_change IS NOT NULL
+";
+
+/// Records a split for `symbol` taking effect on `effective_date`. Recording
+/// the same date again replaces the ratio.
+pub async fn upsert_split_adjustment(pool: Arc<PgPool>, symbol: &str, ratio: f64, effective_date: chrono::NaiveDate) -> Result<(), FinanceError> {
+    let statement = "INSERT INTO split_adjustments (symbol, ratio, effective_date) VALUES ($1, $2, $3) ON CONFLICT (symbol, effective_date) DO UPDATE SET ratio = EXCLUDED.ratio";
+    let mut connection = pool.acquire().await?;
+    query(statement).bind(symbol).bind(ratio).bind(effective_date).execute(&mut *connection).await?;
+    Ok(())
+}
+
+/// Split ratios taking effect on `date`, keyed by symbol. Several splits for
+/// one symbol on the same date can't be recorded, so each symbol appears once.
+pub async fn get_split_ratios_on(pool: Arc<PgPool>, date: chrono::NaiveDate) -> HashMap<String, f64> {
+    let statement = "SELECT symbol, ratio::FLOAT8 FROM split_adjustments WHERE effective_date = $1";
+    let res: Result<Vec<(String, f64)>, sqlx::Error> = async {
+        let mut connection = pool.acquire().await?;
+        let data = query_as(statement).bind(date).fetch_all(&mut *connection).await?;
+        Ok(data)
+    }.await;
+
+    match res {
+        Ok(data) => data.into_iter().collect(),
+        Err(e) => {
+            log::error!("Failed to get split adjustments: {}", e);
+            HashMap::new()
+        }
+    }
+}
+
 /// Returns symbols where exchange is NULL (need metadata fetch).
 pub async fn get_symbols_without_exchange(pool: Arc<PgPool>) -> Vec<String> {
     let statement = "SELECT symbol FROM tracked_symbols WHERE exchange IS NULL AND is_enabled = TRUE";
@@ -298,7 +620,7 @@ pub async fn update_symbol_exchange_link(
     symbol: &str,
     exchange: Option<&str>,
     link: &str,
-) -> Result<()> {
+) -> Result<(), FinanceError> {
     let statement = "UPDATE tracked_symbols SET exchange = $1, link = $2 WHERE symbol = $3";
     let mut connection = pool.acquire().await?;
     query(statement)
@@ -309,3 +631,103 @@ pub async fn update_symbol_exchange_link(
         .await?;
     Ok(())
 }
+
+/// `dead_letter.service` value for rows written by this service.
+const DEAD_LETTER_SERVICE: &str = "finance";
+
+/// Cap on a stored payload, matching the other services' `dead_letter` rows.
+const MAX_DEAD_LETTER_PAYLOAD_BYTES: usize = 16 * 1024;
+
+/// Days a dead letter is kept after it was last seen; pruned by the daily
+/// maintenance task.
+pub const DEAD_LETTER_RETENTION_DAYS: i32 = 14;
+
+/// Frames waiting on the dead-letter writer. A burst past this is dropped
+/// rather than queued without limit.
+const DEAD_LETTER_QUEUE_CAPACITY: usize = 256;
+
+#[derive(FromRow, serde::Serialize, Debug)]
+pub struct DeadLetter {
+    pub id: i64,
+    pub source: String,
+    pub reason: String,
+    pub raw_payload: String,
+    pub occurrences: i32,
+    pub created_at: chrono::DateTime<Utc>,
+    pub last_seen_at: chrono::DateTime<Utc>,
+}
+
+/// Parks a TwelveData frame that was dropped instead of applied. Another
+/// frame from the same source for the same reason bumps `occurrences` and
+/// keeps the latest payload. Errors are only logged: the caller must keep
+/// going either way.
+pub async fn record_dead_letter(pool: Arc<PgPool>, source: &str, reason: &str, raw_payload: &str) {
+    let statement = "INSERT INTO dead_letter (service, source, reason, raw_payload) VALUES ($1, $2, $3, $4) ON CONFLICT (service, source, reason) DO UPDATE SET occurrences = dead_letter.occurrences + 1, raw_payload = EXCLUDED.raw_payload, last_seen_at = CURRENT_TIMESTAMP";
+    let res: Result<(), sqlx::Error> = async {
+        let mut connection = pool.acquire().await?;
+        query(statement)
+            .bind(DEAD_LETTER_SERVICE)
+            .bind(source)
+            .bind(reason)
+            .bind(truncate_bytes_safe(raw_payload, MAX_DEAD_LETTER_PAYLOAD_BYTES))
+            .execute(&mut *connection)
+            .await?;
+        Ok(())
+    }.await;
+
+    if let Err(e) = res {
+        log::error!("Failed to record dead letter ({}): {}", reason, e);
+    }
+}
+
+/// Hands dropped frames to a background writer so the WebSocket read loop
+/// never waits on the database. The writer exits once every handle is gone.
+#[derive(Clone)]
+pub struct DeadLetterQueue {
+    sender: mpsc::Sender<(&'static str, String)>,
+}
+
+impl DeadLetterQueue {
+    /// Start a writer that parks each queued frame under `source`.
+    pub fn spawn(pool: Arc<PgPool>, source: &'static str) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<(&'static str, String)>(DEAD_LETTER_QUEUE_CAPACITY);
+        tokio::spawn(async move {
+            while let Some((reason, raw_payload)) = receiver.recv().await {
+                record_dead_letter(pool.clone(), source, reason, &raw_payload).await;
+            }
+        });
+        Self { sender }
+    }
+
+    /// Queue a frame without waiting; drops it if the writer is backed up.
+    pub fn park(&self, reason: &'static str, raw_payload: String) {
+        if self.sender.try_send((reason, raw_payload)).is_err() {
+            log::warn!("Dead-letter queue full, dropping a frame ({})", reason);
+        }
+    }
+}
+
+/// Delete this service's dead letters last seen more than `retention_days`
+/// ago. Returns how many rows went.
+pub async fn prune_dead_letters(pool: Arc<PgPool>, retention_days: i32) -> Result<u64, FinanceError> {
+    let statement = "DELETE FROM dead_letter WHERE service = $1 AND last_seen_at < now() - make_interval(days => $2)";
+    let mut connection = pool.acquire().await?;
+    let result = query(statement)
+        .bind(DEAD_LETTER_SERVICE)
+        .bind(retention_days)
+        .execute(&mut *connection)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Most recently seen dead letters for this service, newest first.
+pub async fn get_dead_letters(pool: Arc<PgPool>, limit: i64) -> Result<Vec<DeadLetter>, FinanceError> {
+    let statement = "SELECT id, source, reason, raw_payload, occurrences, created_at, last_seen_at FROM dead_letter WHERE service = $1 ORDER BY last_seen_at DESC LIMIT $2";
+    let mut connection = pool.acquire().await?;
+    let rows = query_as(statement)
+        .bind(DEAD_LETTER_SERVICE)
+        .bind(limit)
+        .fetch_all(&mut *connection)
+        .await?;
+    Ok(rows)
+}
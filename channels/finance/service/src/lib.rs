@@ -1,78 +1,683 @@
-use std::{sync::Arc, time::Duration, fs};
+use std::{env, sync::{Arc, OnceLock, atomic::{AtomicBool, Ordering}}, time::Duration, fs};
 
-use chrono::{Timelike, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc, Weekday};
 use futures_util::future::join_all;
-use reqwest::Client;
-use tokio::{sync::Mutex, time::{self, sleep}};
+use reqwest::{Client, StatusCode};
+use tokio::{sync::{Mutex, OwnedMutexGuard}, time::{self, sleep}};
 use crate::log::{error, info, warn};
 use crate::database::{
-    PgPool, insert_symbol, update_previous_close, update_trade, get_tracked_symbols,
-    seed_tracked_symbols, get_symbols_without_exchange, get_all_enabled_symbols,
-    update_symbol_exchange_link,
+    PgPool, insert_symbols, update_previous_close, update_trade, get_tracked_symbols,
+    seed_tracked_symbols, reseed_tracked_symbols, get_symbols_without_exchange, get_all_enabled_symbols,
+    update_symbol_exchange_link, recompute_trade, get_split_ratios_on, set_symbol_enabled, DatabaseTradeData,
+    prune_dead_letters, DEAD_LETTER_RETENTION_DAYS,
 };
 
-use crate::{types::{FinanceHealth, FinanceState, QuoteResponse, TrackedSymbolConfig, TwelveDataStocksResponse}, websocket::connect};
+use crate::latency::FetchLatency;
+use crate::{types::{ConfiguredSymbol, FinanceConfig, ReseedSummary, FinanceError, FinanceHealth, FinanceState, QuoteResponse, TrackedSymbolConfig, TradeSession, TwelveDataStocksResponse}, websocket::{connect, DEFAULT_WS_URL, MAX_SYMBOL_LEN}};
 
 pub mod types;
 mod websocket;
 pub mod log;
 pub mod database;
+pub mod auth;
 pub mod init;
+pub mod latency;
+pub mod retry;
+pub mod text;
 
-pub async fn start_finance_services(pool: Arc<PgPool>, health_state: Arc<Mutex<FinanceHealth>>) {
+/// Symbols seeded into `tracked_symbols` on startup.
+pub const SUBSCRIPTIONS_CONFIG_PATH: &str = "./configs/subscriptions.json";
+
+/// Compact symbol list read when [`SUBSCRIPTIONS_CONFIG_PATH`] doesn't
+/// exist; see [`parse_symbol_list`] for the format.
+pub const SYMBOLS_ENV: &str = "FINANCE_SYMBOLS";
+
+/// Category for [`SYMBOLS_ENV`] entries that don't give one.
+const DEFAULT_SYMBOL_CATEGORY: &str = "Other";
+
+/// TwelveData REST API, overridable with `TWELVEDATA_REST_URL`.
+const DEFAULT_REST_URL: &str = "https://api.twelvedata.com";
+
+/// Wait before re-establishing a dropped or failed WebSocket connection.
+pub const WS_RECONNECT_DELAY: Duration = Duration::from_secs(300);
+
+/// Wait before re-quoting symbols whose previous close came back as 0.
+pub const CLOSE_RETRY_DELAY: Duration = Duration::from_secs(30 * 60);
+
+/// Retries of a zeroed previous close before leaving it to the next
+/// scheduled refresh; six half-hour waits cover the first trading hours.
+pub const CLOSE_RETRY_ATTEMPTS: u32 = 6;
+
+/// Default daily previous-close refresh time (UTC hour, minute), shortly
+/// after the US close. Override with `FINANCE_CLOSE_REFRESH_UTC`.
+pub const PREVIOUS_CLOSE_REFRESH_UTC: (u32, u32) = (21, 30);
+
+/// The scheduled previous-close refresh time, or `None` when the internal
+/// schedule is switched off. Read once from `FINANCE_CLOSE_REFRESH_UTC`.
+pub fn close_refresh_time() -> Option<(u32, u32)> {
+    static REFRESH_TIME: OnceLock<Option<(u32, u32)>> = OnceLock::new();
+    *REFRESH_TIME.get_or_init(|| parse_close_refresh_time(std::env::var("FINANCE_CLOSE_REFRESH_UTC").ok().as_deref()))
+}
+
+/// Parse `HH:MM` (UTC). `off` disables the schedule, for deployments that
+/// trigger `POST /refresh-closes` externally; anything unparseable falls back
+/// to [`PREVIOUS_CLOSE_REFRESH_UTC`] with a warning.
+pub fn parse_close_refresh_time(raw: Option<&str>) -> Option<(u32, u32)> {
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Some(PREVIOUS_CLOSE_REFRESH_UTC);
+    };
+    if raw.eq_ignore_ascii_case("off") {
+        return None;
+    }
+    let parsed = raw
+        .split_once(':')
+        .and_then(|(h, m)| Some((h.parse::<u32>().ok()?, m.parse::<u32>().ok()?)))
+        .filter(|&(h, m)| h < 24 && m < 60);
+    if parsed.is_none() {
+        let (h, m) = PREVIOUS_CLOSE_REFRESH_UTC;
+        warn!("Invalid FINANCE_CLOSE_REFRESH_UTC {:?}; using {h:02}:{m:02}", raw);
+    }
+    parsed.or(Some(PREVIOUS_CLOSE_REFRESH_UTC))
+}
+
+/// Quotes the previous-close refresh requests at once unless
+/// `FINANCE_QUOTE_CHUNK_SIZE` says otherwise.
+pub const DEFAULT_QUOTE_CHUNK_SIZE: usize = 8;
+
+/// Pause before each chunk of quotes unless `FINANCE_QUOTE_CHUNK_DELAY_MS`
+/// says otherwise.
+pub const DEFAULT_QUOTE_CHUNK_DELAY_MS: u64 = 1_000;
+
+/// How the previous-close refresh spreads its quote requests: `chunk_size`
+/// in parallel, `delay` before each chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotePacing {
+    pub chunk_size: usize,
+    pub delay: Duration,
+}
+
+/// The refresh's quote pacing, read once from `FINANCE_QUOTE_CHUNK_SIZE` and
+/// `FINANCE_QUOTE_CHUNK_DELAY_MS`. Every quote costs one TwelveData API
+/// credit, so the refresh spends at most `chunk_size` credits per
+/// `delay`: the defaults come to about 480 a minute, inside the Pro plan's
+/// 610. Lower plans need a smaller chunk or a longer delay.
+pub fn quote_pacing() -> QuotePacing {
+    static PACING: OnceLock<QuotePacing> = OnceLock::new();
+    *PACING.get_or_init(|| {
+        let pacing = parse_quote_pacing(
+            std::env::var("FINANCE_QUOTE_CHUNK_SIZE").ok().as_deref(),
+            std::env::var("FINANCE_QUOTE_CHUNK_DELAY_MS").ok().as_deref(),
+        );
+        info!("Quote pacing: {} per chunk, {:?} between chunks", pacing.chunk_size, pacing.delay);
+        pacing
+    })
+}
+
+/// Parse both settings as positive integers; a missing value uses its
+/// default and an invalid one (including 0) falls back with a warning.
+pub fn parse_quote_pacing(chunk_size: Option<&str>, delay_ms: Option<&str>) -> QuotePacing {
+    QuotePacing {
+        chunk_size: parse_positive("FINANCE_QUOTE_CHUNK_SIZE", chunk_size, DEFAULT_QUOTE_CHUNK_SIZE),
+        delay: Duration::from_millis(parse_positive("FINANCE_QUOTE_CHUNK_DELAY_MS", delay_ms, DEFAULT_QUOTE_CHUNK_DELAY_MS)),
+    }
+}
+
+fn parse_positive<T>(var: &str, raw: Option<&str>, default: T) -> T
+where
+    T: std::str::FromStr + PartialOrd + Default + std::fmt::Display,
+{
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return default;
+    };
+    match raw.parse::<T>() {
+        Ok(n) if n > T::default() => n,
+        _ => {
+            warn!("Invalid {} {:?}; using {}", var, raw, default);
+            default
+        }
+    }
+}
+
+/// Symbols per subscribe frame when the price stream connects, unless
+/// `FINANCE_WS_SUBSCRIBE_CHUNK_SIZE` says otherwise.
+pub const DEFAULT_WS_SUBSCRIBE_CHUNK_SIZE: usize = 50;
+
+/// Pause between subscribe frames unless `FINANCE_WS_SUBSCRIBE_CHUNK_DELAY_MS`
+/// says otherwise.
+pub const DEFAULT_WS_SUBSCRIBE_CHUNK_DELAY_MS: u64 = 250;
+
+/// How a bulk subscribe is split: `chunk_size` symbols per frame, `delay`
+/// between frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubscribePacing {
+    pub chunk_size: usize,
+    pub delay: Duration,
+}
+
+/// The subscribe pacing, read once from `FINANCE_WS_SUBSCRIBE_CHUNK_SIZE`
+/// and `FINANCE_WS_SUBSCRIBE_CHUNK_DELAY_MS`. A long symbol list sent as one
+/// frame on connect can have part of it dropped by TwelveData; smaller,
+/// spaced frames get through.
+pub fn ws_subscribe_pacing() -> SubscribePacing {
+    static PACING: OnceLock<SubscribePacing> = OnceLock::new();
+    *PACING.get_or_init(|| {
+        let pacing = parse_subscribe_pacing(
+            std::env::var("FINANCE_WS_SUBSCRIBE_CHUNK_SIZE").ok().as_deref(),
+            std::env::var("FINANCE_WS_SUBSCRIBE_CHUNK_DELAY_MS").ok().as_deref(),
+        );
+        info!("Subscribe pacing: {} symbols per frame, {:?} between frames", pacing.chunk_size, pacing.delay);
+        pacing
+    })
+}
+
+/// Parse both settings as positive integers, like [`parse_quote_pacing`].
+pub fn parse_subscribe_pacing(chunk_size: Option<&str>, delay_ms: Option<&str>) -> SubscribePacing {
+    SubscribePacing {
+        chunk_size: parse_positive("FINANCE_WS_SUBSCRIBE_CHUNK_SIZE", chunk_size, DEFAULT_WS_SUBSCRIBE_CHUNK_SIZE),
+        delay: Duration::from_millis(parse_positive(
+            "FINANCE_WS_SUBSCRIBE_CHUNK_DELAY_MS",
+            delay_ms,
+            DEFAULT_WS_SUBSCRIBE_CHUNK_DELAY_MS,
+        )),
+    }
+}
+
+/// Symbols per insert when startup ensures every subscription has a
+/// `trades` row, unless `FINANCE_SYMBOL_INIT_CHUNK_SIZE` says otherwise.
+pub const DEFAULT_SYMBOL_INIT_CHUNK_SIZE: usize = 100;
+
+/// The startup insert's chunk size, read once from
+/// `FINANCE_SYMBOL_INIT_CHUNK_SIZE`.
+pub fn symbol_init_chunk_size() -> usize {
+    static SIZE: OnceLock<usize> = OnceLock::new();
+    *SIZE.get_or_init(|| parse_symbol_init_chunk_size(std::env::var("FINANCE_SYMBOL_INIT_CHUNK_SIZE").ok().as_deref()))
+}
+
+/// Parse a positive integer, falling back to
+/// [`DEFAULT_SYMBOL_INIT_CHUNK_SIZE`] with a warning.
+pub fn parse_symbol_init_chunk_size(raw: Option<&str>) -> usize {
+    parse_positive("FINANCE_SYMBOL_INIT_CHUNK_SIZE", raw, DEFAULT_SYMBOL_INIT_CHUNK_SIZE)
+}
+
+/// Seconds without any frame from TwelveData before the watchdog drops the
+/// socket, unless `TWELVEDATA_WS_IDLE_TIMEOUT_SECS` says otherwise.
+/// TwelveData answers our 30s heartbeat, so a live stream is never this quiet.
+pub const DEFAULT_WS_IDLE_TIMEOUT_SECS: u64 = 120;
+
+/// Shortest accepted idle timeout: two heartbeat round trips, so one slow
+/// heartbeat reply can't trip it.
+pub const MIN_WS_IDLE_TIMEOUT_SECS: u64 = 60;
+
+/// How long the price stream may stay silent during the US regular session
+/// (see [`trade_session`]) before the connection is dropped and
+/// re-established, or `None` when the watchdog is off. Read once from `TWELVEDATA_WS_IDLE_TIMEOUT_SECS`.
+pub fn ws_idle_timeout() -> Option<Duration> {
+    static TIMEOUT: OnceLock<Option<u64>> = OnceLock::new();
+    TIMEOUT
+        .get_or_init(|| parse_ws_idle_timeout_secs(std::env::var("TWELVEDATA_WS_IDLE_TIMEOUT_SECS").ok().as_deref()))
+        .map(Duration::from_secs)
+}
+
+/// Parse an idle timeout in seconds. `0` or `off` disables the watchdog;
+/// values below [`MIN_WS_IDLE_TIMEOUT_SECS`] or unparseable ones fall back
+/// to [`DEFAULT_WS_IDLE_TIMEOUT_SECS`] with a warning.
+pub fn parse_ws_idle_timeout_secs(raw: Option<&str>) -> Option<u64> {
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Some(DEFAULT_WS_IDLE_TIMEOUT_SECS);
+    };
+    if raw == "0" || raw.eq_ignore_ascii_case("off") {
+        return None;
+    }
+    match raw.parse::<u64>() {
+        Ok(n) if n >= MIN_WS_IDLE_TIMEOUT_SECS => Some(n),
+        _ => {
+            warn!(
+                "Invalid TWELVEDATA_WS_IDLE_TIMEOUT_SECS {:?} (minimum {}); using {}",
+                raw, MIN_WS_IDLE_TIMEOUT_SECS, DEFAULT_WS_IDLE_TIMEOUT_SECS
+            );
+            Some(DEFAULT_WS_IDLE_TIMEOUT_SECS)
+        }
+    }
+}
+
+/// US Eastern's offset from UTC at `at`: EDT (-4h) from 2am on the second
+/// Sunday of March until 2am on the first Sunday of November, EST (-5h)
+/// otherwise.
+fn us_eastern_offset(at: DateTime<Utc>) -> chrono::Duration {
+    let nth_sunday = |month: u32, n: i64| {
+        let first = NaiveDate::from_ymd_opt(at.year(), month, 1).expect("valid first of month");
+        let to_sunday = (7 - i64::from(first.weekday().num_days_from_sunday())) % 7;
+        first + chrono::Duration::days(to_sunday + 7 * (n - 1))
+    };
+    let dst_start = nth_sunday(3, 2).and_hms_opt(7, 0, 0).expect("valid time").and_utc();
+    let dst_end = nth_sunday(11, 1).and_hms_opt(6, 0, 0).expect("valid time").and_utc();
+    if (dst_start..dst_end).contains(&at) {
+        chrono::Duration::hours(-4)
+    } else {
+        chrono::Duration::hours(-5)
+    }
+}
+
+/// The US equity session a price event at `at` belongs to, by Eastern wall
+/// clock, tracking daylight saving. Holidays aren't known and count as
+/// weekdays.
+pub fn trade_session(at: DateTime<Utc>) -> TradeSession {
+    let local = at.naive_utc() + us_eastern_offset(at);
+    if matches!(local.weekday(), Weekday::Sat | Weekday::Sun) {
+        return TradeSession::Closed;
+    }
+    match local.hour() * 60 + local.minute() {
+        240..570 => TradeSession::Pre,
+        570..960 => TradeSession::Regular,
+        960..1200 => TradeSession::Post,
+        _ => TradeSession::Closed,
+    }
+}
+
+/// [`trade_session`] for an equity; `None` for crypto and forex pairs
+/// (`BTC/USD`), which trade around the clock.
+pub fn session_for(symbol: &str, at: DateTime<Utc>) -> Option<TradeSession> {
+    (!symbol.contains('/')).then(|| trade_session(at))
+}
+
+/// Trades written concurrently per batch unless `FINANCE_BATCH_CONCURRENCY`
+/// says otherwise.
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 5;
+
+/// Pool ceiling when `DB_MAX_CONNECTIONS` is unset; see the sizing note
+/// in [`database::initialize_pool`].
+pub const DEFAULT_DB_MAX_CONNECTIONS: u32 = 20;
+
+/// Warm connections kept when `DB_MIN_CONNECTIONS` is unset.
+pub const DEFAULT_DB_MIN_CONNECTIONS: u32 = 1;
+
+/// Bounds for the Postgres connection pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSize {
+    pub max_connections: u32,
+    pub min_connections: u32,
+}
+
+/// Pool bounds, read once from `DB_MAX_CONNECTIONS` / `DB_MIN_CONNECTIONS`.
+pub fn db_pool_size() -> PoolSize {
+    static SIZE: OnceLock<PoolSize> = OnceLock::new();
+    *SIZE.get_or_init(|| {
+        parse_pool_size(
+            env::var("DB_MAX_CONNECTIONS").ok().as_deref(),
+            env::var("DB_MIN_CONNECTIONS").ok().as_deref(),
+        )
+    })
+}
+
+/// Parse the pool bounds. A maximum that isn't a positive number, or a
+/// minimum that isn't a number, falls back to its default; a minimum above
+/// the maximum is lowered to it. Each is logged.
+pub fn parse_pool_size(max_raw: Option<&str>, min_raw: Option<&str>) -> PoolSize {
+    let parse = |var: &str, raw: Option<&str>, default: u32, floor: u32| {
+        match raw.map(str::trim).filter(|s| !s.is_empty()) {
+            None => default,
+            Some(s) => match s.parse::<u32>() {
+                Ok(n) if n >= floor => n,
+                _ => {
+                    warn!("Invalid {}={:?}, using default {}", var, s, default);
+                    default
+                }
+            },
+        }
+    };
+    let max_connections = parse("DB_MAX_CONNECTIONS", max_raw, DEFAULT_DB_MAX_CONNECTIONS, 1);
+    let mut min_connections = parse("DB_MIN_CONNECTIONS", min_raw, DEFAULT_DB_MIN_CONNECTIONS, 0);
+    if min_connections > max_connections {
+        warn!(
+            "DB_MIN_CONNECTIONS={} is above DB_MAX_CONNECTIONS={}; using {}",
+            min_connections, max_connections, max_connections
+        );
+        min_connections = max_connections;
+    }
+    PoolSize { max_connections, min_connections }
+}
+
+/// Pool connections a batch never claims, left for the symbol refresh, the
+/// HTTP handlers and the batch's own `get_trades` read.
+const BATCH_POOL_HEADROOM: usize = 4;
+
+/// How many trades a batch writes at once. Read once from
+/// `FINANCE_BATCH_CONCURRENCY` and clamped to what `pool` can spare.
+pub fn batch_concurrency(pool: &PgPool) -> usize {
+    static CONCURRENCY: OnceLock<usize> = OnceLock::new();
+    *CONCURRENCY.get_or_init(|| {
+        let concurrency = parse_batch_concurrency(
+            std::env::var("FINANCE_BATCH_CONCURRENCY").ok().as_deref(),
+            pool.options().get_max_connections() as usize,
+        );
+        info!("Batch write concurrency: {}", concurrency);
+        concurrency
+    })
+}
+
+/// Parse a positive integer, falling back to [`DEFAULT_BATCH_CONCURRENCY`]
+/// with a warning, then cap it at `pool_max` minus [`BATCH_POOL_HEADROOM`]
+/// (never below 1) so a batch can't starve the rest of the service of
+/// connections.
+pub fn parse_batch_concurrency(raw: Option<&str>, pool_max: usize) -> usize {
+    let requested = match raw.map(str::trim).filter(|s| !s.is_empty()) {
+        None => DEFAULT_BATCH_CONCURRENCY,
+        Some(raw) => match raw.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                warn!("Invalid FINANCE_BATCH_CONCURRENCY {:?}; using {}", raw, DEFAULT_BATCH_CONCURRENCY);
+                DEFAULT_BATCH_CONCURRENCY
+            }
+        },
+    };
+    let cap = pool_max.saturating_sub(BATCH_POOL_HEADROOM).max(1);
+    if requested > cap {
+        warn!(
+            "FINANCE_BATCH_CONCURRENCY {} exceeds what the {}-connection pool can spare; using {}",
+            requested, pool_max, cap
+        );
+        return cap;
+    }
+    requested
+}
+
+/// Why a previous-close refresh didn't start.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RefreshRejected {
+    /// The finance service hasn't finished initializing.
+    NotReady,
+    /// Another refresh is still in progress.
+    AlreadyRunning,
+}
+
+/// Single entry point for [`update_all_previous_closes`], shared by the
+/// startup run, the daily schedule and `POST /refresh-closes`, so two
+/// refreshes never overlap and double the TwelveData credit spend. A
+/// refresh counts as running until its deferred retries finish too.
+#[derive(Default)]
+pub struct CloseRefresher {
+    state: OnceLock<FinanceState>,
+    running: Arc<Mutex<()>>,
+}
+
+impl CloseRefresher {
+    /// The service state, once startup has initialized it.
+    pub fn state(&self) -> Option<&FinanceState> {
+        self.state.get()
+    }
+
+    /// Refresh now and wait for it to finish.
+    pub async fn run(&self) -> Result<(), RefreshRejected> {
+        let state = self.state.get().ok_or(RefreshRejected::NotReady)?.clone();
+        let guard = self.running.clone().try_lock_owned().map_err(|_| RefreshRejected::AlreadyRunning)?;
+        let deferred = update_all_previous_closes(state.clone()).await;
+        retry_holding(state, deferred, guard);
+        Ok(())
+    }
+
+    /// Start a refresh in the background and return straight away.
+    pub fn spawn(&self) -> Result<(), RefreshRejected> {
+        let state = self.state.get().ok_or(RefreshRejected::NotReady)?.clone();
+        let guard = self.running.clone().try_lock_owned().map_err(|_| RefreshRejected::AlreadyRunning)?;
+        tokio::spawn(async move {
+            let deferred = update_all_previous_closes(state.clone()).await;
+            retry_holding(state, deferred, guard);
+        });
+        Ok(())
+    }
+}
+
+/// Retry zeroed closes in the background, keeping `guard` until the
+/// retries are done so no other refresh starts in the meantime.
+fn retry_holding(state: FinanceState, deferred: Vec<String>, guard: OwnedMutexGuard<()>) {
+    if deferred.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        retry_deferred_closes(state, deferred).await;
+        drop(guard);
+    });
+}
+
+/// `path` made absolute when it exists, so `/config` shows which file the
+/// pod actually read; otherwise returned as given.
+pub fn resolve_config_path(path: &str) -> String {
+    fs::canonicalize(path)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// Strip credentials and the query string (where an `apikey` would live)
+/// from a URL before it is shown in `/config`. Anything unparseable is
+/// withheld entirely.
+pub fn redact_url(raw: &str) -> String {
+    match reqwest::Url::parse(raw) {
+        Ok(mut url) => {
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            if url.query().is_some() {
+                url.set_query(Some("redacted"));
+            }
+            url.to_string()
+        }
+        Err(_) => "<unparseable>".to_string(),
+    }
+}
+
+/// Everything in [`FinanceConfig`] except the symbol list, which
+/// [`start_finance_services`] fills in once it has loaded it.
+pub fn startup_config() -> FinanceConfig {
+    let env_or = |var: &str, default: &str| std::env::var(var).unwrap_or_else(|_| default.to_string());
+    FinanceConfig {
+        config_file: resolve_config_path(SUBSCRIPTIONS_CONFIG_PATH),
+        ws_url: redact_url(&env_or("TWELVEDATA_WS_URL", DEFAULT_WS_URL)),
+        rest_url: redact_url(&env_or("TWELVEDATA_REST_URL", DEFAULT_REST_URL)),
+        api_key_configured: std::env::var("TWELVEDATA_API_KEY").is_ok_and(|k| !k.trim().is_empty()),
+        ws_reconnect_delay_secs: WS_RECONNECT_DELAY.as_secs(),
+        db_max_connections: db_pool_size().max_connections,
+        db_min_connections: db_pool_size().min_connections,
+        previous_close_refresh_utc: close_refresh_time()
+            .map_or_else(|| "off".to_string(), |(hour, minute)| format!("{hour:02}:{minute:02}")),
+        quote_chunk_size: quote_pacing().chunk_size,
+        quote_chunk_delay_ms: quote_pacing().delay.as_millis() as u64,
+        ws_subscribe_chunk_size: ws_subscribe_pacing().chunk_size,
+        ws_subscribe_chunk_delay_ms: ws_subscribe_pacing().delay.as_millis() as u64,
+        symbols_loaded_at: None,
+        symbols: Vec::new(),
+    }
+}
+
+/// Symbols to seed `tracked_symbols` with, as loaded by
+/// [`load_symbol_configs`].
+struct SymbolSeed {
+    /// `"file"` or `"env"`, for `/config`.
+    source: &'static str,
+    entries: Vec<TrackedSymbolConfig>,
+    skipped: usize,
+}
+
+/// `subscriptions.json`, or the compact [`SYMBOLS_ENV`] list when the file
+/// doesn't exist. `None` when there is neither.
+fn load_symbol_configs() -> Result<Option<SymbolSeed>, FinanceError> {
+    if let Ok(contents) = fs::read_to_string(SUBSCRIPTIONS_CONFIG_PATH) {
+        let entries: Vec<TrackedSymbolConfig> = serde_json::from_str(&contents)?;
+        let total = entries.len();
+        let entries: Vec<TrackedSymbolConfig> = entries
+            .into_iter()
+            .filter(|e| !e.symbol.trim().is_empty())
+            .collect();
+        let skipped = total - entries.len();
+        return Ok(Some(SymbolSeed { source: "file", entries, skipped }));
+    }
+    Ok(env::var(SYMBOLS_ENV)
+        .ok()
+        .filter(|raw| !raw.trim().is_empty())
+        .map(|raw| {
+            let (entries, skipped) = parse_symbol_list(&raw);
+            SymbolSeed { source: "env", entries, skipped }
+        }))
+}
+
+/// Parse the compact [`SYMBOLS_ENV`] form, for deployments without a config
+/// volume: comma-separated `SYMBOL[:category]` entries, e.g.
+/// `AAPL:Technology,BTC/USD:Crypto,SPY`. Symbols are upper-cased and double
+/// as the display name; the category defaults to `Other`. Returns the valid
+/// entries and how many were logged and skipped.
+pub fn parse_symbol_list(raw: &str) -> (Vec<TrackedSymbolConfig>, usize) {
+    let mut valid: Vec<TrackedSymbolConfig> = Vec::new();
+    let mut skipped = 0;
+    for (i, entry) in raw.split(',').map(str::trim).filter(|e| !e.is_empty()).enumerate() {
+        let (symbol, category) = match entry.split_once(':') {
+            Some((symbol, category)) => (symbol.trim(), category.trim()),
+            None => (entry, DEFAULT_SYMBOL_CATEGORY),
+        };
+        let symbol = symbol.to_ascii_uppercase();
+        let problem = if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LEN {
+            Some(format!("symbol must be 1-{MAX_SYMBOL_LEN} characters"))
+        } else if !symbol.bytes().all(|b| b.is_ascii_alphanumeric() || b"./-_=^".contains(&b)) {
+            Some(format!("symbol {symbol:?} has unexpected characters"))
+        } else if category.is_empty() || category.contains(':') {
+            Some("expected SYMBOL or SYMBOL:category".to_string())
+        } else if valid.iter().any(|e| e.symbol == symbol) {
+            Some("duplicate symbol".to_string())
+        } else {
+            None
+        };
+        match problem {
+            Some(problem) => {
+                warn!("Skipping {} entry {} ({:?}): {}", SYMBOLS_ENV, i, entry, problem);
+                skipped += 1;
+            }
+            None => valid.push(TrackedSymbolConfig {
+                name: symbol.clone(),
+                symbol,
+                category: category.to_string(),
+                exchange: None,
+            }),
+        }
+    }
+    (valid, skipped)
+}
+
+/// Re-read `subscriptions.json` (or [`SYMBOLS_ENV`]) and insert any symbol
+/// not yet in `tracked_symbols`. Existing rows keep their metadata and
+/// enabled flag. The WebSocket's periodic resubscribe picks up what was
+/// added.
+pub async fn reseed_symbols(pool: Arc<PgPool>) -> Result<ReseedSummary, FinanceError> {
+    let SymbolSeed { entries, skipped, .. } = load_symbol_configs()?.ok_or_else(|| {
+        FinanceError::Config(format!("failed to read {SUBSCRIPTIONS_CONFIG_PATH} and {SYMBOLS_ENV} is not set"))
+    })?;
+    let added = reseed_tracked_symbols(pool, &entries).await?;
+    info!("Reseeded symbols from config: {} added, {} skipped", added, skipped);
+    Ok(ReseedSummary {
+        added,
+        existing: entries.len() as u64 - added,
+        skipped,
+    })
+}
+
+pub async fn start_finance_services(pool: Arc<PgPool>, health_state: Arc<Mutex<FinanceHealth>>, config_state: Arc<Mutex<FinanceConfig>>, refresher: Arc<CloseRefresher>) {
     info!("Starting finance service...");
 
     // Seed from JSON if database is empty, or update name/category for existing symbols
     let existing = get_tracked_symbols(pool.clone()).await;
-    if let Ok(file_contents) = fs::read_to_string("./configs/subscriptions.json")
-        && let Ok(entries) = serde_json::from_str::<Vec<TrackedSymbolConfig>>(&file_contents)
-    {
-        if existing.is_empty() {
-            info!("Database tracked_symbols is empty, seeding from local config...");
-        } else {
-            info!("Syncing name/category metadata for tracked symbols...");
+    let mut config_symbols: Vec<String> = Vec::new();
+    let mut config_source = "file";
+    match load_symbol_configs() {
+        Ok(Some(SymbolSeed { source, entries, skipped })) => {
+            if existing.is_empty() {
+                info!("Database tracked_symbols is empty, seeding from {} config ({} skipped)...", source, skipped);
+            } else {
+                info!("Syncing name/category metadata for tracked symbols...");
+            }
+            config_source = source;
+            config_symbols = entries.iter().map(|e| e.symbol.clone()).collect();
+            let summary = seed_tracked_symbols(pool.clone(), entries).await;
+            if summary.unchanged {
+                info!("Tracked symbols already match config, nothing to seed");
+            } else if summary.failed.is_empty() {
+                info!("Seeded {} symbols ({} duplicates skipped)", summary.seeded, summary.skipped);
+            } else {
+                error!(
+                    "Seeded {} symbols ({} duplicates skipped), {} failed",
+                    summary.seeded, summary.skipped, summary.failed.len()
+                );
+            }
         }
-        let _ = seed_tracked_symbols(pool.clone(), entries).await;
+        Ok(None) => warn!("Could not read {} and {} is not set", SUBSCRIPTIONS_CONFIG_PATH, SYMBOLS_ENV),
+        Err(e) => error!("Failed to parse {}: {}", SUBSCRIPTIONS_CONFIG_PATH, e),
     }
 
     // Initialization with database-driven state
     let state = FinanceState::new(Arc::clone(&pool)).await;
+    {
+        let mut config = config_state.lock().await;
+        config.symbols = state.subscriptions.iter()
+            .map(|symbol| ConfiguredSymbol {
+                symbol: symbol.clone(),
+                source: if config_symbols.contains(symbol) { config_source } else { "db" },
+            })
+            .collect();
+        config.symbols_loaded_at = Some(Utc::now());
+    }
     initialize_symbols(state.clone()).await;
     
     // Fetch exchange metadata for symbols that don't have it yet
     fetch_exchange_metadata(state.clone()).await;
     
-    update_all_previous_closes(state.clone()).await;
+    let _ = refresher.state.set(state.clone());
+    if let Err(e) = refresher.run().await {
+        warn!("[ TwelveData ] Startup previous close refresh skipped: {e:?}");
+    }
 
-    // Spawn background task to verify/refresh exchange metadata every 24 hours
+    // Spawn background task to verify/refresh exchange metadata every 24
+    // hours and prune old dead letters
     let bg_state = state.clone();
     tokio::spawn(async move {
         loop {
             sleep(Duration::from_secs(86400)).await; // 24 hours
             info!("[ TwelveData ] Running background exchange metadata verification...");
             verify_exchange_metadata(bg_state.clone()).await;
+            match prune_dead_letters(bg_state.pool.clone(), DEAD_LETTER_RETENTION_DAYS).await {
+                Ok(0) => {}
+                Ok(count) => info!("Pruned {} old dead letters", count),
+                Err(e) => warn!("Failed to prune dead letters: {e:?}"),
+            }
         }
     });
 
     // Spawn background task to refresh previous closes daily, shortly after
-    // the US market closes. We target 21:30 UTC (~16:30 ET / 17:30 EDT), which
-    // is comfortably after the 4:00pm ET close and after TwelveData's official
+    // the US market closes. The default 21:30 UTC (~16:30 ET / 17:30 EDT) is
+    // comfortably after the 4:00pm ET close and after TwelveData's official
     // previous_close values have settled. Without this, previous_close only
     // refreshes when the pod restarts, causing the stale numbers users see.
-    let prev_close_state = state.clone();
-    tokio::spawn(async move {
-        loop {
-            sleep(duration_until_next_utc(21, 30)).await;
-            info!("[ TwelveData ] Running daily previous close refresh...");
-            update_all_previous_closes(prev_close_state.clone()).await;
-        }
-    });
+    if let Some((hour, minute)) = close_refresh_time() {
+        let refresher = refresher.clone();
+        tokio::spawn(async move {
+            loop {
+                sleep(duration_until_next_utc(hour, minute)).await;
+                info!("[ TwelveData ] Running daily previous close refresh...");
+                if let Err(e) = refresher.run().await {
+                    warn!("[ TwelveData ] Daily previous close refresh skipped: {e:?}");
+                }
+            }
+        });
+    } else {
+        info!("[ TwelveData ] Daily previous close refresh disabled (FINANCE_CLOSE_REFRESH_UTC=off)");
+    }
 
     loop {
         match connect(state.subscriptions.clone(), state.api_key.clone(), state.client.clone(), pool.clone(), health_state.clone()).await {
             Ok(()) => {
                 error!("WebSocket disconnected, attempting reconnect in 5 minutes...");
             }
+            Err(e @ FinanceError::Auth(_)) => {
+                // A rejected key fails the same way on every attempt. Stop
+                // here and leave the status on the health payload; readiness
+                // goes stale and the pod drops out of rotation.
+                health_state.lock().await.mark_auth_failed(&e);
+                std::future::pending::<()>().await;
+            }
             Err(e) => {
                 // Surface connect() failures in the health payload so the
                 // /health/ready response actually reflects the pod's state.
@@ -82,31 +687,74 @@ pub async fn start_finance_services(pool: Arc<PgPool>, health_state: Arc<Mutex<F
                 {
                     let mut h = health_state.lock().await;
                     h.error_count += 1;
-                    h.last_error = Some(format!("{e:#}"));
+                    h.record_error_kind(&e);
+                    h.set_last_error(format!("{e:#}"));
                 }
                 error!("WebSocket connect failed: {e:#}, retrying in 5 minutes...");
             }
         }
-        sleep(Duration::from_secs(300)).await;
+        sleep(WS_RECONNECT_DELAY).await;
     }
 }
 
 async fn initialize_symbols(state: FinanceState) {
-    info!("Ensuring symbols exist in trades table...");
-    for symbol in state.subscriptions {
-        let _ = insert_symbol(state.pool.clone(), symbol).await;
-    }
-    info!("[ TwelveData ] Symbol initialization complete")
+    let chunk_size = symbol_init_chunk_size();
+    info!("Ensuring {} symbols exist in trades table, {} per insert...", state.subscriptions.len(), chunk_size);
+    let added = insert_symbols(&state.pool, &state.subscriptions, chunk_size).await;
+    info!("[ TwelveData ] Symbol initialization complete ({} new)", added)
 }
 
-pub async fn update_all_previous_closes(state: FinanceState) {
+/// Refresh every subscribed symbol's previous close. Returns the symbols
+/// that came back as 0, for the caller to retry.
+pub async fn update_all_previous_closes(state: FinanceState) -> Vec<String> {
     info!("Updating previous closes for {} symbols...", state.subscriptions.len());
+    let deferred = refresh_previous_closes(&state, &state.subscriptions).await;
+    info!("[ TwelveData ] Previous closes update complete.");
+
+    if !deferred.is_empty() {
+        warn!(
+            "[ TwelveData ] Zero previous close for {} symbols ({}); keeping the stored close and retrying in {} min",
+            deferred.len(), deferred.join(", "), CLOSE_RETRY_DELAY.as_secs() / 60
+        );
+    }
+    deferred
+}
 
-    // TwelveData Pro tier: 610 API credits/min, 500 WS symbols.
-    // Batch 8 at a time with 1s delay to stay within limits.
-    let batch_size = 8;
-    for batch in state.subscriptions.chunks(batch_size) {
-        time::sleep(Duration::from_millis(1_000)).await;
+/// Re-quote `symbols` every [`CLOSE_RETRY_DELAY`] until each has a positive
+/// previous close or [`CLOSE_RETRY_ATTEMPTS`] run out.
+async fn retry_deferred_closes(state: FinanceState, mut symbols: Vec<String>) {
+    for attempt in 1..=CLOSE_RETRY_ATTEMPTS {
+        sleep(CLOSE_RETRY_DELAY).await;
+        symbols = refresh_previous_closes(&state, &symbols).await;
+        if symbols.is_empty() {
+            info!("[ TwelveData ] Deferred previous closes filled in on retry {}", attempt);
+            return;
+        }
+        info!(
+            "[ TwelveData ] Previous close still 0 for {} symbols after retry {}/{}",
+            symbols.len(), attempt, CLOSE_RETRY_ATTEMPTS
+        );
+    }
+    warn!(
+        "[ TwelveData ] Giving up on previous close for {} until the next refresh",
+        symbols.join(", ")
+    );
+}
+
+/// Quote `symbols` and store each previous close and price. Returns the
+/// symbols whose quote had no positive previous close; their stored close
+/// is left as it was.
+async fn refresh_previous_closes(state: &FinanceState, symbols: &[String]) -> Vec<String> {
+    // On a split's effective date TwelveData's previous close is still the
+    // pre-split price; scale it so today's change isn't a bogus -50%.
+    let splits = get_split_ratios_on(state.pool.clone(), Utc::now().date_naive()).await;
+    let splits = &splits;
+
+    // One API credit per quote; see `quote_pacing` for the rate-limit math.
+    let pacing = quote_pacing();
+    let mut deferred = Vec::new();
+    for batch in symbols.chunks(pacing.chunk_size) {
+        time::sleep(pacing.delay).await;
         let futures: Vec<_> = batch.iter().map(|symbol| {
             let client = state.client.clone();
             let api_key = state.api_key.clone();
@@ -115,16 +763,24 @@ pub async fn update_all_previous_closes(state: FinanceState) {
                 let quote_response = get_quote(symbol.to_string(), client, &api_key).await;
                 match quote_response {
                     Ok(quote) => {
-                        let pc = quote.previous_close_f64();
-                        if pc > 0.0 {
+                        let split = splits.get(symbol).copied();
+                        let pc = split_adjusted_close(quote.previous_close_f64(), split);
+                        let zeroed = pc <= 0.0;
+                        if !zeroed {
+                            if split.is_some() {
+                                info!("[ TwelveData ] Split-adjusted previous close for {}: {}", symbol, pc);
+                            }
                             let _ = update_previous_close(pool.clone(), symbol.to_string(), pc).await;
                         }
 
                         let close = quote.close_f64();
                         if close > 0.0 {
-                            let change = quote.change_f64();
-                            let pct = quote.percent_change_f64();
-                            let direction = if change >= 0.0 { "up" } else { "down" };
+                            let (change, pct, direction) = if split.is_some() && pc > 0.0 {
+                                price_change(close, pc)
+                            } else {
+                                let change = quote.change_f64();
+                                (change, quote.percent_change_f64(), if change >= 0.0 { "up" } else { "down" })
+                            };
                             let _ = update_trade(
                                 pool.clone(),
                                 symbol.to_string(),
@@ -132,18 +788,125 @@ pub async fn update_all_previous_closes(state: FinanceState) {
                                 change,
                                 pct,
                                 direction,
+                                None,
                             ).await;
                         } else {
                             warn!("[ TwelveData ] Skipping price update for {}: close is 0", symbol);
                         }
+                        zeroed.then(|| symbol.clone())
+                    }
+                    Err(e) => {
+                        warn!("[ TwelveData ] Quote Error for {}: {e}", symbol);
+                        None
                     }
-                    Err(e) => warn!("[ TwelveData ] Quote Error for {}: {e}", symbol),
                 }
             }
         }).collect();
-        join_all(futures).await;
+        deferred.extend(join_all(futures).await.into_iter().flatten());
+    }
+    deferred
+}
+
+/// Change, percentage change and direction of `price` against
+/// `previous_close`, as stored on a `trades` row.
+pub(crate) fn price_change(price: f64, previous_close: f64) -> (f64, f64, &'static str) {
+    let change = price - previous_close;
+    let percentage = if previous_close == 0.0 {
+        0.0
+    } else {
+        (change / previous_close) * 100.0
+    };
+    (change, percentage, if change >= 0.0 { "up" } else { "down" })
+}
+
+/// `previous_close` in post-split terms. `ratio` is new shares per old share
+/// (2.0 for a 2:1 split, 0.1 for a 1:10 reverse split); no ratio, a 1:1
+/// ratio or a non-positive one leaves the price as it is.
+pub(crate) fn split_adjusted_close(previous_close: f64, ratio: Option<f64>) -> f64 {
+    match ratio {
+        Some(r) if r.is_finite() && r > 0.0 && r != 1.0 => previous_close / r,
+        _ => previous_close,
+    }
+}
+
+/// Refetch `symbol`'s quote and rebuild its `trades` row against the fresh
+/// previous close, for rows left with stale change figures after a
+/// corrected close (e.g. a split). `None` when the symbol has no row.
+pub async fn recompute_symbol(state: &FinanceState, symbol: &str) -> Result<Option<DatabaseTradeData>, FinanceError> {
+    let quote = get_quote(symbol.to_string(), state.client.clone(), &state.api_key).await?;
+    let baseline = quote
+        .baseline_close()
+        .ok_or_else(|| FinanceError::Api(format!("no close for {symbol}")))?;
+    let splits = get_split_ratios_on(state.pool.clone(), Utc::now().date_naive()).await;
+    let previous_close = split_adjusted_close(baseline, splits.get(symbol).copied());
+    let row = recompute_trade(state.pool.clone(), symbol, previous_close).await?;
+    if row.is_some() {
+        info!("Recomputed {} against previous close {}", symbol, previous_close);
+    }
+    Ok(row)
+}
+
+/// Overwrite `symbol`'s previous close with an operator-supplied `value`
+/// and rebuild its change figures against the stored price, for a bad quote
+/// that would otherwise stand until the next trading day. The next daily
+/// refresh replaces it as usual. `None` when the symbol has no row.
+pub async fn force_previous_close(pool: Arc<PgPool>, symbol: &str, value: f64) -> Result<Option<DatabaseTradeData>, FinanceError> {
+    let row = recompute_trade(pool, symbol, value).await?;
+    if row.is_some() {
+        info!("Previous close for {} forced to {}", symbol, value);
+    }
+    Ok(row)
+}
+
+/// Enable or disable `symbol` in `tracked_symbols`. The live subscription
+/// follows within one symbol refresh interval; the `trades` row is kept
+/// either way. A re-enabled symbol may have missed daily close refreshes
+/// while paused, so its quote is recomputed in the background once `finance`
+/// is initialized; before that, the startup close refresh covers it.
+/// Returns `false` when the symbol isn't tracked.
+pub async fn set_symbol_tracking(pool: Arc<PgPool>, finance: Option<&FinanceState>, symbol: &str, enabled: bool) -> Result<bool, FinanceError> {
+    if !set_symbol_enabled(pool.clone(), symbol, enabled).await? {
+        return Ok(false);
+    }
+    info!("{} {}", if enabled { "Enabled" } else { "Disabled" }, symbol);
+    if enabled && let Some(finance) = finance {
+        let finance = finance.clone();
+        let symbol = symbol.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = recompute_symbol(&finance, &symbol).await {
+                warn!("Failed to refresh previous close for re-enabled {}: {}", symbol, e);
+            }
+        });
+    }
+    Ok(true)
+}
+
+/// Render `trades` rows as CSV with a header row. `last_updated` is written
+/// as RFC 3339 (ISO-8601) in UTC.
+pub fn trades_csv(rows: &[DatabaseTradeData]) -> String {
+    let mut out = String::from("symbol,price,previous_close,price_change,percentage_change,direction,last_updated\r\n");
+    for row in rows {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\r\n",
+            csv_field(&row.symbol),
+            row.price,
+            row.previous_close,
+            row.price_change,
+            row.percentage_change,
+            csv_field(&row.direction),
+            row.last_updated.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        ));
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
     }
-    info!("[ TwelveData ] Previous closes update complete.");
 }
 
 /// Returns the `Duration` until the next occurrence of `hour:minute` UTC.
@@ -161,23 +924,67 @@ fn duration_until_next_utc(hour: u32, minute: u32) -> Duration {
     Duration::from_secs(delta as u64)
 }
 
-pub(crate) async fn get_quote(symbol: String, client: Arc<Client>, api_key: &str) -> anyhow::Result<QuoteResponse> {
-    let rest_base = std::env::var("TWELVEDATA_REST_URL")
-        .unwrap_or_else(|_| "https://api.twelvedata.com".to_string());
+/// Duration of recent TwelveData quote requests. Quotes are fetched by the
+/// trade batcher, the previous-close refresh and the admin endpoints, none
+/// of which hold the health state, so the window is process-wide and
+/// [`FinanceHealth::get_health`] copies it into the payload.
+pub(crate) static QUOTE_LATENCY: std::sync::Mutex<FetchLatency> = std::sync::Mutex::new(FetchLatency::new());
+
+/// TwelveData REST base URL: `TWELVEDATA_REST_URL`, else [`DEFAULT_REST_URL`].
+pub(crate) fn rest_base_url() -> String {
+    std::env::var("TWELVEDATA_REST_URL").unwrap_or_else(|_| DEFAULT_REST_URL.to_string())
+}
+
+pub(crate) async fn get_quote(symbol: String, client: Arc<Client>, api_key: &str) -> Result<QuoteResponse, FinanceError> {
+    get_quote_from(&rest_base_url(), &symbol, &client, api_key).await
+}
+
+pub(crate) async fn get_quote_from(rest_base: &str, symbol: &str, client: &Client, api_key: &str) -> Result<QuoteResponse, FinanceError> {
     let url = format!(
         "{}/quote?symbol={}&apikey={}",
         rest_base, symbol, api_key
     );
-    let response = client.get(&url).send().await?.text().await?;
-    let data: QuoteResponse = serde_json::from_str(&response)?;
+    let started = std::time::Instant::now();
+    let fetched = async {
+        let response = client.get(&url).send().await?;
+        let status = response.status();
+        response.text().await.map(|body| (status, body))
+    }
+    .await;
+    if let Ok(mut latency) = QUOTE_LATENCY.lock() {
+        latency.record(started.elapsed());
+    }
+    let (status, body) = fetched?;
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        return Err(auth_failure(format!("REST returned {status}")));
+    }
+    let data: QuoteResponse = serde_json::from_str(&body)?;
     if data.is_error() {
         let msg = data.message.as_deref().unwrap_or("unknown error");
         let code = data.code.unwrap_or(0);
-        anyhow::bail!("TwelveData API error {code}: {msg}");
+        // TwelveData usually reports a bad key as HTTP 200 with the real
+        // status in the body.
+        if matches!(code, 401 | 403) {
+            return Err(auth_failure(format!("REST error {code}: {msg}")));
+        }
+        return Err(FinanceError::Api(format!("{code}: {msg}")));
     }
     Ok(data)
 }
 
+/// Build the error for a rejected API key. Every quote call after the first
+/// would hit the same wall, so the guidance is logged once per process.
+pub(crate) fn auth_failure(detail: String) -> FinanceError {
+    static LOGGED: AtomicBool = AtomicBool::new(false);
+    if !LOGGED.swap(true, Ordering::Relaxed) {
+        error!(
+            "TwelveData authentication failed ({}). Check TWELVEDATA_API_KEY; retrying won't help until the key is fixed.",
+            detail
+        );
+    }
+    FinanceError::Auth(detail)
+}
+
 // =============================================================================
 // Exchange Metadata
 // =============================================================================
@@ -207,8 +1014,7 @@ async fn fetch_stock_exchange(
     client: Arc<Client>,
     api_key: &str,
 ) -> Option<String> {
-    let rest_base = std::env::var("TWELVEDATA_REST_URL")
-        .unwrap_or_else(|_| "https://api.twelvedata.com".to_string());
+    let rest_base = rest_base_url();
     
     // Must include country=United States to get US exchanges (otherwise returns first alphabetical)
     let url = format!(
@@ -335,3 +1141,226 @@ async fn verify_exchange_metadata(state: FinanceState) {
     
     info!("[ TwelveData ] Exchange metadata verification complete.");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pool_size() {
+        let defaults = PoolSize {
+            max_connections: DEFAULT_DB_MAX_CONNECTIONS,
+            min_connections: DEFAULT_DB_MIN_CONNECTIONS,
+        };
+        assert_eq!(parse_pool_size(None, None), defaults);
+        assert_eq!(parse_pool_size(Some("30"), Some(" 3 ")), PoolSize { max_connections: 30, min_connections: 3 });
+        assert_eq!(parse_pool_size(Some("-1"), Some("")), defaults);
+        assert_eq!(parse_pool_size(Some("2"), Some("5")), PoolSize { max_connections: 2, min_connections: 2 });
+    }
+
+    #[test]
+    fn test_price_change() {
+        assert_eq!(price_change(110.0, 100.0), (10.0, 10.0, "up"));
+        assert_eq!(price_change(90.0, 100.0), (-10.0, -10.0, "down"));
+        assert_eq!(price_change(100.0, 100.0), (0.0, 0.0, "up"));
+        assert_eq!(price_change(5.0, 0.0), (5.0, 0.0, "up"));
+    }
+
+    #[test]
+    fn test_two_for_one_split_halves_previous_close() {
+        assert_eq!(split_adjusted_close(300.0, Some(2.0)), 150.0);
+        // The day's change is then measured against the adjusted close.
+        assert_eq!(price_change(165.0, split_adjusted_close(300.0, Some(2.0))), (15.0, 10.0, "up"));
+    }
+
+    #[test]
+    fn test_split_adjustment_guards() {
+        assert_eq!(split_adjusted_close(300.0, None), 300.0);
+        assert_eq!(split_adjusted_close(300.0, Some(1.0)), 300.0);
+        assert_eq!(split_adjusted_close(300.0, Some(0.0)), 300.0);
+        assert_eq!(split_adjusted_close(300.0, Some(f64::NAN)), 300.0);
+        assert_eq!(split_adjusted_close(5.0, Some(0.1)), 50.0);
+    }
+
+    #[test]
+    fn test_parse_close_refresh_time() {
+        assert_eq!(parse_close_refresh_time(None), Some(PREVIOUS_CLOSE_REFRESH_UTC));
+        assert_eq!(parse_close_refresh_time(Some("20:05")), Some((20, 5)));
+        assert_eq!(parse_close_refresh_time(Some("OFF")), None);
+        assert_eq!(parse_close_refresh_time(Some("24:00")), Some(PREVIOUS_CLOSE_REFRESH_UTC));
+        assert_eq!(parse_close_refresh_time(Some("9pm")), Some(PREVIOUS_CLOSE_REFRESH_UTC));
+    }
+
+    #[test]
+    fn test_parse_ws_idle_timeout_secs() {
+        assert_eq!(parse_ws_idle_timeout_secs(None), Some(DEFAULT_WS_IDLE_TIMEOUT_SECS));
+        assert_eq!(parse_ws_idle_timeout_secs(Some("300")), Some(300));
+        assert_eq!(parse_ws_idle_timeout_secs(Some("0")), None);
+        assert_eq!(parse_ws_idle_timeout_secs(Some("Off")), None);
+        assert_eq!(parse_ws_idle_timeout_secs(Some("30")), Some(DEFAULT_WS_IDLE_TIMEOUT_SECS));
+        assert_eq!(parse_ws_idle_timeout_secs(Some("2m")), Some(DEFAULT_WS_IDLE_TIMEOUT_SECS));
+    }
+
+    #[test]
+    fn test_parse_quote_pacing() {
+        let defaults = QuotePacing {
+            chunk_size: DEFAULT_QUOTE_CHUNK_SIZE,
+            delay: Duration::from_millis(DEFAULT_QUOTE_CHUNK_DELAY_MS),
+        };
+        assert_eq!(parse_quote_pacing(None, None), defaults);
+        assert_eq!(parse_quote_pacing(Some(" "), Some("")), defaults);
+        assert_eq!(
+            parse_quote_pacing(Some("3"), Some("1500")),
+            QuotePacing { chunk_size: 3, delay: Duration::from_millis(1500) }
+        );
+        assert_eq!(parse_quote_pacing(Some("0"), Some("-5")), defaults);
+        assert_eq!(parse_quote_pacing(Some("many"), Some("0")), defaults);
+    }
+
+    #[test]
+    fn test_parse_subscribe_pacing() {
+        let defaults = SubscribePacing {
+            chunk_size: DEFAULT_WS_SUBSCRIBE_CHUNK_SIZE,
+            delay: Duration::from_millis(DEFAULT_WS_SUBSCRIBE_CHUNK_DELAY_MS),
+        };
+        assert_eq!(parse_subscribe_pacing(None, None), defaults);
+        assert_eq!(
+            parse_subscribe_pacing(Some("20"), Some("500")),
+            SubscribePacing { chunk_size: 20, delay: Duration::from_millis(500) }
+        );
+        assert_eq!(parse_subscribe_pacing(Some("0"), Some("soon")), defaults);
+    }
+
+    #[test]
+    fn test_parse_symbol_init_chunk_size() {
+        assert_eq!(parse_symbol_init_chunk_size(None), DEFAULT_SYMBOL_INIT_CHUNK_SIZE);
+        assert_eq!(parse_symbol_init_chunk_size(Some("25")), 25);
+        assert_eq!(parse_symbol_init_chunk_size(Some("0")), DEFAULT_SYMBOL_INIT_CHUNK_SIZE);
+        assert_eq!(parse_symbol_init_chunk_size(Some("lots")), DEFAULT_SYMBOL_INIT_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn test_trade_session() {
+        use chrono::TimeZone;
+        let at = |m, d, h, min| Utc.with_ymd_and_hms(2026, m, d, h, min, 0).unwrap();
+        // Wednesday 2026-10-14, EDT (UTC-4).
+        assert_eq!(trade_session(at(10, 14, 7, 59)), TradeSession::Closed);
+        assert_eq!(trade_session(at(10, 14, 8, 0)), TradeSession::Pre);
+        assert_eq!(trade_session(at(10, 14, 13, 29)), TradeSession::Pre);
+        assert_eq!(trade_session(at(10, 14, 13, 30)), TradeSession::Regular);
+        assert_eq!(trade_session(at(10, 14, 19, 59)), TradeSession::Regular);
+        assert_eq!(trade_session(at(10, 14, 20, 0)), TradeSession::Post);
+        assert_eq!(trade_session(at(10, 14, 23, 59)), TradeSession::Post);
+        assert_eq!(trade_session(at(10, 15, 0, 0)), TradeSession::Closed);
+        // Wednesday 2026-12-02, EST (UTC-5): the open moves to 14:30 UTC.
+        assert_eq!(trade_session(at(12, 2, 14, 29)), TradeSession::Pre);
+        assert_eq!(trade_session(at(12, 2, 14, 30)), TradeSession::Regular);
+        assert_eq!(trade_session(at(12, 2, 21, 0)), TradeSession::Post);
+        // Late Friday evening in New York is already Saturday in UTC: 19:30
+        // EST is still post-market, 20:30 EDT is past it.
+        assert_eq!(trade_session(at(12, 5, 0, 30)), TradeSession::Post);
+        assert_eq!(trade_session(at(10, 17, 0, 30)), TradeSession::Closed);
+        assert_eq!(trade_session(at(10, 17, 15, 0)), TradeSession::Closed);
+        // DST switches: 2026-03-08 and 2026-11-01 are Sundays; the Mondays
+        // after open at 13:30 and 14:30 UTC respectively.
+        assert_eq!(trade_session(at(3, 9, 13, 30)), TradeSession::Regular);
+        assert_eq!(trade_session(at(3, 6, 13, 30)), TradeSession::Pre);
+        assert_eq!(trade_session(at(11, 2, 13, 30)), TradeSession::Pre);
+        assert_eq!(trade_session(at(11, 2, 14, 30)), TradeSession::Regular);
+
+        assert_eq!(session_for("AAPL", at(10, 14, 15, 0)), Some(TradeSession::Regular));
+        assert_eq!(session_for("BTC/USD", at(10, 14, 15, 0)), None);
+    }
+
+    #[test]
+    fn test_parse_batch_concurrency_clamps_to_pool() {
+        assert_eq!(parse_batch_concurrency(None, 20), DEFAULT_BATCH_CONCURRENCY);
+        assert_eq!(parse_batch_concurrency(Some("12"), 20), 12);
+        assert_eq!(parse_batch_concurrency(Some("0"), 20), DEFAULT_BATCH_CONCURRENCY);
+        assert_eq!(parse_batch_concurrency(Some("lots"), 20), DEFAULT_BATCH_CONCURRENCY);
+        // Never more than the pool minus its headroom...
+        assert_eq!(parse_batch_concurrency(Some("50"), 20), 20 - BATCH_POOL_HEADROOM);
+        assert_eq!(parse_batch_concurrency(None, 6), 6 - BATCH_POOL_HEADROOM);
+        // ...and never zero, even on a tiny pool.
+        assert_eq!(parse_batch_concurrency(Some("8"), 2), 1);
+    }
+
+    #[test]
+    fn test_parse_symbol_list() {
+        let (entries, skipped) = parse_symbol_list("aapl:Technology, BTC/USD:Crypto,SPY,");
+        assert_eq!(skipped, 0);
+        let symbols: Vec<&str> = entries.iter().map(|e| e.symbol.as_str()).collect();
+        assert_eq!(symbols, ["AAPL", "BTC/USD", "SPY"]);
+        assert_eq!(entries[0].name, "AAPL");
+        assert_eq!(entries[1].category, "Crypto");
+        assert_eq!(entries[2].category, DEFAULT_SYMBOL_CATEGORY);
+
+        let (entries, skipped) = parse_symbol_list("MSFT,:Tech,A B,MSFT,X:a:b,THISSYMBOLISFARTOOLONG,NVDA:");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(skipped, 6);
+    }
+
+    #[test]
+    fn test_redact_url_drops_apikey() {
+        let redacted = redact_url("wss://ws.twelvedata.com/v1/quotes/price?apikey=sk_live_123");
+        assert_eq!(redacted, "wss://ws.twelvedata.com/v1/quotes/price?redacted");
+        assert!(!redacted.contains("sk_live_123"));
+        assert_eq!(redact_url("https://u:p@api.twelvedata.com"), "https://api.twelvedata.com/");
+        assert_eq!(redact_url("not a url"), "<unparseable>");
+    }
+
+    #[test]
+    fn test_trades_csv() {
+        use chrono::TimeZone;
+        let row = |symbol: &str| DatabaseTradeData {
+            symbol: symbol.to_string(),
+            price: 165.5,
+            previous_close: 150.0,
+            price_change: 15.5,
+            percentage_change: 10.0,
+            direction: "up".to_string(),
+            last_updated: Utc.with_ymd_and_hms(2026, 3, 2, 14, 30, 0).unwrap(),
+            session: Some("regular".to_string()),
+        };
+        let csv = trades_csv(&[row("AAPL"), row("ODD,\"SYM\"")]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "symbol,price,previous_close,price_change,percentage_change,direction,last_updated");
+        assert_eq!(lines[1], "AAPL,165.5,150,15.5,10,up,2026-03-02T14:30:00Z");
+        assert_eq!(lines[2], "\"ODD,\"\"SYM\"\"\",165.5,150,15.5,10,up,2026-03-02T14:30:00Z");
+        assert_eq!(lines[3], "");
+    }
+
+    /// Serve one canned HTTP response on a loopback port and return its base URL.
+    fn serve_once(response: &'static str) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut socket, _)) = listener.accept() {
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf);
+                let _ = socket.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_rest_401_is_auth_error() {
+        let base = serve_once(
+            "HTTP/1.1 401 Unauthorized\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+        );
+        let err = get_quote_from(&base, "AAPL", &Client::new(), "bad-key").await.unwrap_err();
+        assert!(matches!(err, FinanceError::Auth(_)), "got {err:?}");
+        assert_eq!(err.kind(), "auth");
+    }
+
+    #[tokio::test]
+    async fn test_rest_401_in_body_is_auth_error() {
+        let base = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 57\r\nConnection: close\r\n\r\n{\"code\":401,\"message\":\"invalid api key\",\"status\":\"error\"}",
+        );
+        let err = get_quote_from(&base, "AAPL", &Client::new(), "bad-key").await.unwrap_err();
+        assert!(matches!(err, FinanceError::Auth(_)), "got {err:?}");
+    }
+}
@@ -1,16 +1,17 @@
 use anyhow::{Context, Result};
-use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use axum::{extract::{Path, Query, State}, http::{header, HeaderMap, HeaderName, StatusCode}, response::IntoResponse, routing::{get, patch, post}, Json, Router};
 use dotenv::dotenv;
-use serde::Serialize;
-use std::{sync::Arc, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::{sync::{Arc, OnceLock}, time::Duration};
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 use finance_service::{
-    database::initialize_pool,
+    auth::require_api_token,
+    database::{get_dead_letters, get_movers, get_trades, get_trades_since, initialize_pool, upsert_split_adjustment, DatabaseTradeData, DeadLetter, PgPool},
     init::{fatal, spawn_supervised, ReadinessGate, ReadinessSnapshot},
     log::init_async_logger,
-    start_finance_services,
-    types::FinanceHealth,
+    force_previous_close, recompute_symbol, reseed_symbols, set_symbol_tracking, start_finance_services, trades_csv, startup_config, CloseRefresher, RefreshRejected,
+    types::{FinanceConfig, FinanceError, FinanceHealth, MoverDirection, ReseedSummary},
 };
 
 /// Freshness window for `/health/ready`. If the WebSocket hasn't processed
@@ -25,10 +26,22 @@ const MAX_POLL_STALENESS: Duration = Duration::from_secs(10 * 60);
 /// mutex-read + one RwLock-write) so it runs on a tight interval.
 const READINESS_BRIDGE_INTERVAL: Duration = Duration::from_secs(10);
 
+/// Rows `/dead-letters` returns without `?limit=`, and the cap with it.
+const DEAD_LETTERS_DEFAULT_LIMIT: i64 = 100;
+const DEAD_LETTERS_MAX_LIMIT: i64 = 1000;
+
+/// Rows `/movers` returns without `?limit=`, and the cap with it.
+const MOVERS_DEFAULT_LIMIT: i64 = 10;
+const MOVERS_MAX_LIMIT: i64 = 100;
+
 #[derive(Clone)]
 struct AppState {
     health: Arc<Mutex<FinanceHealth>>,
     readiness: Arc<ReadinessGate>,
+    config: Arc<Mutex<FinanceConfig>>,
+    /// Set by the init task once the DB connection is established.
+    pool: Arc<OnceLock<Arc<PgPool>>>,
+    refresher: Arc<CloseRefresher>,
 }
 
 #[derive(Serialize)]
@@ -141,14 +154,31 @@ async fn run_service() -> Result<()> {
     // something to talk to, but the readiness probe at /health/ready will
     // return 503 until `readiness.mark_ready()` is called from the init
     // task below.
+    let config = Arc::new(Mutex::new(startup_config()));
+    let pool_cell = Arc::new(OnceLock::new());
+    let refresher = Arc::new(CloseRefresher::default());
     let state = AppState {
         health: health.clone(),
         readiness: readiness.clone(),
+        config: config.clone(),
+        pool: pool_cell.clone(),
+        refresher: refresher.clone(),
     };
     let app = Router::new()
         .route("/health", get(health_ready_handler))
         .route("/health/live", get(health_live_handler))
         .route("/health/ready", get(health_ready_handler))
+        .route("/config", get(config_handler))
+        .route("/dead-letters", get(dead_letters_handler))
+        .route("/reseed", post(reseed_handler))
+        .route("/refresh-closes", post(refresh_closes_handler))
+        .route("/movers", get(movers_handler))
+        .route("/symbols.csv", get(symbols_csv_handler))
+        .route("/symbols/changes", get(symbol_changes_handler))
+        .route("/symbols/{symbol}", patch(symbol_tracking_handler))
+        .route("/symbols/{symbol}/previous-close", post(previous_close_handler))
+        .route("/symbols/{symbol}/recompute", post(recompute_handler))
+        .route("/symbols/{symbol}/split", post(split_handler))
         .with_state(state);
 
     let port = std::env::var("PORT").unwrap_or_else(|_| "3001".to_string());
@@ -191,6 +221,7 @@ async fn run_service() -> Result<()> {
                 }
             }
         };
+        let _ = pool_cell.set(pool.clone());
 
         // DB is up and migrations have succeeded. Readiness can flip to
         // `Ready` — but /health/ready will keep returning 503 until the
@@ -254,7 +285,7 @@ async fn run_service() -> Result<()> {
         // Start the background service (WebSocket). Shutdown is cooperative
         // via `cancel`.
         tokio::select! {
-            _ = start_finance_services(pool, health_bg) => {},
+            _ = start_finance_services(pool, health_bg, config, refresher) => {},
             _ = cancel_bg.cancelled() => {
                 println!("Finance background service shutting down...");
             }
@@ -295,6 +326,294 @@ async fn shutdown_signal() {
     }
 }
 
+/// Effective configuration — which symbols this pod subscribed to and
+/// where it streams them from. Secrets are never part of [`FinanceConfig`].
+async fn config_handler(State(state): State<AppState>) -> Json<FinanceConfig> {
+    Json(state.config.lock().await.clone())
+}
+
+#[derive(Deserialize)]
+struct DeadLettersQuery {
+    limit: Option<i64>,
+}
+
+/// TwelveData frames dropped by the read loop (oversized symbol, missing
+/// fields), most recently seen first. 503 until the DB pool is up.
+async fn dead_letters_handler(
+    State(state): State<AppState>,
+    Query(params): Query<DeadLettersQuery>,
+) -> Result<Json<Vec<DeadLetter>>, (StatusCode, Json<serde_json::Value>)> {
+    let pool = ready_pool(&state)?;
+    let limit = params
+        .limit
+        .unwrap_or(DEAD_LETTERS_DEFAULT_LIMIT)
+        .clamp(1, DEAD_LETTERS_MAX_LIMIT);
+    get_dead_letters(pool.clone(), limit).await.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })
+}
+
+#[derive(Deserialize)]
+struct MoversQuery {
+    direction: Option<String>,
+    limit: Option<i64>,
+}
+
+/// Top gainers (`?direction=up`, the default) or losers (`?direction=down`)
+/// by percentage change since the previous close, as `trades` rows. 503
+/// until the DB pool is up.
+async fn movers_handler(
+    State(state): State<AppState>,
+    Query(params): Query<MoversQuery>,
+) -> Result<Json<Vec<DatabaseTradeData>>, (StatusCode, Json<serde_json::Value>)> {
+    let direction = match params.direction.as_deref() {
+        None => MoverDirection::default(),
+        Some(raw) => MoverDirection::parse(raw).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "direction must be up or down"})),
+            )
+        })?,
+    };
+    let limit = params
+        .limit
+        .unwrap_or(MOVERS_DEFAULT_LIMIT)
+        .clamp(1, MOVERS_MAX_LIMIT);
+    let pool = ready_pool(&state)?;
+    get_movers(pool.clone(), direction, limit).await.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })
+}
+
+/// Every `trades` row as a CSV download, for spreadsheets. 503 until the DB
+/// pool is up.
+async fn symbols_csv_handler(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let pool = ready_pool(&state)?;
+    let rows = get_trades(pool.clone()).await;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"symbols.csv\""),
+        ],
+        trades_csv(&rows),
+    ))
+}
+
+#[derive(Deserialize)]
+struct ChangesQuery {
+    since: Option<String>,
+}
+
+/// `trades` rows updated after `?since=` (RFC 3339), so a ticker can redraw
+/// only the symbols that moved. A missing or unparseable `since` returns
+/// every row. The `X-Server-Time` header is the cursor for the next call;
+/// it is taken before the query, so rows written meanwhile are sent again
+/// rather than missed. 503 until the DB pool is up.
+async fn symbol_changes_handler(
+    State(state): State<AppState>,
+    Query(params): Query<ChangesQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let since = params.since.as_deref().and_then(|raw| {
+        chrono::DateTime::parse_from_rfc3339(raw.trim())
+            .map(|ts| ts.with_timezone(&chrono::Utc))
+            .ok()
+    });
+    let pool = ready_pool(&state)?;
+    let server_time = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+    let rows = get_trades_since(pool.clone(), since).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })?;
+    Ok(([(HeaderName::from_static("x-server-time"), server_time)], Json(rows)))
+}
+
+/// Add any symbol in `subscriptions.json` that isn't tracked yet, without touching
+/// existing rows. Requires the `INGEST_API_TOKEN` bearer token.
+async fn reseed_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ReseedSummary>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    let pool = ready_pool(&state)?;
+    reseed_symbols(pool.clone()).await.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })
+}
+
+/// Kick off a previous-close refresh for every tracked symbol in the
+/// background. 409 while one is already running. Requires the
+/// `INGEST_API_TOKEN` bearer token.
+async fn refresh_closes_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    match state.refresher.spawn() {
+        Ok(()) => Ok((StatusCode::ACCEPTED, Json(serde_json::json!({"status": "started"})))),
+        Err(RefreshRejected::AlreadyRunning) => Err((
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": "a previous close refresh is already running"})),
+        )),
+        Err(RefreshRejected::NotReady) => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "finance service not initialized"})),
+        )),
+    }
+}
+
+/// Refetch a symbol's quote and recompute its stored change figures from
+/// the fresh previous close. Slash symbols are sent URL-encoded
+/// (`BTC%2FUSD`). Requires the `INGEST_API_TOKEN` bearer token.
+async fn recompute_handler(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<DatabaseTradeData>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    let Some(finance) = state.refresher.state() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "finance service not initialized"})),
+        ));
+    };
+    match recompute_symbol(finance, &symbol).await {
+        Ok(Some(row)) => Ok(Json(row)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("no trades row for {symbol}")})),
+        )),
+        Err(e) => {
+            let code = match e {
+                FinanceError::Http(_) | FinanceError::Api(_) | FinanceError::Parse(_) => StatusCode::BAD_GATEWAY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            Err((code, Json(serde_json::json!({"error": e.to_string()}))))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PreviousCloseOverride {
+    value: f64,
+}
+
+/// Set a symbol's previous close by hand when the quote-derived one is
+/// wrong, and recompute its change figures against the current price.
+/// Requires the `INGEST_API_TOKEN` bearer token.
+async fn previous_close_handler(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    headers: HeaderMap,
+    Json(body): Json<PreviousCloseOverride>,
+) -> Result<Json<DatabaseTradeData>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    if !body.value.is_finite() || body.value <= 0.0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "value must be a positive number"})),
+        ));
+    }
+    let pool = ready_pool(&state)?;
+    match force_previous_close(pool.clone(), &symbol, body.value).await {
+        Ok(Some(row)) => Ok(Json(row)),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("no trades row for {symbol}")})),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )),
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct SymbolTracking {
+    is_enabled: bool,
+}
+
+/// Pause or resume a symbol via `tracked_symbols.is_enabled`; its `trades`
+/// row and history are kept. The subscription catches up on the next
+/// symbol refresh. Requires the `INGEST_API_TOKEN` bearer token.
+async fn symbol_tracking_handler(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    headers: HeaderMap,
+    Json(tracking): Json<SymbolTracking>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    let pool = ready_pool(&state)?;
+    match set_symbol_tracking(pool.clone(), state.refresher.state(), &symbol, tracking.is_enabled).await {
+        Ok(true) => Ok(Json(serde_json::json!({"symbol": symbol, "is_enabled": tracking.is_enabled}))),
+        Ok(false) => Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("{symbol} is not tracked")})),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )),
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct SplitAdjustment {
+    /// New shares per old share: 2 for a 2:1 split, 0.1 for a 1:10 reverse split.
+    ratio: f64,
+    effective_date: chrono::NaiveDate,
+}
+
+/// Record a stock split so the daily previous-close refresh on
+/// `effective_date` stores a split-adjusted close. Requires the
+/// `INGEST_API_TOKEN` bearer token.
+async fn split_handler(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    headers: HeaderMap,
+    Json(split): Json<SplitAdjustment>,
+) -> Result<Json<SplitAdjustment>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    if !split.ratio.is_finite() || split.ratio <= 0.0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "ratio must be a positive number"})),
+        ));
+    }
+    let pool = ready_pool(&state)?;
+    upsert_split_adjustment(pool.clone(), &symbol, split.ratio, split.effective_date)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        })?;
+    Ok(Json(split))
+}
+
+/// The DB pool, or a 503 while the init task is still connecting.
+fn ready_pool(state: &AppState) -> Result<&Arc<PgPool>, (StatusCode, Json<serde_json::Value>)> {
+    state.pool.get().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "database not ready"})),
+        )
+    })
+}
+
 /// Liveness probe: returns 200 as long as the process is running. Lets
 /// Kubernetes tell apart "process crashed" (kill+restart) from "process is
 /// up but not doing work" (stop routing traffic, but don't restart — a
@@ -0,0 +1,103 @@
+//! Retry wrapper for writes that can hit transient Postgres failures.
+//!
+//! A deadlock, serialization failure or dropped connection says nothing
+//! about the row being written; waiting briefly and running the statement
+//! again almost always succeeds. Anything else (constraint violations, bad
+//! SQL, decode errors) is returned on the first attempt.
+//!
+//! Copied into each Rust service (`finance`, `sports`, `rss`) alongside
+//! `init.rs`, for the same module-isolation reason.
+
+use std::{future::Future, time::Duration};
+
+use crate::log::warn;
+
+/// Total attempts, including the first.
+pub const DB_RETRY_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubles on each further attempt.
+const DB_RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// Whether `err` is worth retrying: SQLSTATE class 08 (connection
+/// exception), 40001 (serialization_failure), 40P01 (deadlock_detected),
+/// 57P01 (admin_shutdown), or a transport-level I/O error / pool timeout.
+pub fn is_retryable(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(db) => db.code().is_some_and(|code| {
+            code.starts_with("08") || matches!(code.as_ref(), "40001" | "40P01" | "57P01")
+        }),
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        _ => false,
+    }
+}
+
+/// Run `op` up to [`DB_RETRY_ATTEMPTS`] times, backing off between attempts
+/// while it fails with a [retryable](is_retryable) error. `what` names the
+/// operation in the retry log line.
+pub async fn with_db_retry<T, F, Fut>(what: &str, mut op: F) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if attempt < DB_RETRY_ATTEMPTS && is_retryable(&e) => {
+                let delay = DB_RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                warn!("{} failed (attempt {}/{}), retrying in {:?}: {}", what, attempt, DB_RETRY_ATTEMPTS, delay, e);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn connection_reset() -> sqlx::Error {
+        sqlx::Error::Io(std::io::ErrorKind::ConnectionReset.into())
+    }
+
+    #[tokio::test]
+    async fn retries_transient_error_then_succeeds() {
+        let calls = &AtomicU32::new(0);
+        let result = with_db_retry("test", || async move {
+            if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(connection_reset())
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn non_retryable_error_fails_immediately() {
+        let calls = &AtomicU32::new(0);
+        let result: Result<(), _> = with_db_retry("test", || async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(sqlx::Error::RowNotFound)
+        })
+        .await;
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let calls = &AtomicU32::new(0);
+        let result: Result<(), _> = with_db_retry("test", || async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(connection_reset())
+        })
+        .await;
+        assert!(matches!(result, Err(sqlx::Error::Io(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), DB_RETRY_ATTEMPTS);
+    }
+}
@@ -0,0 +1,54 @@
+//! String truncation that never splits a UTF-8 sequence. Slicing a `&str`
+//! at an arbitrary byte offset panics when the offset lands inside a
+//! multi-byte character, and feed titles, team names and upstream error
+//! bodies all routinely carry them.
+//!
+//! Copied into each Rust service (`finance`, `sports`, `rss`) alongside
+//! `init.rs`, for the same module-isolation reason.
+
+/// The first `max` characters of `s`, or all of `s` when it is shorter.
+/// Matches how Postgres counts `VARCHAR(n)`.
+pub fn truncate_chars(s: &str, max: usize) -> &str {
+    match s.char_indices().nth(max) {
+        Some((end, _)) => &s[..end],
+        None => s,
+    }
+}
+
+/// The longest prefix of `s` that is at most `max` bytes and ends on a char
+/// boundary. For byte-budgeted storage such as dead-letter payloads.
+pub fn truncate_bytes_safe(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_chars_counts_characters() {
+        assert_eq!(truncate_chars("short", 10), "short");
+        assert_eq!(truncate_chars("exact", 5), "exact");
+        assert_eq!(truncate_chars("Saint-Étienne", 7), "Saint-É");
+        assert_eq!(truncate_chars("🏈🏀⚾", 2), "🏈🏀");
+        assert_eq!(truncate_chars("anything", 0), "");
+    }
+
+    #[test]
+    fn truncate_bytes_safe_backs_off_to_char_boundary() {
+        assert_eq!(truncate_bytes_safe("short", 10), "short");
+        // 'é' is two bytes; a 2-byte cut would land inside it.
+        assert_eq!(truncate_bytes_safe("aé", 2), "a");
+        assert_eq!(truncate_bytes_safe("aé", 3), "aé");
+        // A four-byte emoji is dropped whole rather than split.
+        assert_eq!(truncate_bytes_safe("ab🏈", 5), "ab");
+        assert_eq!(truncate_bytes_safe("🏈", 3), "");
+    }
+}
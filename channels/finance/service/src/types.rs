@@ -1,10 +1,12 @@
-use std::{collections::HashMap, sync::Arc, time::{Duration, Instant}, pin::Pin};
+use std::{collections::{HashMap, VecDeque}, sync::Arc, time::{Duration, Instant}, pin::Pin};
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tokio::time::Sleep;
-use crate::database::PgPool;
+use chrono::DateTime;
+use crate::database::{PgPool, Utc};
 use crate::init::fatal_env;
+use crate::latency::FetchLatency;
 
 /// A symbol entry from configs/subscriptions.json (categorized format).
 #[derive(Debug, Deserialize, Clone)]
@@ -37,6 +39,11 @@ pub(crate) struct TwelveDataStock {
 /// ```json
 /// {"event":"price","symbol":"AAPL","price":150.75,"timestamp":1678886400,"day_volume":5000000}
 /// ```
+///
+/// Subscribe-status events reuse the same shape with `success`/`fails` lists:
+/// ```json
+/// {"event":"subscribe-status","status":"error","success":[{"symbol":"AAPL"}],"fails":[{"symbol":"MSFT"}],"message":"symbol limit reached"}
+/// ```
 #[derive(Debug, Deserialize, Clone)]
 #[allow(dead_code)]
 pub(crate) struct PriceEvent {
@@ -48,6 +55,32 @@ pub(crate) struct PriceEvent {
     /// Present on status/error events
     pub status: Option<String>,
     pub message: Option<String>,
+    /// Present on subscribe-status events
+    #[serde(default)]
+    pub success: Vec<SubscribedSymbol>,
+    #[serde(default)]
+    pub fails: Vec<SubscribedSymbol>,
+}
+
+impl PriceEvent {
+    /// Whether a subscribe-status event was rejected because the plan's
+    /// simultaneous-subscription cap was hit, as opposed to bad symbols.
+    pub fn is_subscription_limit(&self) -> bool {
+        if self.fails.is_empty() {
+            return false;
+        }
+        [self.message.as_deref(), self.status.as_deref()]
+            .into_iter()
+            .flatten()
+            .any(|s| s.to_ascii_lowercase().contains("limit"))
+    }
+}
+
+/// Symbol entry inside a subscribe-status `success` or `fails` list.
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub(crate) struct SubscribedSymbol {
+    pub symbol: String,
 }
 
 /// Simplified trade data extracted from a PriceEvent for the update queue.
@@ -100,6 +133,11 @@ impl QuoteResponse {
     pub fn previous_close_f64(&self) -> f64 {
         self.previous_close.as_deref().and_then(|s| s.parse().ok()).unwrap_or(0.0)
     }
+    /// Baseline for change calculations: the previous close, or the last
+    /// close when TwelveData has none (e.g. a symbol's first session).
+    pub fn baseline_close(&self) -> Option<f64> {
+        [self.previous_close_f64(), self.close_f64()].into_iter().find(|&p| p > 0.0)
+    }
     pub fn change_f64(&self) -> f64 {
         self.change.as_deref().and_then(|s| s.parse().ok()).unwrap_or(0.0)
     }
@@ -115,6 +153,11 @@ pub(crate) struct WebSocketState {
     pub stats: BatchStats,
     pub last_log_time: Option<Instant>,
     pub last_error_message: Option<String>,
+    /// Set by the read loop when TwelveData reports the API key as invalid.
+    pub auth_failed: bool,
+    /// Newest event timestamp accepted per symbol. Seeded from
+    /// `trades.trade_ts` on connect, so the dedup survives restarts.
+    pub last_trade_ts: HashMap<String, u64>,
 }
 
 impl WebSocketState {
@@ -126,6 +169,8 @@ impl WebSocketState {
             stats: BatchStats::default(),
             last_log_time: None,
             last_error_message: None,
+            auth_failed: false,
+            last_trade_ts: HashMap::new(),
         }
     }
 }
@@ -172,6 +217,234 @@ impl FinanceState {
     }
 }
 
+/// Error kinds surfaced by the finance pipeline. Callers match on the
+/// variant to tell a flaky network from bad data or a database outage;
+/// `kind()` gives the same split as a stable label for the health payload.
+#[derive(Debug, thiserror::Error)]
+pub enum FinanceError {
+    /// Transport failure talking to TwelveData REST.
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    /// An error body TwelveData REST returned, or a quote missing the data
+    /// we asked for.
+    #[error("TwelveData API error: {0}")]
+    Api(String),
+    /// A response or frame that did not deserialize into the expected shape.
+    #[error("Parse error: {0}")]
+    Parse(#[from] serde_json::Error),
+    /// Any sqlx failure, including pool acquisition.
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    /// WebSocket connect/send/receive failure.
+    #[error("WebSocket error: {0}")]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+    /// A local config file that could not be read.
+    #[error("Config error: {0}")]
+    Config(String),
+    /// TwelveData rejected the API key (401/403). Retrying won't help.
+    #[error("TwelveData authentication failed: {0}")]
+    Auth(String),
+}
+
+impl FinanceError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FinanceError::Http(_) | FinanceError::Api(_) => "http",
+            FinanceError::Parse(_) => "parse",
+            FinanceError::Database(_) => "database",
+            FinanceError::WebSocket(_) => "websocket",
+            FinanceError::Config(_) => "config",
+            FinanceError::Auth(_) => "auth",
+        }
+    }
+}
+
+/// Effective running configuration, served at `GET /config`. Only resolved,
+/// non-secret values: the TwelveData key travels as an `apikey` query
+/// parameter, so URLs are shown with their query stripped and the key
+/// itself only as `api_key_configured`.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct FinanceConfig {
+    /// Resolved path of `subscriptions.json`.
+    pub config_file: String,
+    pub ws_url: String,
+    pub rest_url: String,
+    pub api_key_configured: bool,
+    pub ws_reconnect_delay_secs: u64,
+    pub db_max_connections: u32,
+    pub db_min_connections: u32,
+    /// `HH:MM` UTC, or `off` when only `POST /refresh-closes` triggers it.
+    pub previous_close_refresh_utc: String,
+    /// Quotes the previous-close refresh requests at once.
+    pub quote_chunk_size: usize,
+    /// Pause before each chunk of refresh quotes.
+    pub quote_chunk_delay_ms: u64,
+    /// Symbols per subscribe frame on the price stream.
+    pub ws_subscribe_chunk_size: usize,
+    /// Pause between subscribe frames.
+    pub ws_subscribe_chunk_delay_ms: u64,
+    /// Set once the symbol list has been loaded from `tracked_symbols`.
+    pub symbols_loaded_at: Option<DateTime<Utc>>,
+    pub symbols: Vec<ConfiguredSymbol>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ConfiguredSymbol {
+    pub symbol: String,
+    /// `"file"` when listed in `subscriptions.json`, `"env"` when listed in
+    /// `FINANCE_SYMBOLS` (used without the file), `"db"` when the symbol
+    /// only exists as an enabled `tracked_symbols` row.
+    pub source: &'static str,
+}
+
+/// Outcome of `POST /reseed`.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ReseedSummary {
+    /// Config entries inserted because they weren't tracked yet.
+    pub added: u64,
+    /// Valid entries that were already tracked and were left as they are.
+    pub existing: u64,
+    /// Entries dropped by validation.
+    pub skipped: usize,
+}
+
+/// Outcome of seeding the config symbols on startup.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SeedSummary {
+    /// Symbols inserted or updated.
+    pub seeded: u64,
+    /// Repeats of a symbol already earlier in the list.
+    pub skipped: u64,
+    pub failed: Vec<SeedFailure>,
+    /// Every symbol already matched its row, so nothing was written.
+    pub unchanged: bool,
+}
+
+/// A config entry the database rejected while seeding.
+#[derive(Serialize, Clone, Debug)]
+pub struct SeedFailure {
+    /// The symbol.
+    pub entry: String,
+    pub reason: String,
+}
+
+/// Which end of the day's `percentage_change` ranking `GET /movers` returns.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MoverDirection {
+    /// Top gainers, largest percentage change first.
+    #[default]
+    Up,
+    /// Top losers, most negative percentage change first.
+    Down,
+}
+
+impl MoverDirection {
+    /// `up` or `down`, ignoring case and surrounding whitespace.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+
+    /// Sort direction for `percentage_change`: `DESC` for gainers, `ASC`
+    /// for losers.
+    pub fn sql_order(self) -> &'static str {
+        match self {
+            Self::Up => "DESC",
+            Self::Down => "ASC",
+        }
+    }
+}
+
+/// Part of the US equity trading day a price event fell in, stored on
+/// `trades.session` so the UI can label extended-hours moves.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSession {
+    /// 04:00–09:30 US Eastern.
+    Pre,
+    /// 09:30–16:00 US Eastern.
+    Regular,
+    /// 16:00–20:00 US Eastern.
+    Post,
+    /// Overnight and weekends.
+    Closed,
+}
+
+impl TradeSession {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pre => "pre",
+            Self::Regular => "regular",
+            Self::Post => "post",
+            Self::Closed => "closed",
+        }
+    }
+}
+
+/// Connection status changes kept in the health payload; the oldest is
+/// dropped first.
+const MAX_STATUS_TRANSITIONS: usize = 20;
+
+/// One change of `connection_status`, e.g. `connected` → `disconnected`.
+/// Batches re-report the same status constantly, so only edges are kept.
+#[derive(Serialize, Clone, Debug)]
+pub struct StatusTransition {
+    pub from: String,
+    pub to: String,
+    pub at: DateTime<Utc>,
+    /// Last error seen when the connection dropped; `None` on (re)connect.
+    pub reason: Option<String>,
+}
+
+/// Errors kept in the health payload; the oldest is dropped first.
+const MAX_RECENT_ERRORS: usize = 20;
+
+/// One distinct value `last_error` took. Batches re-report the current
+/// error every cycle, so a repeat of the newest entry is not added again.
+#[derive(Serialize, Clone, Debug)]
+pub struct RecentError {
+    pub message: String,
+    pub at: DateTime<Utc>,
+}
+
+/// How far back `messages_per_sec` / `trades_per_sec` look.
+pub(crate) const THROUGHPUT_WINDOW_SECS: i64 = 60;
+
+/// Event counts over the last [`THROUGHPUT_WINDOW_SECS`] seconds, kept as
+/// one bucket per second so a busy stream costs at most a minute of
+/// buckets rather than one entry per event.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ThroughputWindow {
+    /// `(unix second, count)`, oldest first.
+    buckets: VecDeque<(i64, u64)>,
+}
+
+impl ThroughputWindow {
+    pub(crate) fn record(&mut self, now_secs: i64, count: u64) {
+        match self.buckets.back_mut() {
+            Some((second, total)) if *second == now_secs => *total += count,
+            _ => self.buckets.push_back((now_secs, count)),
+        }
+        while self.buckets.front().is_some_and(|(second, _)| *second <= now_secs - THROUGHPUT_WINDOW_SECS) {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Average rate over the window ending at `now_secs`.
+    pub(crate) fn per_sec(&self, now_secs: i64) -> f64 {
+        let total: u64 = self.buckets
+            .iter()
+            .filter(|(second, _)| *second > now_secs - THROUGHPUT_WINDOW_SECS)
+            .map(|(_, count)| count)
+            .sum();
+        total as f64 / THROUGHPUT_WINDOW_SECS as f64
+    }
+}
+
 #[derive(Serialize)]
 pub struct FinanceHealth {
     pub status: String,
@@ -179,6 +452,42 @@ pub struct FinanceHealth {
     pub batch_number: u64,
     pub error_count: u64,
     pub last_error: Option<String>,
+    /// Number of successful WebSocket connects after the first one.
+    pub reconnect_count: u64,
+    /// Connections the idle watchdog dropped because TwelveData went
+    /// silent; each is followed by a reconnect counted above.
+    pub watchdog_reconnects: u64,
+    /// When the watchdog last dropped a connection.
+    pub last_watchdog_at: Option<DateTime<Utc>>,
+    /// When the current connection was established; `None` while disconnected.
+    pub connected_since: Option<DateTime<Utc>>,
+    /// Subscription cap observed from TwelveData, once we've hit it.
+    pub subscription_limit: Option<usize>,
+    /// Symbols left out because they fell beyond the subscription cap.
+    pub unsubscribed_symbols: Vec<String>,
+    /// Symbols subscribed on the live connection; empty while disconnected.
+    pub subscribed_symbols: Vec<String>,
+    /// Running error count per [`FinanceError::kind`].
+    pub errors_by_kind: HashMap<&'static str, u64>,
+    /// Most recent `connection_status` changes, oldest first.
+    pub transitions: VecDeque<StatusTransition>,
+    /// Most recent errors, oldest first. `last_error` is the newest of these.
+    pub recent_errors: VecDeque<RecentError>,
+    /// WebSocket frames received per second over the last minute. Filled
+    /// in by [`get_health`](Self::get_health).
+    pub messages_per_sec: f64,
+    /// Trades written per second over the last minute. Filled in by
+    /// [`get_health`](Self::get_health).
+    pub trades_per_sec: f64,
+    /// Duration of recent TwelveData quote requests. Filled in by
+    /// [`get_health`](Self::get_health).
+    pub quote_latency: FetchLatency,
+    #[serde(skip)]
+    has_connected: bool,
+    #[serde(skip)]
+    message_window: ThroughputWindow,
+    #[serde(skip)]
+    trade_window: ThroughputWindow,
 }
 
 impl Default for FinanceHealth {
@@ -195,23 +504,168 @@ impl FinanceHealth {
             batch_number: 0,
             error_count: 0,
             last_error: None,
+            reconnect_count: 0,
+            watchdog_reconnects: 0,
+            last_watchdog_at: None,
+            connected_since: None,
+            subscription_limit: None,
+            unsubscribed_symbols: Vec::new(),
+            subscribed_symbols: Vec::new(),
+            errors_by_kind: HashMap::new(),
+            transitions: VecDeque::new(),
+            recent_errors: VecDeque::new(),
+            messages_per_sec: 0.0,
+            trades_per_sec: 0.0,
+            quote_latency: FetchLatency::new(),
+            has_connected: false,
+            message_window: ThroughputWindow::default(),
+            trade_window: ThroughputWindow::default(),
         }
     }
 
     pub(crate) fn update_health(&mut self, connection_status: String, batch_number: u64, error_count: u64, last_error: Option<String>) {
-        self.connection_status = connection_status;
+        let reason = if connection_status == "connected" { None } else { last_error.clone() };
+        self.set_connection_status(connection_status, reason);
         self.batch_number = batch_number;
         self.error_count = error_count;
-        self.last_error = last_error;
+        match last_error {
+            Some(message) if self.last_error.as_ref() != Some(&message) => self.set_last_error(message),
+            Some(_) => {}
+            None => self.last_error = None,
+        }
+    }
+
+    /// Set `last_error` and add it to `recent_errors`.
+    pub(crate) fn set_last_error(&mut self, message: String) {
+        if self.recent_errors.len() == MAX_RECENT_ERRORS {
+            self.recent_errors.pop_front();
+        }
+        self.recent_errors.push_back(RecentError {
+            message: message.clone(),
+            at: Utc::now(),
+        });
+        self.last_error = Some(message);
+    }
+
+    /// Record a successful connect. Every connect after the first counts
+    /// as a reconnect, so a flapping socket shows up as a climbing counter.
+    pub(crate) fn mark_connected(&mut self) {
+        if self.has_connected {
+            self.reconnect_count += 1;
+        }
+        self.has_connected = true;
+        self.connected_since = Some(Utc::now());
+    }
+
+    /// Park the health payload on a rejected API key. Nothing reconnects
+    /// after this, so the status stays put until the pod is restarted with
+    /// a working key.
+    pub(crate) fn mark_auth_failed(&mut self, error: &FinanceError) {
+        self.status = String::from("TwelveData authentication failed");
+        self.set_connection_status(String::from("auth_failed"), Some(error.to_string()));
+        self.error_count += 1;
+        self.record_error_kind(error);
+        self.set_last_error(error.to_string());
+    }
+
+    /// Change `connection_status`, logging the edge in `transitions` when
+    /// it differs.
+    fn set_connection_status(&mut self, to: String, reason: Option<String>) {
+        if self.connection_status == to {
+            return;
+        }
+        if self.transitions.len() == MAX_STATUS_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(StatusTransition {
+            from: std::mem::replace(&mut self.connection_status, to.clone()),
+            to,
+            at: Utc::now(),
+            reason,
+        });
+    }
+
+    /// Count one frame received from TwelveData, whatever its event.
+    pub(crate) fn record_message(&mut self) {
+        self.message_window.record(Utc::now().timestamp(), 1);
+    }
+
+    /// Count trades written by a finished batch.
+    pub(crate) fn record_trades(&mut self, count: u64) {
+        if count > 0 {
+            self.trade_window.record(Utc::now().timestamp(), count);
+        }
+    }
+
+    /// Record the idle watchdog dropping a silent connection.
+    pub(crate) fn record_watchdog_timeout(&mut self) {
+        self.watchdog_reconnects += 1;
+        self.last_watchdog_at = Some(Utc::now());
+    }
+
+    pub(crate) fn mark_disconnected(&mut self) {
+        self.connected_since = None;
+        self.subscribed_symbols.clear();
+    }
+
+    pub(crate) fn record_error_kind(&mut self, error: &FinanceError) {
+        *self.errors_by_kind.entry(error.kind()).or_insert(0) += 1;
+    }
+
+    /// Record a subscribe-status reply that hit the subscription cap and
+    /// return the cap. A reply only covers the frame it answers — one chunk
+    /// of the subscribe, or just the added symbols after a resubscribe — so
+    /// the cap is what is still subscribed once this reply's fails are
+    /// dropped, not its success count.
+    pub(crate) fn record_subscription_limit(&mut self, failed: Vec<String>) -> usize {
+        self.subscribed_symbols.retain(|s| !failed.contains(s));
+        for symbol in failed {
+            if !self.unsubscribed_symbols.contains(&symbol) {
+                self.unsubscribed_symbols.push(symbol);
+            }
+        }
+        let limit = self.subscribed_symbols.len();
+        self.subscription_limit = Some(limit);
+        limit
+    }
+
+    /// Trim `subscriptions` to the observed subscription cap, recording the
+    /// symbols that were dropped. Subscriptions are ordered most recently
+    /// requested first, so the newest symbols are the ones kept.
+    pub(crate) fn apply_subscription_limit(&mut self, mut subscriptions: Vec<String>) -> Vec<String> {
+        match self.subscription_limit {
+            Some(limit) if subscriptions.len() > limit => {
+                self.unsubscribed_symbols = subscriptions.split_off(limit);
+            }
+            _ => self.unsubscribed_symbols.clear(),
+        }
+        subscriptions
     }
 
     pub fn get_health(&self) -> Self {
+        let now = Utc::now().timestamp();
         Self {
             status: self.status.clone(),
             connection_status: self.connection_status.clone(),
             batch_number: self.batch_number,
             error_count: self.error_count,
             last_error: self.last_error.clone(),
+            reconnect_count: self.reconnect_count,
+            watchdog_reconnects: self.watchdog_reconnects,
+            last_watchdog_at: self.last_watchdog_at,
+            connected_since: self.connected_since,
+            subscription_limit: self.subscription_limit,
+            unsubscribed_symbols: self.unsubscribed_symbols.clone(),
+            subscribed_symbols: self.subscribed_symbols.clone(),
+            errors_by_kind: self.errors_by_kind.clone(),
+            transitions: self.transitions.clone(),
+            recent_errors: self.recent_errors.clone(),
+            messages_per_sec: self.message_window.per_sec(now),
+            trades_per_sec: self.trade_window.per_sec(now),
+            quote_latency: crate::QUOTE_LATENCY.lock().map(|l| l.clone()).unwrap_or_default(),
+            has_connected: self.has_connected,
+            message_window: self.message_window.clone(),
+            trade_window: self.trade_window.clone(),
         }
     }
 }
@@ -220,6 +674,136 @@ impl FinanceHealth {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_mover_direction_parse() {
+        assert_eq!(MoverDirection::parse("up"), Some(MoverDirection::Up));
+        assert_eq!(MoverDirection::parse(" DOWN "), Some(MoverDirection::Down));
+        assert_eq!(MoverDirection::parse("sideways"), None);
+        assert_eq!(MoverDirection::default(), MoverDirection::Up);
+        assert_eq!(MoverDirection::Up.sql_order(), "DESC");
+        assert_eq!(MoverDirection::Down.sql_order(), "ASC");
+    }
+
+    #[test]
+    fn test_subscription_limit_detection() {
+        let ev: PriceEvent = serde_json::from_str(
+            r#"{"event":"subscribe-status","status":"error","success":[{"symbol":"AAPL"}],"fails":[{"symbol":"MSFT"}],"message":"Subscription limit reached"}"#,
+        ).unwrap();
+        assert!(ev.is_subscription_limit());
+        assert_eq!(ev.success.len(), 1);
+
+        let invalid: PriceEvent = serde_json::from_str(
+            r#"{"event":"subscribe-status","status":"ok","success":[],"fails":[{"symbol":"NOPE"}]}"#,
+        ).unwrap();
+        assert!(!invalid.is_subscription_limit());
+    }
+
+    #[test]
+    fn test_apply_subscription_limit() {
+        let mut health = FinanceHealth::new();
+        let subs = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        assert_eq!(health.apply_subscription_limit(subs.clone()).len(), 3);
+
+        health.subscription_limit = Some(2);
+        assert_eq!(health.apply_subscription_limit(subs), vec!["A", "B"]);
+        assert_eq!(health.unsubscribed_symbols, vec!["C"]);
+    }
+
+    #[test]
+    fn test_finance_error_kinds() {
+        let parse: FinanceError = serde_json::from_str::<QuoteResponse>("not json").unwrap_err().into();
+        assert_eq!(parse.kind(), "parse");
+
+        let mut health = FinanceHealth::new();
+        health.record_error_kind(&parse);
+        health.record_error_kind(&FinanceError::Database(sqlx::Error::PoolTimedOut));
+        health.record_error_kind(&FinanceError::Database(sqlx::Error::PoolTimedOut));
+        assert_eq!(health.errors_by_kind.get("parse"), Some(&1));
+        assert_eq!(health.errors_by_kind.get("database"), Some(&2));
+    }
+
+    #[test]
+    fn test_throughput_window() {
+        let mut window = ThroughputWindow::default();
+        assert_eq!(window.per_sec(1_000), 0.0);
+
+        // 120 events in the same second, then 60 over the next minute.
+        window.record(1_000, 120);
+        for second in 1_001..1_061 {
+            window.record(second, 1);
+        }
+        // At 1_060 the window is 1_001..=1_060: the burst has aged out.
+        assert_eq!(window.per_sec(1_060), 1.0);
+        assert!(window.buckets.len() <= THROUGHPUT_WINDOW_SECS as usize);
+
+        // A quiet stream decays to zero without any new records.
+        assert_eq!(window.per_sec(1_060 + THROUGHPUT_WINDOW_SECS), 0.0);
+    }
+
+    #[test]
+    fn test_health_reports_throughput() {
+        let mut health = FinanceHealth::new();
+        for _ in 0..30 {
+            health.record_message();
+        }
+        health.record_trades(6);
+        health.record_trades(0);
+        let snap = health.get_health();
+        assert_eq!(snap.messages_per_sec, 0.5);
+        assert_eq!(snap.trades_per_sec, 0.1);
+    }
+
+    #[test]
+    fn test_health_reconnect_tracking() {
+        let mut health = FinanceHealth::new();
+        health.mark_connected();
+        assert_eq!(health.reconnect_count, 0);
+        assert!(health.connected_since.is_some());
+
+        health.mark_disconnected();
+        assert!(health.connected_since.is_none());
+
+        health.mark_connected();
+        assert_eq!(health.reconnect_count, 1);
+        assert!(health.get_health().connected_since.is_some());
+    }
+
+    #[test]
+    fn test_health_records_connection_edges() {
+        let mut health = FinanceHealth::new();
+        health.update_health(String::from("connected"), 0, 0, None);
+        health.update_health(String::from("connected"), 1, 0, None);
+        health.update_health(String::from("connected"), 2, 0, None);
+        health.update_health(String::from("disconnected"), 2, 1, Some(String::from("Server closed connection")));
+        health.update_health(String::from("connected"), 0, 0, None);
+
+        let edges: Vec<(&str, &str)> = health
+            .transitions
+            .iter()
+            .map(|t| (t.from.as_str(), t.to.as_str()))
+            .collect();
+        assert_eq!(edges, [("disconnected", "connected"), ("connected", "disconnected"), ("disconnected", "connected")]);
+        assert_eq!(health.transitions[1].reason.as_deref(), Some("Server closed connection"));
+        assert_eq!(health.get_health().transitions.len(), 3);
+    }
+
+    #[test]
+    fn test_health_keeps_distinct_recent_errors() {
+        let mut health = FinanceHealth::new();
+        health.update_health(String::from("connected"), 1, 1, Some(String::from("Batch #1 had 1 errors processing trades")));
+        health.update_health(String::from("connected"), 2, 1, Some(String::from("Batch #1 had 1 errors processing trades")));
+        health.update_health(String::from("disconnected"), 2, 1, None);
+        health.set_last_error(String::from("connect refused"));
+
+        let messages: Vec<&str> = health.recent_errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["Batch #1 had 1 errors processing trades", "connect refused"]);
+        assert_eq!(health.last_error.as_deref(), Some("connect refused"));
+
+        for i in 0..MAX_RECENT_ERRORS {
+            health.set_last_error(format!("connect refused {i}"));
+        }
+        assert_eq!(health.get_health().recent_errors.len(), MAX_RECENT_ERRORS);
+    }
 
     #[test]
     fn test_quote_response_success() {
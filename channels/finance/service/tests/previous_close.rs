@@ -0,0 +1,59 @@
+//! Previous-close refresh — verifies a zeroed quote can't overwrite a good
+//! stored previous close, while a positive one still replaces it.
+//!
+//! Skips when DATABASE_URL is not set so unit-test runs in CI without
+//! a Postgres backend don't fail.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use finance_service::database::{initialize_pool, update_previous_close};
+use sqlx::{query, query_scalar};
+
+const SYMBOL: &str = "__PC_TEST__";
+
+async fn skip_unless_db() -> Option<Arc<sqlx::PgPool>> {
+    if std::env::var("DATABASE_URL").is_err() && std::env::var("DB_HOST").is_err() {
+        eprintln!("Skipping previous close test: no DATABASE_URL / DB_HOST set");
+        return None;
+    }
+    match initialize_pool().await {
+        Ok(p) => Some(Arc::new(p)),
+        Err(e) => {
+            eprintln!("Skipping previous close test: could not connect: {e:#}");
+            None
+        }
+    }
+}
+
+async fn stored(pool: &sqlx::PgPool) -> f64 {
+    query_scalar("SELECT previous_close::float8 FROM trades WHERE symbol = $1")
+        .bind(SYMBOL)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_zeroed_quote_keeps_existing_previous_close() {
+    let Some(pool) = skip_unless_db().await else { return };
+
+    query("DELETE FROM trades WHERE symbol = $1")
+        .bind(SYMBOL)
+        .execute(&*pool).await.unwrap();
+    query("INSERT INTO trades (symbol, price, previous_close, price_change, percentage_change, direction)
+           VALUES ($1, 101, 100, 1, 1, 'up')")
+        .bind(SYMBOL)
+        .execute(&*pool).await.unwrap();
+
+    assert!(!update_previous_close(pool.clone(), SYMBOL.to_string(), 0.0).await.unwrap());
+    assert!(!update_previous_close(pool.clone(), SYMBOL.to_string(), f64::NAN).await.unwrap());
+    assert_eq!(stored(&pool).await, 100.0);
+
+    assert!(update_previous_close(pool.clone(), SYMBOL.to_string(), 99.5).await.unwrap());
+    assert_eq!(stored(&pool).await, 99.5);
+
+    query("DELETE FROM trades WHERE symbol = $1")
+        .bind(SYMBOL)
+        .execute(&*pool).await.unwrap();
+}
@@ -0,0 +1,62 @@
+//! Startup symbol insert — verifies every symbol gets a `trades` row when
+//! the list spans several chunks, and that existing rows are left alone.
+//!
+//! Skips when DATABASE_URL is not set so unit-test runs in CI without
+//! a Postgres backend don't fail.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use finance_service::database::{initialize_pool, insert_symbols};
+use sqlx::{query, query_scalar};
+
+const PREFIX: &str = "__INIT_TEST_";
+
+async fn skip_unless_db() -> Option<Arc<sqlx::PgPool>> {
+    if std::env::var("DATABASE_URL").is_err() && std::env::var("DB_HOST").is_err() {
+        eprintln!("Skipping symbol init test: no DATABASE_URL / DB_HOST set");
+        return None;
+    }
+    match initialize_pool().await {
+        Ok(p) => Some(Arc::new(p)),
+        Err(e) => {
+            eprintln!("Skipping symbol init test: could not connect: {e:#}");
+            None
+        }
+    }
+}
+
+async fn clear(pool: &sqlx::PgPool) {
+    query("DELETE FROM trades WHERE symbol LIKE $1 || '%'")
+        .bind(PREFIX)
+        .execute(pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_chunked_insert_covers_every_symbol() {
+    let Some(pool) = skip_unless_db().await else { return };
+    clear(&pool).await;
+
+    query("INSERT INTO trades (symbol, price, previous_close, price_change, percentage_change, direction)
+           VALUES ($1, 101, 100, 1, 1, 'up')")
+        .bind(format!("{PREFIX}0"))
+        .execute(&*pool).await.unwrap();
+
+    // Seven symbols in chunks of three: three statements, the last one short.
+    let symbols: Vec<String> = (0..7).map(|i| format!("{PREFIX}{i}")).collect();
+    assert_eq!(insert_symbols(&pool, &symbols, 3).await, 6);
+
+    let count: i64 = query_scalar("SELECT COUNT(*) FROM trades WHERE symbol LIKE $1 || '%'")
+        .bind(PREFIX)
+        .fetch_one(&*pool).await.unwrap();
+    assert_eq!(count, 7);
+    let price: f64 = query_scalar("SELECT price::float8 FROM trades WHERE symbol = $1")
+        .bind(format!("{PREFIX}0"))
+        .fetch_one(&*pool).await.unwrap();
+    assert_eq!(price, 101.0);
+
+    // A second run adds nothing.
+    assert_eq!(insert_symbols(&pool, &symbols, 3).await, 0);
+
+    clear(&pool).await;
+}
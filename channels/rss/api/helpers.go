@@ -137,3 +137,52 @@ func ProxyInternalHealth(c *fiber.Ctx, httpClient *http.Client, internalURL stri
 	c.Set("Content-Type", "application/json")
 	return c.Status(resp.StatusCode).Send(body)
 }
+
+// normalizeLang reduces a ?lang= value ("en-US", "pt_BR", "EN") to the
+// lowercase primary subtag the ingestion service stores in rss_items.lang.
+// Anything that isn't 2–3 ASCII letters yields "" (no filter).
+func normalizeLang(raw string) string {
+	primary := strings.ToLower(strings.TrimSpace(raw))
+	if i := strings.IndexAny(primary, "-_"); i >= 0 {
+		primary = primary[:i]
+	}
+	if len(primary) < 2 || len(primary) > 3 {
+		return ""
+	}
+	for _, r := range primary {
+		if r < 'a' || r > 'z' {
+			return ""
+		}
+	}
+	return primary
+}
+
+// parseSince parses a ?since= value. Empty means no filter (nil); anything
+// other than an RFC 3339 timestamp is an error.
+func parseSince(raw string) (*time.Time, error) {
+	raw = strings.TrimSpace(raw)
+	if raw == "" {
+		return nil, nil
+	}
+	t, err := time.Parse(time.RFC3339Nano, raw)
+	if err != nil {
+		return nil, err
+	}
+	return &t, nil
+}
+
+// itemsCursor is the newest item timestamp in items, using the same
+// published_at-else-created_at rule as the ?since= filter. ok is false for
+// an empty slice.
+func itemsCursor(items []RssItem) (cursor time.Time, ok bool) {
+	for _, item := range items {
+		ts := item.CreatedAt
+		if item.PublishedAt != nil {
+			ts = *item.PublishedAt
+		}
+		if !ok || ts.After(cursor) {
+			cursor, ok = ts, true
+		}
+	}
+	return cursor, ok
+}
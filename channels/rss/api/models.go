@@ -12,6 +12,7 @@ type RssItem struct {
 	Description string     `json:"description"`
 	SourceName  string     `json:"source_name"`
 	PublishedAt *time.Time `json:"published_at"`
+	Lang        *string    `json:"lang"`
 	CreatedAt   time.Time  `json:"created_at"`
 	UpdatedAt   time.Time  `json:"updated_at"`
 }
@@ -36,6 +36,10 @@ const (
 	// DefaultRSSItemsLimit caps the number of RSS items returned for dashboard.
 	DefaultRSSItemsLimit = 50
 
+	// HeaderRSSCursor carries the newest item timestamp in a dashboard
+	// response; clients send it back as ?since= on their next poll.
+	HeaderRSSCursor = "X-RSS-Cursor"
+
 	// MaxConsecutiveFailures is the threshold above which feeds are excluded
 	// from the catalog.
 	MaxConsecutiveFailures = 3
@@ -471,34 +475,66 @@ func (a *App) handleInternalCDC(c *fiber.Ctx) error {
 }
 
 // handleInternalDashboard returns RSS items for a user's dashboard.
-// Query param: user={logto_sub}
+// Query params: user={logto_sub}, optional lang={code} to keep only items
+// in that language (e.g. "en"; region subtags like "en-GB" are ignored),
+// optional since={rfc3339} to keep only items published (or, without a
+// publish date, ingested) at or after that time. The newest timestamp in
+// the response is returned in X-RSS-Cursor for the next ?since=.
 func (a *App) handleInternalDashboard(c *fiber.Ctx) error {
 	ctx := c.Context()
 
+	since, err := parseSince(c.Query("since"))
+	if err != nil {
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error",
+			Error:  "since must be an RFC 3339 timestamp",
+		})
+	}
+
 	userSub := c.Query("user")
 	if userSub == "" {
 		return c.JSON(fiber.Map{"rss": []RssItem{}})
 	}
+	lang := normalizeLang(c.Query("lang"))
 
-	// Check per-user cache first
+	// Check per-user cache first. Filtered views are cached under their own
+	// key; invalidation only clears the unfiltered one, so they lean on the
+	// short TTL. Delta reads are keyed by each client's own cursor, so they
+	// skip the cache entirely.
 	cacheKey := CacheKeyRSSPrefix + userSub
+	if lang != "" {
+		cacheKey += ":lang:" + lang
+	}
 	var items []RssItem
-	if GetCache(a.rdb, ctx, cacheKey, &items) {
-		return c.JSON(fiber.Map{"rss": items})
+	if since == nil && GetCache(a.rdb, ctx, cacheKey, &items) {
+		return dashboardResponse(c, items, since)
 	}
 
 	// Get user's RSS feed URLs from their channel config
 	feedURLs := a.getUserRSSFeedURLs(ctx, userSub)
 	if len(feedURLs) == 0 {
-		return c.JSON(fiber.Map{"rss": []RssItem{}})
+		return dashboardResponse(c, []RssItem{}, since)
 	}
 
-	items = a.queryRSSItems(ctx, feedURLs)
+	items = a.queryRSSItems(ctx, feedURLs, lang, since)
 	if items == nil {
 		items = make([]RssItem, 0)
 	}
 
-	SetCache(a.rdb, ctx, cacheKey, items, RSSItemsCacheTTL)
+	if since == nil {
+		SetCache(a.rdb, ctx, cacheKey, items, RSSItemsCacheTTL)
+	}
+	return dashboardResponse(c, items, since)
+}
+
+// dashboardResponse writes items with the X-RSS-Cursor header. With nothing
+// newer than since, the cursor stays at since so the client doesn't rewind.
+func dashboardResponse(c *fiber.Ctx, items []RssItem, since *time.Time) error {
+	if cursor, ok := itemsCursor(items); ok {
+		c.Set(HeaderRSSCursor, cursor.Format(time.RFC3339Nano))
+	} else if since != nil {
+		c.Set(HeaderRSSCursor, since.Format(time.RFC3339Nano))
+	}
 	return c.JSON(fiber.Map{"rss": items})
 }
 
@@ -668,19 +704,30 @@ func (a *App) getUserRSSFeedURLs(ctx context.Context, logtoSub string) []string
 	return extractFeedURLsFromConfig(configJSON)
 }
 
-// queryRSSItems fetches the latest RSS items for the given feed URLs.
-func (a *App) queryRSSItems(ctx context.Context, feedURLs []string) []RssItem {
+// queryRSSItems fetches the latest RSS items for the given feed URLs,
+// limited to one language when lang is non-empty and to items at or after
+// since (by published_at, falling back to created_at) when since is set.
+//
+// A delta read (since set) returns the oldest matching items first. When
+// more than the limit are new, the cursor then only moves past items that
+// were actually sent, and the next poll picks up the rest.
+func (a *App) queryRSSItems(ctx context.Context, feedURLs []string, lang string, since *time.Time) []RssItem {
 	if len(feedURLs) == 0 {
 		return nil
 	}
 
+	order := "published_at DESC NULLS LAST"
+	if since != nil {
+		order = "COALESCE(published_at, created_at) ASC, id ASC"
+	}
 	rows, err := a.db.Query(ctx, `
-		SELECT id, feed_url, guid, title, link, description, source_name, published_at, created_at, updated_at
+		SELECT id, feed_url, guid, title, link, description, source_name, published_at, lang, created_at, updated_at
 		FROM rss_items
-		WHERE feed_url = ANY($1)
-		ORDER BY published_at DESC NULLS LAST
+		WHERE feed_url = ANY($1) AND ($3 = '' OR lang = $3)
+			AND ($4::timestamptz IS NULL OR COALESCE(published_at, created_at) >= $4)
+		ORDER BY `+order+`
 		LIMIT $2
-	`, feedURLs, DefaultRSSItemsLimit)
+	`, feedURLs, DefaultRSSItemsLimit, lang, since)
 	if err != nil {
 		log.Printf("[RSS] Items query failed: %v", err)
 		return nil
@@ -693,7 +740,7 @@ func (a *App) queryRSSItems(ctx context.Context, feedURLs []string) []RssItem {
 		if err := rows.Scan(
 			&item.ID, &item.FeedURL, &item.GUID, &item.Title, &item.Link,
 			&item.Description, &item.SourceName, &item.PublishedAt,
-			&item.CreatedAt, &item.UpdatedAt,
+			&item.Lang, &item.CreatedAt, &item.UpdatedAt,
 		); err != nil {
 			log.Printf("[RSS] Items scan error: %v", err)
 			continue
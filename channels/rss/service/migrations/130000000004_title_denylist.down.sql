@@ -0,0 +1 @@
+ALTER TABLE tracked_feeds DROP COLUMN IF EXISTS title_denylist;
@@ -0,0 +1,6 @@
+-- Per-feed title denylist: a JSON array of substrings, e.g.
+-- '["Sponsored", "[AD]"]'. Entries whose title contains any of them (case
+-- insensitive) are skipped and counted as filtered. NULL means the feed only
+-- uses the global RSS_TITLE_DENYLIST.
+
+ALTER TABLE tracked_feeds ADD COLUMN IF NOT EXISTS title_denylist JSONB;
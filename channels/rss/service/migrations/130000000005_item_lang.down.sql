@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_rss_items_lang;
+ALTER TABLE rss_items DROP COLUMN IF EXISTS lang;
@@ -0,0 +1,7 @@
+-- Language of each item as a lowercase ISO 639 code ("en", "de"), taken from
+-- the entry's xml:lang or the feed's <language>, primary subtag only. NULL
+-- when neither declares one.
+
+ALTER TABLE rss_items ADD COLUMN IF NOT EXISTS lang TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_rss_items_lang ON rss_items (lang) WHERE lang IS NOT NULL;
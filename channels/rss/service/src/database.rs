@@ -1,10 +1,13 @@
-use std::{env, time::Duration, sync::Arc};
+use std::{collections::HashSet, env, time::Duration, sync::Arc};
 use anyhow::{Context, Result};
 use sqlx::postgres::PgPoolOptions;
 pub use sqlx::PgPool;
-use sqlx::{FromRow, query, query_as};
-use serde::Deserialize;
+use sqlx::{FromRow, PgConnection, query, query_as, query_scalar};
+use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::retry::with_db_retry;
+use crate::text::truncate_bytes_safe;
+use crate::types::{SeedFailure, SeedSummary};
 
 /// Build the sqlx migrator for this service.
 ///
@@ -37,15 +40,16 @@ pub const RSS_MIGRATION_NEW_MIN: i64 = 130_000_000_000;
 pub const RSS_MIGRATION_NEW_MAX: i64 = 139_999_999_999;
 
 pub async fn initialize_pool() -> Result<PgPool> {
+    let size = crate::db_pool_size();
     let pool_options = PgPoolOptions::new()
         // Pool sizing rationale: rss runs up to 20 concurrent feed polls
         // per cycle (see the semaphore in lib.rs), each doing a batch
-        // upsert. Ten connections left us fighting for them; 20 matches
-        // the concurrency ceiling and removes the contention.
-        .max_connections(20)
-        // Keep one warm connection so the first query after an idle period
-        // doesn't eat the TLS/auth handshake latency.
-        .min_connections(1)
+        // upsert. Ten connections left us fighting for them; the default
+        // of 20 matches the concurrency ceiling and removes the contention.
+        .max_connections(size.max_connections)
+        // By default keep one warm connection so the first query after an
+        // idle period doesn't eat the TLS/auth handshake latency.
+        .min_connections(size.min_connections)
         .acquire_timeout(Duration::from_secs(10))
         .idle_timeout(Duration::from_secs(30));
 
@@ -78,6 +82,10 @@ pub async fn initialize_pool() -> Result<PgPool> {
         format!("postgres://{}:{}@{}:{}/{}", user, password, raw_host, port, database)
     };
 
+    eprintln!(
+        "[DB] Pool: max {} connections, min {}",
+        size.max_connections, size.min_connections
+    );
     eprintln!("[DB] Connecting to database...");
     let pool = tokio::time::timeout(
         Duration::from_secs(15),
@@ -160,6 +168,11 @@ pub struct FeedConfig {
     pub name: String,
     pub url: String,
     pub category: String,
+    /// Make `POST /reseed` overwrite the category of an already-tracked
+    /// feed with this entry's. Without it a reseed never touches existing
+    /// rows.
+    #[serde(default)]
+    pub force_category: bool,
 }
 
 #[derive(Clone, Debug, FromRow)]
@@ -170,6 +183,8 @@ pub struct TrackedFeed {
     pub is_default: bool,
     pub is_enabled: bool,
     pub consecutive_failures: i32,
+    /// Title substrings this feed skips on top of the global denylist.
+    pub title_denylist: Vec<String>,
 }
 
 // ── Parsed article ready for DB insertion ────────────────────────
@@ -182,19 +197,43 @@ pub struct ParsedArticle {
     pub description: String,
     pub source_name: String,
     pub published_at: Option<DateTime<Utc>>,
+    /// Normalized language code, see [`normalize_lang`](crate::normalize_lang).
+    pub lang: Option<String>,
 }
 
-// ── Seed default feeds from config file (batched) ───────────────
+// ── Seed default feeds from config file ─────────────────────────
 
-pub async fn seed_tracked_feeds(pool: Arc<PgPool>, feeds: Vec<FeedConfig>) -> Result<()> {
-    if feeds.is_empty() {
-        return Ok(());
+/// Upsert every config feed inside one transaction, so a crash mid-seed
+/// leaves no partial rows. Each feed gets its own savepoint: a feed the
+/// database rejects is rolled back, logged and reported without blocking
+/// the rest. Repeated URLs after the first are skipped.
+///
+/// When every feed already matches its row (the usual restart), nothing is
+/// written and the summary is marked `unchanged`.
+pub async fn seed_tracked_feeds(pool: Arc<PgPool>, feeds: Vec<FeedConfig>) -> SeedSummary {
+    let (unique, skipped) = dedup_feeds(&feeds);
+    if feeds_already_seeded(&pool, &unique).await {
+        return SeedSummary { skipped, unchanged: true, ..Default::default() };
     }
 
-    let urls: Vec<&str> = feeds.iter().map(|f| f.url.as_str()).collect();
-    let names: Vec<&str> = feeds.iter().map(|f| f.name.as_str()).collect();
-    let categories: Vec<&str> = feeds.iter().map(|f| f.category.as_str()).collect();
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => {
+            log::error!("Failed to start feed seed transaction: {}", e);
+            return all_failed(&unique, skipped, &e);
+        }
+    };
+    let summary = seed_feeds_in(&mut tx, &feeds).await;
+    if let Err(e) = tx.commit().await {
+        log::error!("Failed to commit feed seed: {}", e);
+        return all_failed(&unique, skipped, &e);
+    }
+    summary
+}
 
+/// The seeding half of [`seed_tracked_feeds`], run on a caller-owned
+/// connection. Nothing is visible to others until the caller commits.
+pub async fn seed_feeds_in(conn: &mut PgConnection, feeds: &[FeedConfig]) -> SeedSummary {
     // On conflict we want a default feed to always end up `is_enabled = true`
     // and `is_default = true`, even if somebody previously flipped it off or
     // it got quarantined. Before this change a default feed that hit the 288
@@ -207,9 +246,7 @@ pub async fn seed_tracked_feeds(pool: Arc<PgPool>, feeds: Vec<FeedConfig>) -> Re
     // that's been down all week and is only temporarily back up.
     let statement = "
         INSERT INTO tracked_feeds (url, name, category, is_default, is_enabled, consecutive_failures)
-        SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[])
-            AS t(url, name, category),
-            LATERAL (SELECT true AS is_default, true AS is_enabled, 0 AS consecutive_failures) defaults
+        VALUES ($1, $2, $3, true, true, 0)
         ON CONFLICT (url) DO UPDATE SET
             name = EXCLUDED.name,
             category = EXCLUDED.category,
@@ -220,22 +257,151 @@ pub async fn seed_tracked_feeds(pool: Arc<PgPool>, feeds: Vec<FeedConfig>) -> Re
                 ELSE tracked_feeds.consecutive_failures
             END
     ";
+
+    let (unique, skipped) = dedup_feeds(feeds);
+    let mut summary = SeedSummary { skipped, ..Default::default() };
+    for feed in unique {
+        // A failed statement aborts the whole transaction unless it is
+        // rolled back to a savepoint taken just before it.
+        let res = async {
+            query("SAVEPOINT seed_row").execute(&mut *conn).await?;
+            let res = query(statement)
+                .bind(&feed.url)
+                .bind(&feed.name)
+                .bind(&feed.category)
+                .execute(&mut *conn)
+                .await;
+            let release = if res.is_ok() { "RELEASE SAVEPOINT seed_row" } else { "ROLLBACK TO SAVEPOINT seed_row" };
+            query(release).execute(&mut *conn).await?;
+            res
+        }
+        .await;
+        match res {
+            Ok(_) => summary.seeded += 1,
+            Err(e) => {
+                log::error!("Failed to seed feed {}: {}", feed.url, e);
+                summary.failed.push(SeedFailure { entry: feed.url.clone(), reason: e.to_string() });
+            }
+        }
+    }
+    summary
+}
+
+/// First occurrence of each URL, plus how many repeats were dropped.
+fn dedup_feeds(feeds: &[FeedConfig]) -> (Vec<&FeedConfig>, u64) {
+    let mut seen = HashSet::new();
+    let unique: Vec<&FeedConfig> = feeds.iter().filter(|f| seen.insert(f.url.as_str())).collect();
+    let skipped = (feeds.len() - unique.len()) as u64;
+    (unique, skipped)
+}
+
+/// Whether every feed already has a row the upsert would leave unchanged:
+/// same name and category, default, enabled and not quarantined. Errors
+/// count as "no" so the full seed runs and reports them.
+async fn feeds_already_seeded(pool: &PgPool, feeds: &[&FeedConfig]) -> bool {
+    let urls: Vec<&str> = feeds.iter().map(|f| f.url.as_str()).collect();
+    let names: Vec<&str> = feeds.iter().map(|f| f.name.as_str()).collect();
+    let categories: Vec<&str> = feeds.iter().map(|f| f.category.as_str()).collect();
+
+    let statement = "
+        SELECT COUNT(*) FROM tracked_feeds t
+        JOIN UNNEST($1::text[], $2::text[], $3::text[]) AS c(url, name, category)
+            ON t.url = c.url
+        WHERE t.name = c.name
+          AND t.category = c.category
+          AND t.is_default
+          AND t.is_enabled
+          AND t.consecutive_failures < 288
+    ";
+    let matching: Result<i64, sqlx::Error> = query_scalar(statement)
+        .bind(&urls)
+        .bind(&names)
+        .bind(&categories)
+        .fetch_one(pool)
+        .await;
+    matches!(matching, Ok(n) if n == feeds.len() as i64)
+}
+
+/// A summary reporting every feed as failed, for when the transaction
+/// itself could not be opened or committed.
+fn all_failed(feeds: &[&FeedConfig], skipped: u64, e: &sqlx::Error) -> SeedSummary {
+    let failed = feeds
+        .iter()
+        .map(|f| SeedFailure { entry: f.url.clone(), reason: e.to_string() })
+        .collect();
+    SeedSummary { skipped, failed, ..Default::default() }
+}
+
+// ── Reseed default feeds without touching existing rows ─────────
+
+/// Insert config feeds whose URL isn't tracked yet. Unlike
+/// [`seed_tracked_feeds`] this never re-enables, renames or un-quarantines
+/// an existing feed. Returns how many were added.
+pub async fn reseed_tracked_feeds(pool: &Arc<PgPool>, feeds: &[FeedConfig]) -> Result<u64> {
+    if feeds.is_empty() {
+        return Ok(0);
+    }
+
+    let urls: Vec<&str> = feeds.iter().map(|f| f.url.as_str()).collect();
+    let names: Vec<&str> = feeds.iter().map(|f| f.name.as_str()).collect();
+    let categories: Vec<&str> = feeds.iter().map(|f| f.category.as_str()).collect();
+
+    let statement = "
+        INSERT INTO tracked_feeds (url, name, category, is_default, is_enabled, consecutive_failures)
+        SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[])
+            AS t(url, name, category),
+            LATERAL (SELECT true AS is_default, true AS is_enabled, 0 AS consecutive_failures) defaults
+        ON CONFLICT (url) DO NOTHING
+    ";
     let mut connection = pool.acquire().await?;
-    query(statement)
+    let result = query(statement)
         .bind(&urls)
         .bind(&names)
         .bind(&categories)
         .execute(&mut *connection)
         .await
-        .context("Failed to batch seed tracked feeds")?;
-    Ok(())
+        .context("Failed to reseed tracked feeds")?;
+    Ok(result.rows_affected())
+}
+
+/// Set the category of every tracked feed whose config entry has
+/// `force_category`, where it differs. Returns how many rows changed.
+pub async fn recategorize_tracked_feeds(pool: &Arc<PgPool>, feeds: &[FeedConfig]) -> Result<u64> {
+    let (urls, categories): (Vec<&str>, Vec<&str>) = feeds
+        .iter()
+        .filter(|f| f.force_category)
+        .map(|f| (f.url.as_str(), f.category.as_str()))
+        .unzip();
+    if urls.is_empty() {
+        return Ok(0);
+    }
+
+    let statement = "
+        UPDATE tracked_feeds
+        SET category = t.category
+        FROM UNNEST($1::text[], $2::text[]) AS t(url, category)
+        WHERE tracked_feeds.url = t.url
+            AND tracked_feeds.category IS DISTINCT FROM t.category
+    ";
+    let mut connection = pool.acquire().await?;
+    let result = query(statement)
+        .bind(&urls)
+        .bind(&categories)
+        .execute(&mut *connection)
+        .await
+        .context("Failed to recategorize tracked feeds")?;
+    Ok(result.rows_affected())
 }
 
 // ── Get all enabled, non-quarantined feeds ──────────────────────
 
 pub async fn get_tracked_feeds(pool: Arc<PgPool>) -> Vec<TrackedFeed> {
     let statement = "
-        SELECT url, name, category, is_default, is_enabled, consecutive_failures
+        SELECT url, name, category, is_default, is_enabled, consecutive_failures,
+            CASE WHEN jsonb_typeof(title_denylist) = 'array'
+                THEN ARRAY(SELECT jsonb_array_elements_text(title_denylist))
+                ELSE '{}'::text[]
+            END AS title_denylist
         FROM tracked_feeds
         WHERE is_enabled = TRUE AND consecutive_failures < 288
     ";
@@ -258,7 +424,11 @@ pub async fn get_tracked_feeds(pool: Arc<PgPool>) -> Vec<TrackedFeed> {
 
 pub async fn get_quarantined_feeds(pool: Arc<PgPool>) -> Vec<TrackedFeed> {
     let statement = "
-        SELECT url, name, category, is_default, is_enabled, consecutive_failures
+        SELECT url, name, category, is_default, is_enabled, consecutive_failures,
+            CASE WHEN jsonb_typeof(title_denylist) = 'array'
+                THEN ARRAY(SELECT jsonb_array_elements_text(title_denylist))
+                ELSE '{}'::text[]
+            END AS title_denylist
         FROM tracked_feeds
         WHERE is_enabled = TRUE AND consecutive_failures >= 288
     ";
@@ -343,15 +513,16 @@ pub async fn batch_upsert_rss_items(pool: &Arc<PgPool>, articles: Vec<ParsedArti
     let descriptions: Vec<&str> = articles.iter().map(|a| a.description.as_str()).collect();
     let source_names: Vec<&str> = articles.iter().map(|a| a.source_name.as_str()).collect();
     let published_ats: Vec<Option<DateTime<Utc>>> = articles.iter().map(|a| a.published_at).collect();
+    let langs: Vec<Option<&str>> = articles.iter().map(|a| a.lang.as_deref()).collect();
 
     // Only touch the row when content actually changed — unchanged articles
     // are skipped so Sequin CDC won't fire redundant UPDATE events on repoll.
     let statement = "
-        INSERT INTO rss_items (feed_url, guid, title, link, description, source_name, published_at)
+        INSERT INTO rss_items (feed_url, guid, title, link, description, source_name, published_at, lang)
         SELECT * FROM UNNEST(
             $1::text[], $2::text[], $3::text[], $4::text[],
-            $5::text[], $6::text[], $7::timestamptz[]
-        ) AS t(feed_url, guid, title, link, description, source_name, published_at)
+            $5::text[], $6::text[], $7::timestamptz[], $8::text[]
+        ) AS t(feed_url, guid, title, link, description, source_name, published_at, lang)
         ON CONFLICT (feed_url, guid)
         DO UPDATE SET
             title = EXCLUDED.title,
@@ -359,6 +530,7 @@ pub async fn batch_upsert_rss_items(pool: &Arc<PgPool>, articles: Vec<ParsedArti
             description = EXCLUDED.description,
             source_name = EXCLUDED.source_name,
             published_at = EXCLUDED.published_at,
+            lang = EXCLUDED.lang,
             updated_at = CURRENT_TIMESTAMP
         WHERE
             rss_items.title        IS DISTINCT FROM EXCLUDED.title
@@ -366,37 +538,46 @@ pub async fn batch_upsert_rss_items(pool: &Arc<PgPool>, articles: Vec<ParsedArti
             OR rss_items.description  IS DISTINCT FROM EXCLUDED.description
             OR rss_items.source_name  IS DISTINCT FROM EXCLUDED.source_name
             OR rss_items.published_at IS DISTINCT FROM EXCLUDED.published_at
+            OR rss_items.lang         IS DISTINCT FROM EXCLUDED.lang
     ";
-    let mut connection = pool.acquire().await?;
-    query(statement)
-        .bind(&feed_urls)
-        .bind(&guids)
-        .bind(&titles)
-        .bind(&links)
-        .bind(&descriptions)
-        .bind(&source_names)
-        .bind(&published_ats)
-        .execute(&mut *connection)
-        .await
-        .context("Failed to batch upsert RSS items")?;
+    let columns = (&feed_urls, &guids, &titles, &links, &descriptions, &source_names, &published_ats, &langs);
+    with_db_retry("batch_upsert_rss_items", || async move {
+        let (feed_urls, guids, titles, links, descriptions, source_names, published_ats, langs) = columns;
+        let mut connection = pool.acquire().await?;
+        query(statement)
+            .bind(feed_urls)
+            .bind(guids)
+            .bind(titles)
+            .bind(links)
+            .bind(descriptions)
+            .bind(source_names)
+            .bind(published_ats)
+            .bind(langs)
+            .execute(&mut *connection)
+            .await
+    })
+    .await
+    .context("Failed to batch upsert RSS items")?;
     Ok(())
 }
 
 // ── Cleanup old articles (batched to keep transactions small) ────
 
-pub async fn cleanup_old_articles(pool: &Arc<PgPool>) -> Result<u64> {
+/// Delete articles published more than `retention_days` ago. Returns how
+/// many rows went.
+pub async fn cleanup_old_articles(pool: &Arc<PgPool>, retention_days: i32) -> Result<u64> {
     let statement = "
         DELETE FROM rss_items
         WHERE id IN (
             SELECT id FROM rss_items
-            WHERE published_at < now() - interval '7 days'
+            WHERE published_at < now() - make_interval(days => $1)
             LIMIT 1000
         )
     ";
     let mut total: u64 = 0;
     let mut connection = pool.acquire().await?;
     loop {
-        let result = query(statement).execute(&mut *connection).await?;
+        let result = query(statement).bind(retention_days).execute(&mut *connection).await?;
         let deleted = result.rows_affected();
         total += deleted;
         if deleted < 1000 {
@@ -405,3 +586,86 @@ pub async fn cleanup_old_articles(pool: &Arc<PgPool>) -> Result<u64> {
     }
     Ok(total)
 }
+
+// ── Dead letters ─────────────────────────────────────────────────
+
+/// `dead_letter.service` value for rows written by this service.
+const DEAD_LETTER_SERVICE: &str = "rss";
+
+/// Cap on a stored payload; entries carry full article bodies.
+const MAX_DEAD_LETTER_PAYLOAD_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DeadLetter {
+    pub id: i64,
+    pub source: String,
+    pub reason: String,
+    pub raw_payload: String,
+    pub occurrences: i32,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+/// Park a feed entry that was skipped. `source` is the feed URL. Another
+/// entry skipped from the same feed for the same reason bumps `occurrences`
+/// and keeps the latest payload rather than adding a row; errors are logged
+/// so a DB hiccup never fails the feed poll.
+pub async fn record_dead_letter(pool: &Arc<PgPool>, source: &str, reason: &str, raw_payload: &str) {
+    let statement = "
+        INSERT INTO dead_letter (service, source, reason, raw_payload)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (service, source, reason) DO UPDATE SET
+            occurrences = dead_letter.occurrences + 1,
+            raw_payload = EXCLUDED.raw_payload,
+            last_seen_at = CURRENT_TIMESTAMP
+    ";
+    let res: Result<(), sqlx::Error> = async {
+        let mut connection = pool.acquire().await?;
+        query(statement)
+            .bind(DEAD_LETTER_SERVICE)
+            .bind(source)
+            .bind(reason)
+            .bind(truncate_bytes_safe(raw_payload, MAX_DEAD_LETTER_PAYLOAD_BYTES))
+            .execute(&mut *connection)
+            .await?;
+        Ok(())
+    }.await;
+
+    if let Err(e) = res {
+        log::error!("Failed to record dead letter from {}: {}", source, e);
+    }
+}
+
+/// Delete this service's dead letters last seen more than `retention_days`
+/// ago. Returns how many rows went.
+pub async fn prune_dead_letters(pool: &Arc<PgPool>, retention_days: i32) -> Result<u64> {
+    let statement = "
+        DELETE FROM dead_letter
+        WHERE service = $1 AND last_seen_at < now() - make_interval(days => $2)
+    ";
+    let mut connection = pool.acquire().await?;
+    let result = query(statement)
+        .bind(DEAD_LETTER_SERVICE)
+        .bind(retention_days)
+        .execute(&mut *connection)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Most recently seen dead letters for this service, newest first.
+pub async fn get_dead_letters(pool: &Arc<PgPool>, limit: i64) -> Result<Vec<DeadLetter>> {
+    let statement = "
+        SELECT id, source, reason, raw_payload, occurrences, created_at, last_seen_at
+        FROM dead_letter
+        WHERE service = $1
+        ORDER BY last_seen_at DESC
+        LIMIT $2
+    ";
+    let mut connection = pool.acquire().await?;
+    let rows = query_as::<_, DeadLetter>(statement)
+        .bind(DEAD_LETTER_SERVICE)
+        .bind(limit)
+        .fetch_all(&mut *connection)
+        .await?;
+    Ok(rows)
+}
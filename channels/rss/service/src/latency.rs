@@ -0,0 +1,114 @@
+//! Upstream fetch latency for the health payload.
+//!
+//! Each service times its upstream requests (api-sports.io, feed fetches,
+//! TwelveData quotes) into a [`FetchLatency`] and reports the summary in
+//! `/health`, so a provider getting gradually slower shows up well before
+//! requests start hitting their timeouts.
+//!
+//! Copied into each Rust service (`finance`, `sports`, `rss`) alongside
+//! `retry.rs`.
+
+use std::{collections::VecDeque, time::Duration};
+
+use serde::{Serialize, Serializer};
+
+/// Most recent fetches a [`FetchLatency`] keeps.
+pub const LATENCY_WINDOW: usize = 256;
+
+/// The last [`LATENCY_WINDOW`] fetch durations. Serializes as its
+/// [`LatencySummary`], or `null` before anything was recorded.
+#[derive(Debug, Clone, Default)]
+pub struct FetchLatency {
+    samples_ms: VecDeque<u64>,
+}
+
+/// Min / mean / nearest-rank percentiles / max over a [`FetchLatency`]
+/// window, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LatencySummary {
+    pub samples: usize,
+    pub min_ms: u64,
+    pub avg_ms: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+impl FetchLatency {
+    pub const fn new() -> Self {
+        Self { samples_ms: VecDeque::new() }
+    }
+
+    /// Add one fetch, dropping the oldest once the window is full.
+    pub fn record(&mut self, elapsed: Duration) {
+        if self.samples_ms.len() == LATENCY_WINDOW {
+            self.samples_ms.pop_front();
+        }
+        self.samples_ms.push_back(u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX));
+    }
+
+    pub fn clear(&mut self) {
+        self.samples_ms.clear();
+    }
+
+    pub fn summary(&self) -> Option<LatencySummary> {
+        let mut sorted: Vec<u64> = self.samples_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        let (&min_ms, &max_ms) = (sorted.first()?, sorted.last()?);
+        let n = sorted.len();
+        let percentile = |p: usize| sorted[(n * p).div_ceil(100).saturating_sub(1)];
+        let total = sorted.iter().fold(0u64, |acc, &ms| acc.saturating_add(ms));
+        Some(LatencySummary {
+            samples: n,
+            min_ms,
+            avg_ms: total / n as u64,
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            max_ms,
+        })
+    }
+}
+
+impl Serialize for FetchLatency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.summary().serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_of_empty_window_is_none() {
+        assert_eq!(FetchLatency::new().summary(), None);
+        assert_eq!(serde_json::to_string(&FetchLatency::new()).unwrap(), "null");
+    }
+
+    #[test]
+    fn summary_percentiles() {
+        let mut latency = FetchLatency::new();
+        for ms in (1..=100).rev() {
+            latency.record(Duration::from_millis(ms));
+        }
+        assert_eq!(
+            latency.summary(),
+            Some(LatencySummary { samples: 100, min_ms: 1, avg_ms: 50, p50_ms: 50, p95_ms: 95, max_ms: 100 })
+        );
+    }
+
+    #[test]
+    fn window_drops_oldest_samples() {
+        let mut latency = FetchLatency::new();
+        latency.record(Duration::from_secs(30));
+        for _ in 0..LATENCY_WINDOW {
+            latency.record(Duration::from_millis(10));
+        }
+        let summary = latency.summary().unwrap();
+        assert_eq!(summary.samples, LATENCY_WINDOW);
+        assert_eq!(summary.max_ms, 10);
+
+        latency.clear();
+        assert_eq!(latency.summary(), None);
+    }
+}
@@ -1,30 +1,47 @@
 use anyhow::{Context, Result};
-use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use axum::{extract::{Query, State}, http::StatusCode, routing::{get, post}, Json, Router};
 use dotenvy::dotenv;
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
-use serde::Serialize;
-use std::{sync::Arc, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::{sync::{Arc, OnceLock}, time::Duration};
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 use rss_service::{
-    database::initialize_pool,
+    auth::require_api_token,
+    database::{cleanup_old_articles, get_dead_letters, initialize_pool, DeadLetter, PgPool},
     init::{fatal, spawn_supervised, ReadinessGate, ReadinessSnapshot},
     log::init_async_logger,
-    start_rss_service, RssHealth,
+    db_pool_size, description_max_chars, min_title_chars, poll_interval, reseed_feeds, title_denylist, resolve_config_path, start_rss_service, CleanupSummary, ReseedSummary, RssConfig, RssHealth,
+    ARTICLE_RETENTION_DAYS, FEED_FETCH_CONCURRENCY, FEEDS_CONFIG_PATH, MAX_FEED_BODY_BYTES,
 };
 
-/// RSS polls on a 5-minute loop. Staleness = 2x that, giving one full cycle
-/// of runway before the pod drops out of the ready pool.
-const INGEST_INTERVAL: Duration = Duration::from_secs(300);
-const MAX_POLL_STALENESS: Duration = Duration::from_secs(300 * 2);
+/// Readiness allows this many poll intervals without a completed cycle,
+/// giving one full cycle of runway before the pod drops out of the ready
+/// pool.
+const POLL_STALENESS_INTERVALS: u32 = 2;
+
+/// Per-feed HTTP timeouts. A slow feed only costs its own slot in the
+/// fetch semaphore.
+const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Longest `?days=` `POST /cleanup` accepts.
+const MAX_CLEANUP_DAYS: i32 = 365;
 
 /// How often the bridge loop checks `RssHealth.last_poll` for progress.
 const READINESS_BRIDGE_INTERVAL: Duration = Duration::from_secs(10);
 
+/// `/dead-letters` page size when `?limit=` is absent, and its upper bound.
+const DEAD_LETTERS_DEFAULT_LIMIT: i64 = 100;
+const DEAD_LETTERS_MAX_LIMIT: i64 = 1000;
+
 #[derive(Clone)]
 struct AppState {
     health: Arc<Mutex<RssHealth>>,
     readiness: Arc<ReadinessGate>,
+    config: Arc<Mutex<RssConfig>>,
+    /// Filled by the background task once Postgres is reachable.
+    pool: Arc<OnceLock<Arc<PgPool>>>,
 }
 
 #[derive(Serialize)]
@@ -121,22 +138,47 @@ async fn run_service() -> Result<()> {
     // a Tokio 1.x runtime".
     let _ = init_async_logger("./logs");
 
-    let health = Arc::new(Mutex::new(RssHealth::new()));
-    let readiness = Arc::new(ReadinessGate::new(Some(MAX_POLL_STALENESS)));
+    let ingest_interval = poll_interval();
+    let health = Arc::new(Mutex::new(RssHealth {
+        poll_interval_secs: ingest_interval.as_secs(),
+        ..RssHealth::new()
+    }));
+    let readiness = Arc::new(ReadinessGate::new(Some(ingest_interval * POLL_STALENESS_INTERVALS)));
 
     // Cancellation token for coordinated shutdown
     let cancel = CancellationToken::new();
 
     // Start HTTP server immediately. Liveness always 200, readiness 503
     // until init + first poll.
+    let config = Arc::new(Mutex::new(RssConfig {
+        config_file: resolve_config_path(FEEDS_CONFIG_PATH),
+        poll_interval_secs: ingest_interval.as_secs(),
+        fetch_concurrency: FEED_FETCH_CONCURRENCY,
+        max_feed_body_bytes: MAX_FEED_BODY_BYTES,
+        http_connect_timeout_secs: HTTP_CONNECT_TIMEOUT.as_secs(),
+        http_timeout_secs: HTTP_TIMEOUT.as_secs(),
+        db_max_connections: db_pool_size().max_connections,
+        db_min_connections: db_pool_size().min_connections,
+        description_max_chars: description_max_chars(),
+        min_title_chars: min_title_chars(),
+        title_denylist: title_denylist().to_vec(),
+        ..Default::default()
+    }));
+    let pool_cell = Arc::new(OnceLock::new());
     let state = AppState {
         health: health.clone(),
         readiness: readiness.clone(),
+        config: config.clone(),
+        pool: pool_cell.clone(),
     };
     let app = Router::new()
         .route("/health", get(health_ready_handler))
         .route("/health/live", get(health_live_handler))
         .route("/health/ready", get(health_ready_handler))
+        .route("/config", get(config_handler))
+        .route("/dead-letters", get(dead_letters_handler))
+        .route("/reseed", post(reseed_handler))
+        .route("/cleanup", post(cleanup_handler))
         .with_state(state);
 
     let port = std::env::var("PORT").unwrap_or_else(|_| "3004".to_string());
@@ -175,6 +217,7 @@ async fn run_service() -> Result<()> {
                 }
             }
         };
+        let _ = pool_cell.set(pool.clone());
 
         // Build HTTP client once and reuse across all cycles for connection pooling.
         // reqwest's builder failing means the TLS stack is broken — no fallback,
@@ -197,8 +240,8 @@ async fn run_service() -> Result<()> {
 
         let http_client = match reqwest::Client::builder()
             .default_headers(default_headers)
-            .timeout(std::time::Duration::from_secs(15))
-            .connect_timeout(std::time::Duration::from_secs(5))
+            .timeout(HTTP_TIMEOUT)
+            .connect_timeout(HTTP_CONNECT_TIMEOUT)
             .gzip(true)
             .build()
         {
@@ -239,7 +282,7 @@ async fn run_service() -> Result<()> {
         });
 
         // Periodic ingest loop.
-        println!("Starting periodic RSS ingest loop (5 minute interval)...");
+        println!("Starting periodic RSS ingest loop ({}s interval)...", ingest_interval.as_secs());
         let mut cycle: u64 = 0;
         loop {
             tokio::select! {
@@ -248,9 +291,9 @@ async fn run_service() -> Result<()> {
                     break;
                 }
                 _ = async {
-                    start_rss_service(pool.clone(), health_bg.clone(), &http_client, cycle).await;
+                    start_rss_service(pool.clone(), health_bg.clone(), &config, &http_client, cycle).await;
                     cycle += 1;
-                    tokio::time::sleep(INGEST_INTERVAL).await;
+                    tokio::time::sleep(ingest_interval).await;
                 } => {}
             }
         }
@@ -290,13 +333,102 @@ async fn shutdown_signal() {
     }
 }
 
+/// Effective configuration — the feeds polled in the last cycle and the
+/// settings they were polled with. Secrets are never part of [`RssConfig`].
+async fn config_handler(State(state): State<AppState>) -> Json<RssConfig> {
+    Json(state.config.lock().await.clone())
+}
+
+#[derive(Deserialize)]
+struct DeadLettersQuery {
+    limit: Option<i64>,
+}
+
+/// Feed entries skipped for a missing guid or title, most recently seen
+/// first. 503 until the DB pool is up.
+async fn dead_letters_handler(
+    State(state): State<AppState>,
+    Query(params): Query<DeadLettersQuery>,
+) -> Result<Json<Vec<DeadLetter>>, (StatusCode, Json<serde_json::Value>)> {
+    let pool = ready_pool(&state)?;
+    let limit = params
+        .limit
+        .unwrap_or(DEAD_LETTERS_DEFAULT_LIMIT)
+        .clamp(1, DEAD_LETTERS_MAX_LIMIT);
+    get_dead_letters(pool, limit).await.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        )
+    })
+}
+
+/// Add any feed in `feeds.json` that isn't tracked yet, without touching
+/// existing rows. Requires the `INGEST_API_TOKEN` bearer token.
+async fn reseed_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ReseedSummary>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    let pool = ready_pool(&state)?;
+    reseed_feeds(pool).await.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        )
+    })
+}
+
+#[derive(Deserialize)]
+struct CleanupQuery {
+    days: Option<i32>,
+}
+
+/// Delete expired articles now instead of at the end of the next cycle,
+/// keeping `?days=` (1 to [`MAX_CLEANUP_DAYS`]) instead of the default
+/// [`ARTICLE_RETENTION_DAYS`] for this run only. Requires the
+/// `INGEST_API_TOKEN` bearer token.
+async fn cleanup_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<CleanupQuery>,
+) -> Result<Json<CleanupSummary>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    let retention_days = params.days.unwrap_or(ARTICLE_RETENTION_DAYS);
+    if !(1..=MAX_CLEANUP_DAYS).contains(&retention_days) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("days must be 1-{MAX_CLEANUP_DAYS}")})),
+        ));
+    }
+    let pool = ready_pool(&state)?;
+    let deleted = cleanup_old_articles(pool, retention_days).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("{e:#}")})),
+        )
+    })?;
+    println!("Manual cleanup deleted {deleted} articles older than {retention_days} days");
+    Ok(Json(CleanupSummary { deleted, retention_days }))
+}
+
+/// The DB pool, or a 503 while the init task is still connecting.
+fn ready_pool(state: &AppState) -> Result<&Arc<PgPool>, (StatusCode, Json<serde_json::Value>)> {
+    state.pool.get().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "database not ready"})),
+        )
+    })
+}
+
 /// Liveness probe: 200 as long as the process is up.
 async fn health_live_handler() -> (StatusCode, Json<serde_json::Value>) {
     (StatusCode::OK, Json(serde_json::json!({"status": "alive"})))
 }
 
 /// Readiness probe: 200 only when init succeeded AND the first poll cycle
-/// completed within `MAX_POLL_STALENESS`.
+/// completed within `POLL_STALENESS_INTERVALS` poll intervals.
 async fn health_ready_handler(
     State(state): State<AppState>,
 ) -> (StatusCode, Json<ReadyPayload>) {
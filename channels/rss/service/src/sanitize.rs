@@ -0,0 +1,245 @@
+//! Allowlist HTML sanitizer for article descriptions, used in place of the
+//! full tag strip when `RSS_KEEP_SAFE_HTML` is on.
+//!
+//! Only [`ALLOWED_TAGS`] survive, and they lose every attribute except an
+//! `href` on `<a>` with a safe scheme. Script-like elements are removed
+//! together with their content; any other tag is dropped but its text kept.
+//! Descriptions are truncated before they get here, so a tag cut off at the
+//! end is dropped and still-open tags are closed to keep the output
+//! well-formed.
+
+/// Tags kept in sanitized descriptions.
+pub const ALLOWED_TAGS: &[&str] = &["p", "br", "a", "strong", "em", "ul", "li"];
+
+/// Elements removed along with everything inside them.
+const DROPPED_WITH_CONTENT: &[&str] = &["script", "style", "iframe", "object", "noscript", "template"];
+
+/// `href` prefixes a kept link may start with (compared case-insensitively).
+const SAFE_HREF_SCHEMES: &[&str] = &["http://", "https://", "mailto:"];
+
+/// Reduce `input` to the [`ALLOWED_TAGS`] allowlist, collapsing whitespace
+/// the same way the plain-text strip does.
+pub fn sanitize_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut open: Vec<&'static str> = Vec::new();
+    let mut rest = input;
+
+    while let Some(lt) = rest.find('<') {
+        out.push_str(&rest[..lt]);
+        rest = &rest[lt..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(gt) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = parse_tag(&rest[1..gt]);
+        rest = &rest[gt + 1..];
+        let Some((name, closing, attrs)) = tag else {
+            continue;
+        };
+
+        if !closing && DROPPED_WITH_CONTENT.contains(&name.as_str()) {
+            rest = skip_past_close(rest, &name);
+            continue;
+        }
+        let Some(&name) = ALLOWED_TAGS.iter().find(|t| **t == name) else {
+            continue;
+        };
+
+        if name == "br" {
+            if !closing {
+                out.push_str("<br>");
+            }
+        } else if closing {
+            // Unmatched closers are dropped; matching one also closes
+            // anything opened inside it.
+            if let Some(pos) = open.iter().rposition(|t| *t == name) {
+                for inner in open.drain(pos..).rev() {
+                    push_close(&mut out, inner);
+                }
+            }
+        } else {
+            let href = (name == "a")
+                .then(|| attr_value(attrs, "href").and_then(safe_href))
+                .flatten();
+            match href {
+                Some(href) => {
+                    out.push_str("<a href=\"");
+                    out.push_str(&href);
+                    out.push_str("\" rel=\"nofollow noopener\">");
+                }
+                None => {
+                    out.push('<');
+                    out.push_str(name);
+                    out.push('>');
+                }
+            }
+            open.push(name);
+        }
+    }
+    out.push_str(rest);
+    for name in open.into_iter().rev() {
+        push_close(&mut out, name);
+    }
+
+    out.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Split the inside of `<...>` into lowercase tag name, whether it is a
+/// closing tag, and the raw attribute text. `None` for doctypes, processing
+/// instructions and other things without a name.
+fn parse_tag(inner: &str) -> Option<(String, bool, &str)> {
+    let inner = inner.trim_start();
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, inner),
+    };
+    let name_end = inner
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(inner.len());
+    if name_end == 0 {
+        return None;
+    }
+    Some((inner[..name_end].to_ascii_lowercase(), closing, &inner[name_end..]))
+}
+
+/// The remainder of `rest` after the `</name ...>` that closes a dropped
+/// element, or nothing if it is never closed.
+fn skip_past_close<'a>(rest: &'a str, name: &str) -> &'a str {
+    // ASCII lowercasing keeps byte offsets, so indices carry over to `rest`.
+    let lower = rest.to_ascii_lowercase();
+    let Some(start) = lower.find(&format!("</{name}")) else {
+        return "";
+    };
+    match lower[start..].find('>') {
+        Some(end) => &rest[start + end + 1..],
+        None => "",
+    }
+}
+
+/// The value of attribute `wanted` in a tag's attribute text, handling
+/// double-, single- and unquoted values.
+fn attr_value<'a>(attrs: &'a str, wanted: &str) -> Option<&'a str> {
+    let is_sep = |c: char| c.is_whitespace() || c == '/';
+    let mut rest = attrs.trim_start_matches(is_sep);
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+
+        let mut value = "";
+        if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            let (v, tail) = match after_eq.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let quoted = &after_eq[1..];
+                    let end = quoted.find(quote).unwrap_or(quoted.len());
+                    (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                    after_eq.split_at(end)
+                }
+            };
+            value = v;
+            rest = tail;
+        }
+        if name.eq_ignore_ascii_case(wanted) {
+            return Some(value);
+        }
+        rest = rest.trim_start_matches(is_sep);
+    }
+    None
+}
+
+/// `raw` made safe to emit inside a double-quoted `href`, or `None` when it
+/// doesn't start with one of [`SAFE_HREF_SCHEMES`]. Control characters are
+/// removed before the check so `java\nscript:` can't slip through.
+fn safe_href(raw: &str) -> Option<String> {
+    let cleaned: String = raw.trim().chars().filter(|c| !c.is_control()).collect();
+    let lower = cleaned.to_ascii_lowercase();
+    if !SAFE_HREF_SCHEMES.iter().any(|scheme| lower.starts_with(scheme)) {
+        return None;
+    }
+    Some(
+        cleaned
+            .replace('"', "&quot;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace(' ', "%20"),
+    )
+}
+
+fn push_close(out: &mut String, name: &str) {
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_allowed_tags() {
+        assert_eq!(
+            sanitize_html("<p>Hello <strong>bold</strong> and <em>em</em><br/>next</p>"),
+            "<p>Hello <strong>bold</strong> and <em>em</em><br>next</p>"
+        );
+        assert_eq!(sanitize_html("<UL><LI>one</LI><li>two</li></UL>"), "<ul><li>one</li><li>two</li></ul>");
+    }
+
+    #[test]
+    fn strips_other_tags_but_keeps_text() {
+        assert_eq!(
+            sanitize_html("<div class=\"x\"><span>Text</span> <img src=\"a.png\"> <b>b</b></div>"),
+            "Text b"
+        );
+    }
+
+    #[test]
+    fn removes_scripts_with_their_content() {
+        assert_eq!(sanitize_html("<p>Safe</p><script>alert('xss')</script> text"), "<p>Safe</p> text");
+        assert_eq!(sanitize_html("a<STYLE type=\"text/css\">body{}</Style>b"), "ab");
+        assert_eq!(sanitize_html("<p>x<script>never closed"), "<p>x</p>");
+        assert_eq!(sanitize_html("a<!-- <script>hidden</script> -->b"), "ab");
+    }
+
+    #[test]
+    fn strips_dangerous_attributes() {
+        assert_eq!(
+            sanitize_html("<p onclick=\"evil()\" style=\"color:red\">Hi</p>"),
+            "<p>Hi</p>"
+        );
+        assert_eq!(
+            sanitize_html("<a href='https://example.com/a?b=1' onmouseover=\"evil()\" target=_blank>link</a>"),
+            "<a href=\"https://example.com/a?b=1\" rel=\"nofollow noopener\">link</a>"
+        );
+    }
+
+    #[test]
+    fn drops_unsafe_hrefs() {
+        assert_eq!(sanitize_html("<a href=\"javascript:alert(1)\">x</a>"), "<a>x</a>");
+        assert_eq!(sanitize_html("<a href=\"java\nscript:alert(1)\">x</a>"), "<a>x</a>");
+        assert_eq!(sanitize_html("<a href=\"data:text/html,hi\">x</a>"), "<a>x</a>");
+        assert_eq!(sanitize_html("<a href=\"/relative\">x</a>"), "<a>x</a>");
+        assert_eq!(
+            sanitize_html("<a href=mailto:hi@example.com>mail</a>"),
+            "<a href=\"mailto:hi@example.com\" rel=\"nofollow noopener\">mail</a>"
+        );
+    }
+
+    #[test]
+    fn output_is_balanced() {
+        // Truncated mid-tag, with tags still open.
+        assert_eq!(sanitize_html("<p><strong>Cut off here<a hr"), "<p><strong>Cut off here</strong></p>");
+        // A closer for an outer tag closes the inner ones too; strays vanish.
+        assert_eq!(sanitize_html("</em><p><em>x</p>y</li>"), "<p><em>x</em></p>y");
+    }
+}
@@ -1,5 +1,34 @@
+use std::collections::VecDeque;
+
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use crate::DEFAULT_POLL_INTERVAL_SECS;
+use crate::latency::FetchLatency;
+
+/// Status changes kept in the health payload; the oldest is dropped first.
+const MAX_STATUS_TRANSITIONS: usize = 20;
+
+/// One change of `status`, e.g. `starting` → `healthy`. Only edges are
+/// recorded, so a service that stays degraded for an hour adds one entry.
+#[derive(Serialize, Clone, Debug)]
+pub struct StatusTransition {
+    pub from: String,
+    pub to: String,
+    pub at: DateTime<Utc>,
+    /// The error that caused a move to `degraded`; `None` on recovery.
+    pub reason: Option<String>,
+}
+
+/// Errors kept in the health payload; the oldest is dropped first.
+const MAX_RECENT_ERRORS: usize = 20;
+
+/// One error passed to `record_error`, kept even when the status was
+/// already `degraded` so distinct causes don't collapse into `last_error`.
+#[derive(Serialize, Clone, Debug)]
+pub struct RecentError {
+    pub message: String,
+    pub at: DateTime<Utc>,
+}
 
 #[derive(Serialize, Clone)]
 pub struct RssHealth {
@@ -7,8 +36,18 @@ pub struct RssHealth {
     pub last_poll: Option<DateTime<Utc>>,
     pub feeds_polled: u64,
     pub items_ingested: u64,
+    /// Entries skipped this cycle by the title length / denylist filters.
+    pub items_filtered: u64,
     pub error_count: u64,
     pub last_error: Option<String>,
+    /// Most recent status changes, oldest first.
+    pub transitions: VecDeque<StatusTransition>,
+    /// Most recent errors, oldest first. `last_error` is the newest of these.
+    pub recent_errors: VecDeque<RecentError>,
+    /// Configured pause between poll cycles (`RSS_POLL_INTERVAL_SECS`).
+    pub poll_interval_secs: u64,
+    /// Time to download each successfully fetched feed this cycle.
+    pub fetch_latency: FetchLatency,
 }
 
 impl Default for RssHealth {
@@ -28,31 +67,182 @@ impl RssHealth {
             last_poll: None,
             feeds_polled: 0,
             items_ingested: 0,
+            items_filtered: 0,
             error_count: 0,
             last_error: None,
+            transitions: VecDeque::new(),
+            recent_errors: VecDeque::new(),
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            fetch_latency: FetchLatency::new(),
         }
     }
 
-    pub fn record_success(&mut self, items: u64) {
+    pub fn record_success(&mut self, items: u64, filtered: u64) {
         self.last_poll = Some(Utc::now());
         self.feeds_polled += 1;
         self.items_ingested += items;
-        self.status = String::from("healthy");
+        self.items_filtered += filtered;
+        self.set_status("healthy", None);
     }
 
     pub fn record_error(&mut self, error: String) {
         self.error_count += 1;
+        self.set_status("degraded", Some(error.clone()));
+        if self.recent_errors.len() == MAX_RECENT_ERRORS {
+            self.recent_errors.pop_front();
+        }
+        self.recent_errors.push_back(RecentError {
+            message: error.clone(),
+            at: Utc::now(),
+        });
         self.last_error = Some(error);
-        self.status = String::from("degraded");
+    }
+
+    /// Change `status`, logging the edge in `transitions` when it differs.
+    fn set_status(&mut self, to: &str, reason: Option<String>) {
+        if self.status == to {
+            return;
+        }
+        if self.transitions.len() == MAX_STATUS_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(StatusTransition {
+            from: std::mem::replace(&mut self.status, to.to_string()),
+            to: to.to_string(),
+            at: Utc::now(),
+            reason,
+        });
     }
 
     /// Reset per-cycle counters at the start of each poll cycle.
     pub fn reset_cycle(&mut self) {
         self.feeds_polled = 0;
         self.items_ingested = 0;
+        self.items_filtered = 0;
+        self.fetch_latency.clear();
     }
 
     pub fn get_health(&self) -> Self {
         self.clone()
     }
 }
+
+/// Effective running configuration, served at `GET /config`. Answers "why
+/// isn't feed X being polled?" without DB access. Holds no secrets: feed
+/// URLs have credentials and query strings stripped.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct RssConfig {
+    /// Resolved path of `feeds.json`.
+    pub config_file: String,
+    pub poll_interval_secs: u64,
+    pub fetch_concurrency: usize,
+    pub max_feed_body_bytes: usize,
+    pub http_connect_timeout_secs: u64,
+    pub http_timeout_secs: u64,
+    pub db_max_connections: u32,
+    pub db_min_connections: u32,
+    pub description_max_chars: usize,
+    pub min_title_chars: usize,
+    /// Global title denylist; feeds can add their own via `title_denylist`.
+    pub title_denylist: Vec<String>,
+    /// Feeds polled in the most recent cycle; updated every cycle.
+    pub feeds_loaded_at: Option<DateTime<Utc>>,
+    pub feeds: Vec<ConfiguredFeed>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ConfiguredFeed {
+    pub name: String,
+    pub url: String,
+    pub category: String,
+    /// `"file"` for defaults seeded from `feeds.json`, `"env"` for defaults
+    /// from `RSS_FEEDS` (used without the file), `"user"` for feeds added
+    /// through the API.
+    pub source: &'static str,
+    pub consecutive_failures: i32,
+    pub title_denylist: Vec<String>,
+}
+
+/// Outcome of `POST /reseed`.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ReseedSummary {
+    /// Config entries inserted because they weren't tracked yet.
+    pub added: u64,
+    /// Valid entries that were already tracked. Only their category may
+    /// have changed (see `recategorized`).
+    pub existing: u64,
+    /// Existing feeds whose category was overwritten because their entry
+    /// sets `force_category`.
+    pub recategorized: u64,
+    /// Entries dropped by validation.
+    pub skipped: usize,
+}
+
+/// Outcome of `POST /cleanup`.
+#[derive(Serialize, Clone, Debug)]
+pub struct CleanupSummary {
+    /// Articles deleted.
+    pub deleted: u64,
+    /// Retention the run used, from `?days=` or the default.
+    pub retention_days: i32,
+}
+
+/// Outcome of seeding the config feeds on startup.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SeedSummary {
+    /// Feeds inserted or updated.
+    pub seeded: u64,
+    /// Repeats of a URL already earlier in the list.
+    pub skipped: u64,
+    pub failed: Vec<SeedFailure>,
+    /// Every feed already matched its row, so nothing was written.
+    pub unchanged: bool,
+}
+
+/// A config entry the database rejected while seeding.
+#[derive(Serialize, Clone, Debug)]
+pub struct SeedFailure {
+    /// The feed URL.
+    pub entry: String,
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_records_only_status_edges() {
+        let mut health = RssHealth::new();
+        health.record_success(4, 0);
+        health.record_error("feed A: 503".to_string());
+        health.record_error("feed B: 503".to_string());
+        health.record_success(2, 1);
+        health.record_success(1, 0);
+
+        let edges: Vec<(&str, &str)> = health
+            .transitions
+            .iter()
+            .map(|t| (t.from.as_str(), t.to.as_str()))
+            .collect();
+        assert_eq!(edges, [("starting", "healthy"), ("healthy", "degraded"), ("degraded", "healthy")]);
+        assert_eq!(health.transitions[1].reason.as_deref(), Some("feed A: 503"));
+    }
+
+    #[test]
+    fn test_health_keeps_errors_while_degraded() {
+        let mut health = RssHealth::new();
+        health.record_error("feed A: 503".to_string());
+        health.record_error("feed B: invalid XML".to_string());
+
+        let messages: Vec<&str> = health.recent_errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["feed A: 503", "feed B: invalid XML"]);
+        assert_eq!(health.transitions.len(), 1);
+
+        for _ in 0..MAX_RECENT_ERRORS {
+            health.record_error("feed C: timeout".to_string());
+        }
+        assert_eq!(health.recent_errors.len(), MAX_RECENT_ERRORS);
+        assert!(health.recent_errors.iter().all(|e| e.message == "feed C: timeout"));
+    }
+}
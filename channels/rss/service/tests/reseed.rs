@@ -0,0 +1,74 @@
+//! Reseed behavior — verifies a `force_category` entry recategorizes an
+//! already-tracked feed while a plain entry leaves it alone.
+//!
+//! Skips when DATABASE_URL is not set so unit-test runs in CI without
+//! a Postgres backend don't fail.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use rss_service::database::{
+    initialize_pool, recategorize_tracked_feeds, reseed_tracked_feeds, FeedConfig,
+};
+use sqlx::{query, query_scalar};
+
+const FORCED_URL: &str = "https://reseed-test.invalid/forced.xml";
+const PLAIN_URL: &str = "https://reseed-test.invalid/plain.xml";
+
+async fn skip_unless_db() -> Option<Arc<sqlx::PgPool>> {
+    if std::env::var("DATABASE_URL").is_err() && std::env::var("DB_HOST").is_err() {
+        eprintln!("Skipping reseed test: no DATABASE_URL / DB_HOST set");
+        return None;
+    }
+    match initialize_pool().await {
+        Ok(p) => Some(Arc::new(p)),
+        Err(e) => {
+            eprintln!("Skipping reseed test: could not connect: {e:#}");
+            None
+        }
+    }
+}
+
+fn feed(url: &str, category: &str, force_category: bool) -> FeedConfig {
+    FeedConfig {
+        name: "__reseed_test__".to_string(),
+        url: url.to_string(),
+        category: category.to_string(),
+        force_category,
+    }
+}
+
+async fn category(pool: &sqlx::PgPool, url: &str) -> String {
+    query_scalar("SELECT category FROM tracked_feeds WHERE url = $1")
+        .bind(url)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_force_category_updates_existing_feed() {
+    let Some(pool) = skip_unless_db().await else { return };
+
+    query("DELETE FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&[FORCED_URL, PLAIN_URL][..])
+        .execute(&*pool).await.unwrap();
+
+    let original = [feed(FORCED_URL, "Tech", false), feed(PLAIN_URL, "Tech", false)];
+    assert_eq!(reseed_tracked_feeds(&pool, &original).await.unwrap(), 2);
+
+    // Both entries now say "Science", but only one forces it.
+    let edited = [feed(FORCED_URL, "Science", true), feed(PLAIN_URL, "Science", false)];
+    assert_eq!(reseed_tracked_feeds(&pool, &edited).await.unwrap(), 0);
+    assert_eq!(recategorize_tracked_feeds(&pool, &edited).await.unwrap(), 1);
+
+    assert_eq!(category(&pool, FORCED_URL).await, "Science");
+    assert_eq!(category(&pool, PLAIN_URL).await, "Tech");
+
+    // Already matching: a second forced reseed changes nothing.
+    assert_eq!(recategorize_tracked_feeds(&pool, &edited).await.unwrap(), 0);
+
+    query("DELETE FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&[FORCED_URL, PLAIN_URL][..])
+        .execute(&*pool).await.unwrap();
+}
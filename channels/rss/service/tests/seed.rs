@@ -0,0 +1,126 @@
+//! Startup seeding — verifies a feed the database rejects is reported in
+//! the summary without stopping the feeds after it, that a seed interrupted
+//! before commit leaves no rows, and that re-seeding is a no-op.
+//!
+//! Skips when DATABASE_URL is not set so unit-test runs in CI without
+//! a Postgres backend don't fail.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use rss_service::database::{initialize_pool, seed_feeds_in, seed_tracked_feeds, FeedConfig};
+use sqlx::{query, query_scalar};
+
+const FIRST_URL: &str = "https://seed-test.invalid/first.xml";
+const BAD_URL: &str = "https://seed-test.invalid/bad.xml";
+const LAST_URL: &str = "https://seed-test.invalid/last.xml";
+
+async fn skip_unless_db() -> Option<Arc<sqlx::PgPool>> {
+    if std::env::var("DATABASE_URL").is_err() && std::env::var("DB_HOST").is_err() {
+        eprintln!("Skipping seed test: no DATABASE_URL / DB_HOST set");
+        return None;
+    }
+    match initialize_pool().await {
+        Ok(p) => Some(Arc::new(p)),
+        Err(e) => {
+            eprintln!("Skipping seed test: could not connect: {e:#}");
+            None
+        }
+    }
+}
+
+fn feed(url: &str, name: &str) -> FeedConfig {
+    FeedConfig {
+        name: name.to_string(),
+        url: url.to_string(),
+        category: "Tech".to_string(),
+        force_category: false,
+    }
+}
+
+#[tokio::test]
+async fn test_seed_continues_past_a_rejected_feed() {
+    let Some(pool) = skip_unless_db().await else { return };
+    let urls = [FIRST_URL, BAD_URL, LAST_URL];
+
+    query("DELETE FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&urls[..])
+        .execute(&*pool).await.unwrap();
+
+    // Postgres refuses NUL bytes in text columns.
+    let feeds = vec![
+        feed(FIRST_URL, "__seed_test__"),
+        feed(BAD_URL, "__seed\0test__"),
+        feed(FIRST_URL, "__seed_test__"),
+        feed(LAST_URL, "__seed_test__"),
+    ];
+    let summary = seed_tracked_feeds(pool.clone(), feeds).await;
+
+    assert_eq!(summary.seeded, 2);
+    assert_eq!(summary.skipped, 1);
+    assert_eq!(summary.failed.len(), 1);
+    assert_eq!(summary.failed[0].entry, BAD_URL);
+
+    let stored: i64 = query_scalar("SELECT COUNT(*) FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&urls[..])
+        .fetch_one(&*pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 2);
+
+    query("DELETE FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&urls[..])
+        .execute(&*pool).await.unwrap();
+}
+
+async fn stored_count(pool: &sqlx::PgPool, urls: &[&str]) -> i64 {
+    query_scalar("SELECT COUNT(*) FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(urls)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_interrupted_seed_leaves_no_rows() {
+    let Some(pool) = skip_unless_db().await else { return };
+    let urls = ["https://seed-test.invalid/crash-a.xml", "https://seed-test.invalid/crash-b.xml"];
+
+    query("DELETE FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&urls[..])
+        .execute(&*pool).await.unwrap();
+
+    let feeds: Vec<FeedConfig> = urls.iter().map(|url| feed(url, "__seed_test__")).collect();
+    let mut tx = pool.begin().await.unwrap();
+    let summary = seed_feeds_in(&mut tx, &feeds).await;
+    assert_eq!(summary.seeded, 2);
+    // Dropping the transaction without committing stands in for the
+    // process dying between the upserts and the commit.
+    drop(tx);
+
+    assert_eq!(stored_count(&pool, &urls).await, 0);
+}
+
+#[tokio::test]
+async fn test_reseeding_matching_rows_is_a_no_op() {
+    let Some(pool) = skip_unless_db().await else { return };
+    let urls = ["https://seed-test.invalid/noop.xml"];
+
+    query("DELETE FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&urls[..])
+        .execute(&*pool).await.unwrap();
+
+    let feeds = vec![feed(urls[0], "__seed_test__")];
+    let first = seed_tracked_feeds(pool.clone(), feeds.clone()).await;
+    assert!(!first.unchanged);
+    assert_eq!(first.seeded, 1);
+
+    let second = seed_tracked_feeds(pool.clone(), feeds).await;
+    assert!(second.unchanged);
+    assert_eq!(second.seeded, 0);
+    assert_eq!(stored_count(&pool, &urls).await, 1);
+
+    query("DELETE FROM tracked_feeds WHERE url = ANY($1)")
+        .bind(&urls[..])
+        .execute(&*pool).await.unwrap();
+}
@@ -130,7 +130,11 @@ func main() {
 	// -------------------------------------------------------------------------
 	// Fiber HTTP Server
 	// -------------------------------------------------------------------------
-	app := &App{db: pool, rdb: rdb}
+	app := &App{
+		db:              pool,
+		rdb:             rdb,
+		dayRolloverHour: parseDayRolloverHour(os.Getenv("SPORTS_DAY_ROLLOVER_HOUR")),
+	}
 
 	fiberApp := fiber.New(fiber.Config{
 		AppName:               "Scrollr Sports API",
@@ -156,6 +160,7 @@ func main() {
 	fiberApp.Get("/sports/leagues", app.getLeagueCatalog)
 	fiberApp.Get("/sports/standings", app.getStandings)
 	fiberApp.Get("/sports/teams", app.getTeams)
+	fiberApp.Get("/sports/games/today", app.getGamesToday)
 	fiberApp.Get("/sports/health", app.healthHandler)
 
 	// -------------------------------------------------------------------------
@@ -212,6 +217,7 @@ func startRegistration(ctx context.Context, rdb *redis.Client) {
 			{Method: "GET", Path: "/sports/leagues", Auth: false},
 			{Method: "GET", Path: "/sports/standings", Auth: true},
 			{Method: "GET", Path: "/sports/teams", Auth: true},
+			{Method: "GET", Path: "/sports/games/today", Auth: false},
 			{Method: "GET", Path: "/sports/health", Auth: false},
 		},
 	}
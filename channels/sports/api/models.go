@@ -25,6 +25,13 @@ type Game struct {
 	Timer          string    `json:"timer,omitempty"`
 	Venue          string    `json:"venue,omitempty"`
 	Season         string    `json:"season,omitempty"`
+	VenueCity      string    `json:"venue_city,omitempty"`
+	VenueState     string    `json:"venue_state,omitempty"`
+	SeriesSummary  string    `json:"series_summary,omitempty"`
+	// SportGroup is the league's broad sport (basketball, football, hockey,
+	// baseball, soccer) from tracked_leagues.sport, for grouping leagues.
+	// Sport above is the api-sports.io API name.
+	SportGroup     string    `json:"sport_group,omitempty"`
 }
 
 // TrackedLeague represents a league entry from the catalog, enriched with
@@ -113,6 +120,20 @@ type Standing struct {
 	Streak        string `json:"streak,omitempty"`
 }
 
+// TodayGamesResponse is returned by /sports/games/today: the games starting
+// on the requested zone's current local day, grouped by league.
+type TodayGamesResponse struct {
+	Date     string        `json:"date"`
+	Timezone string        `json:"timezone"`
+	Leagues  []LeagueGames `json:"leagues"`
+}
+
+// LeagueGames is one league's slice of a TodayGamesResponse.
+type LeagueGames struct {
+	League string `json:"league"`
+	Games  []Game `json:"games"`
+}
+
 // TeamInfo represents a team entry from the teams table.
 type TeamInfo struct {
 	League     string `json:"league"`
@@ -6,7 +6,10 @@ import (
 	"fmt"
 	"log"
 	"os"
+	"strconv"
+	"strings"
 	"time"
+	_ "time/tzdata" // embed zoneinfo so ?tz= works in minimal container images
 
 	"github.com/gofiber/fiber/v2"
 	"github.com/jackc/pgx/v5"
@@ -42,6 +45,10 @@ const (
 	// TeamsCacheTTL is how long teams data is cached.
 	TeamsCacheTTL = 24 * time.Hour
 
+	// CacheKeySportsTodayPrefix is the Redis key prefix for /sports/games/today,
+	// suffixed with "{tz}:{local date}" so each zone's day is cached separately.
+	CacheKeySportsTodayPrefix = "cache:sports:today:"
+
 	// SportsLeagueSubscribersPrefix is the per-league subscriber set prefix.
 	// Keys: sports:subscribers:league:{NFL}, sports:subscribers:league:{NBA}, etc.
 	SportsLeagueSubscribersPrefix = "sports:subscribers:league:"
@@ -73,6 +80,9 @@ const (
 type App struct {
 	db  *pgxpool.Pool
 	rdb *redis.Client
+	// dayRolloverHour is the local hour /sports/games/today's day starts
+	// at; see parseDayRolloverHour.
+	dayRolloverHour int
 }
 
 // =============================================================================
@@ -619,7 +629,9 @@ func (a *App) queryGames(ctx context.Context, limit int, favoriteTeams map[strin
 			away_team_name, COALESCE(away_team_logo, ''), COALESCE(away_team_score::text, ''), COALESCE(away_team_code, ''),
 			start_time, COALESCE(short_detail, ''), state,
 			COALESCE(status_short, ''), COALESCE(status_long, ''),
-			COALESCE(timer, ''), COALESCE(venue, ''), COALESCE(season, '')
+			COALESCE(timer, ''), COALESCE(venue, ''), COALESCE(season, ''),
+			COALESCE(venue_city, ''), COALESCE(venue_state, ''), COALESCE(series_summary, ''),
+			COALESCE((SELECT tl.sport FROM tracked_leagues tl WHERE tl.name = games.league), '')
 		FROM games
 		ORDER BY
 			CASE state WHEN 'in' THEN 0 WHEN 'pre' THEN 1 ELSE 2 END,
@@ -641,6 +653,8 @@ func (a *App) queryGames(ctx context.Context, limit int, favoriteTeams map[strin
 			&g.AwayTeamName, &g.AwayTeamLogo, &g.AwayTeamScore, &g.AwayTeamCode,
 			&g.StartTime, &g.ShortDetail, &g.State,
 			&g.StatusShort, &g.StatusLong, &g.Timer, &g.Venue, &g.Season,
+			&g.VenueCity, &g.VenueState,
+			&g.SeriesSummary, &g.SportGroup,
 		); err != nil {
 			log.Printf("[Sports] Row scan failed: %v", err)
 			continue
@@ -694,6 +708,7 @@ func (a *App) queryGamesByLeagues(ctx context.Context, leagues []string, limit i
 					away_team_name, away_team_logo, away_team_score, away_team_code,
 					start_time, short_detail, state, status_short, status_long,
 					timer, venue, season,
+					venue_city, venue_state, series_summary,
 					ROW_NUMBER() OVER (
 						PARTITION BY league
 						ORDER BY
@@ -710,7 +725,9 @@ func (a *App) queryGamesByLeagues(ctx context.Context, leagues []string, limit i
 				away_team_name, COALESCE(away_team_logo, ''), COALESCE(away_team_score::text, ''), COALESCE(away_team_code, ''),
 				start_time, COALESCE(short_detail, ''), state,
 				COALESCE(status_short, ''), COALESCE(status_long, ''),
-				COALESCE(timer, ''), COALESCE(venue, ''), COALESCE(season, '')
+				COALESCE(timer, ''), COALESCE(venue, ''), COALESCE(season, ''),
+				COALESCE(venue_city, ''), COALESCE(venue_state, ''), COALESCE(series_summary, ''),
+				COALESCE((SELECT tl.sport FROM tracked_leagues tl WHERE tl.name = ranked.league), '')
 			FROM ranked
 			WHERE rn <= %d
 			ORDER BY
@@ -727,7 +744,9 @@ func (a *App) queryGamesByLeagues(ctx context.Context, leagues []string, limit i
 				away_team_name, COALESCE(away_team_logo, ''), COALESCE(away_team_score::text, ''), COALESCE(away_team_code, ''),
 				start_time, COALESCE(short_detail, ''), state,
 				COALESCE(status_short, ''), COALESCE(status_long, ''),
-				COALESCE(timer, ''), COALESCE(venue, ''), COALESCE(season, '')
+				COALESCE(timer, ''), COALESCE(venue, ''), COALESCE(season, ''),
+				COALESCE(venue_city, ''), COALESCE(venue_state, ''), COALESCE(series_summary, ''),
+				COALESCE((SELECT tl.sport FROM tracked_leagues tl WHERE tl.name = games.league), '')
 			FROM games
 			WHERE league = ANY($1)
 			ORDER BY
@@ -753,6 +772,8 @@ func (a *App) queryGamesByLeagues(ctx context.Context, leagues []string, limit i
 			&g.AwayTeamName, &g.AwayTeamLogo, &g.AwayTeamScore, &g.AwayTeamCode,
 			&g.StartTime, &g.ShortDetail, &g.State,
 			&g.StatusShort, &g.StatusLong, &g.Timer, &g.Venue, &g.Season,
+			&g.VenueCity, &g.VenueState,
+			&g.SeriesSummary, &g.SportGroup,
 		); err != nil {
 			log.Printf("[Sports] Row scan failed: %v", err)
 			continue
@@ -924,6 +945,107 @@ func (a *App) getTeams(c *fiber.Ctx) error {
 	return c.JSON(fiber.Map{"teams": teams})
 }
 
+// parseDayRolloverHour reads SPORTS_DAY_ROLLOVER_HOUR: the local hour at
+// which one sports day ends and the next begins, so a 10pm tip-off that
+// runs past midnight still counts as that evening's game. Unset means
+// midnight; anything outside 0-23 falls back to it with a warning.
+func parseDayRolloverHour(raw string) int {
+	raw = strings.TrimSpace(raw)
+	if raw == "" {
+		return 0
+	}
+	hour, err := strconv.Atoi(raw)
+	if err != nil || hour < 0 || hour > 23 {
+		log.Printf("[Sports] Invalid SPORTS_DAY_ROLLOVER_HOUR=%q, rolling over at midnight", raw)
+		return 0
+	}
+	return hour
+}
+
+// localDayWindow returns the UTC bounds [start, end) of the sports day
+// containing `now` in `loc`, plus that day's date string. A sports day
+// starts at `rolloverHour` local time, so before that hour `now` still
+// belongs to the previous date. Built from local wall-clock times rather
+// than start+24h so DST transition days come out as 23 or 25 hours long.
+func localDayWindow(now time.Time, loc *time.Location, rolloverHour int) (time.Time, time.Time, string) {
+	local := now.In(loc)
+	start := time.Date(local.Year(), local.Month(), local.Day(), rolloverHour, 0, 0, 0, loc)
+	if local.Before(start) {
+		start = time.Date(local.Year(), local.Month(), local.Day()-1, rolloverHour, 0, 0, 0, loc)
+	}
+	end := time.Date(start.Year(), start.Month(), start.Day()+1, rolloverHour, 0, 0, 0, loc)
+	return start.UTC(), end.UTC(), start.Format("2006-01-02")
+}
+
+// getGamesToday returns every game starting within the current sports day
+// of the `tz` query parameter (IANA name, default UTC), grouped by league.
+// The day rolls over at SPORTS_DAY_ROLLOVER_HOUR local time.
+func (a *App) getGamesToday(c *fiber.Ctx) error {
+	tz := c.Query("tz", "UTC")
+	loc, err := time.LoadLocation(tz)
+	if err != nil {
+		return c.Status(fiber.StatusBadRequest).JSON(ErrorResponse{
+			Status: "error", Error: "invalid tz: expected an IANA timezone name like America/New_York",
+		})
+	}
+
+	start, end, date := localDayWindow(time.Now(), loc, a.dayRolloverHour)
+
+	cacheKey := CacheKeySportsTodayPrefix + tz + ":" + date
+	var resp TodayGamesResponse
+	if GetCache(a.rdb, cacheKey, &resp) {
+		c.Set("X-Cache", "HIT")
+		return c.JSON(resp)
+	}
+
+	rows, err := a.db.Query(c.Context(), `
+		SELECT id, league, COALESCE(sport, ''), external_game_id, COALESCE(link, ''),
+			home_team_name, COALESCE(home_team_logo, ''), COALESCE(home_team_score::text, ''), COALESCE(home_team_code, ''),
+			away_team_name, COALESCE(away_team_logo, ''), COALESCE(away_team_score::text, ''), COALESCE(away_team_code, ''),
+			start_time, COALESCE(short_detail, ''), state,
+			COALESCE(status_short, ''), COALESCE(status_long, ''),
+			COALESCE(timer, ''), COALESCE(venue, ''), COALESCE(season, ''),
+			COALESCE(venue_city, ''), COALESCE(venue_state, ''), COALESCE(series_summary, ''),
+			COALESCE((SELECT tl.sport FROM tracked_leagues tl WHERE tl.name = games.league), '')
+		FROM games
+		WHERE start_time >= $1 AND start_time < $2
+		ORDER BY league ASC, start_time ASC`, start, end)
+	if err != nil {
+		log.Printf("[Sports] getGamesToday query failed: %v", err)
+		return c.Status(fiber.StatusInternalServerError).JSON(ErrorResponse{
+			Status: "error", Error: "failed to query games",
+		})
+	}
+	defer rows.Close()
+
+	resp = TodayGamesResponse{Date: date, Timezone: tz, Leagues: make([]LeagueGames, 0)}
+	for rows.Next() {
+		var g Game
+		if err := rows.Scan(
+			&g.ID, &g.League, &g.Sport, &g.ExternalGameID, &g.Link,
+			&g.HomeTeamName, &g.HomeTeamLogo, &g.HomeTeamScore, &g.HomeTeamCode,
+			&g.AwayTeamName, &g.AwayTeamLogo, &g.AwayTeamScore, &g.AwayTeamCode,
+			&g.StartTime, &g.ShortDetail, &g.State,
+			&g.StatusShort, &g.StatusLong, &g.Timer, &g.Venue, &g.Season,
+			&g.VenueCity, &g.VenueState,
+			&g.SeriesSummary, &g.SportGroup,
+		); err != nil {
+			log.Printf("[Sports] Row scan failed: %v", err)
+			continue
+		}
+		// Rows arrive ordered by league, so a new group starts whenever
+		// the league changes.
+		if n := len(resp.Leagues); n == 0 || resp.Leagues[n-1].League != g.League {
+			resp.Leagues = append(resp.Leagues, LeagueGames{League: g.League, Games: make([]Game, 0)})
+		}
+		last := &resp.Leagues[len(resp.Leagues)-1]
+		last.Games = append(last.Games, g)
+	}
+
+	SetCache(a.rdb, cacheKey, resp, SportsCacheTTL)
+	return c.JSON(resp)
+}
+
 // =============================================================================
 // Config Parsing Helpers
 // =============================================================================
@@ -3,6 +3,7 @@ package main
 import (
 	"encoding/json"
 	"testing"
+	"time"
 )
 
 func TestExtractLeaguesFromConfig(t *testing.T) {
@@ -152,3 +153,109 @@ func TestExtractLeaguesFromConfigJSON(t *testing.T) {
 		t.Errorf("got %d, want 2", len(got))
 	}
 }
+
+func TestLocalDayWindow(t *testing.T) {
+	ny, err := time.LoadLocation("America/New_York")
+	if err != nil {
+		t.Fatalf("load tz: %v", err)
+	}
+
+	tests := []struct {
+		name      string
+		now       time.Time
+		loc       *time.Location
+		rollover  int
+		wantStart time.Time
+		wantEnd   time.Time
+		wantDate  string
+	}{
+		{
+			name:      "utc default",
+			now:       time.Date(2026, 10, 16, 15, 0, 0, 0, time.UTC),
+			loc:       time.UTC,
+			wantStart: time.Date(2026, 10, 16, 0, 0, 0, 0, time.UTC),
+			wantEnd:   time.Date(2026, 10, 17, 0, 0, 0, 0, time.UTC),
+			wantDate:  "2026-10-16",
+		},
+		{
+			name:      "late evening in new york is still the previous utc day",
+			now:       time.Date(2026, 10, 17, 2, 30, 0, 0, time.UTC),
+			loc:       ny,
+			wantStart: time.Date(2026, 10, 16, 4, 0, 0, 0, time.UTC),
+			wantEnd:   time.Date(2026, 10, 17, 4, 0, 0, 0, time.UTC),
+			wantDate:  "2026-10-16",
+		},
+		{
+			name:      "dst fall back day is 25 hours",
+			now:       time.Date(2026, 11, 1, 12, 0, 0, 0, time.UTC),
+			loc:       ny,
+			wantStart: time.Date(2026, 11, 1, 4, 0, 0, 0, time.UTC),
+			wantEnd:   time.Date(2026, 11, 2, 5, 0, 0, 0, time.UTC),
+			wantDate:  "2026-11-01",
+		},
+		{
+			name:      "after local midnight but before the rollover is the previous day",
+			now:       time.Date(2026, 10, 17, 5, 30, 0, 0, time.UTC),
+			loc:       ny,
+			rollover:  6,
+			wantStart: time.Date(2026, 10, 16, 10, 0, 0, 0, time.UTC),
+			wantEnd:   time.Date(2026, 10, 17, 10, 0, 0, 0, time.UTC),
+			wantDate:  "2026-10-16",
+		},
+		{
+			name:      "one second before the rollover",
+			now:       time.Date(2026, 10, 17, 9, 59, 59, 0, time.UTC),
+			loc:       ny,
+			rollover:  6,
+			wantStart: time.Date(2026, 10, 16, 10, 0, 0, 0, time.UTC),
+			wantEnd:   time.Date(2026, 10, 17, 10, 0, 0, 0, time.UTC),
+			wantDate:  "2026-10-16",
+		},
+		{
+			name:      "the rollover hour starts the next day",
+			now:       time.Date(2026, 10, 17, 10, 0, 0, 0, time.UTC),
+			loc:       ny,
+			rollover:  6,
+			wantStart: time.Date(2026, 10, 17, 10, 0, 0, 0, time.UTC),
+			wantEnd:   time.Date(2026, 10, 18, 10, 0, 0, 0, time.UTC),
+			wantDate:  "2026-10-17",
+		},
+		{
+			name:      "after the dst change the rollover is in standard time",
+			now:       time.Date(2026, 11, 2, 3, 0, 0, 0, time.UTC),
+			loc:       ny,
+			rollover:  6,
+			wantStart: time.Date(2026, 11, 1, 11, 0, 0, 0, time.UTC),
+			wantEnd:   time.Date(2026, 11, 2, 11, 0, 0, 0, time.UTC),
+			wantDate:  "2026-11-01",
+		},
+	}
+
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			start, end, date := localDayWindow(tc.now, tc.loc, tc.rollover)
+			if !start.Equal(tc.wantStart) || !end.Equal(tc.wantEnd) {
+				t.Errorf("window = [%v, %v), want [%v, %v)", start, end, tc.wantStart, tc.wantEnd)
+			}
+			if date != tc.wantDate {
+				t.Errorf("date = %q, want %q", date, tc.wantDate)
+			}
+		})
+	}
+}
+
+func TestParseDayRolloverHour(t *testing.T) {
+	tests := map[string]int{
+		"":    0,
+		" 6 ": 6,
+		"23":  23,
+		"24":  0,
+		"-1":  0,
+		"6am": 0,
+	}
+	for raw, want := range tests {
+		if got := parseDayRolloverHour(raw); got != want {
+			t.Errorf("parseDayRolloverHour(%q) = %d, want %d", raw, got, want)
+		}
+	}
+}
@@ -0,0 +1,2 @@
+ALTER TABLE games DROP COLUMN IF EXISTS venue_city;
+ALTER TABLE games DROP COLUMN IF EXISTS venue_state;
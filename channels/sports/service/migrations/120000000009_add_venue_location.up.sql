@@ -0,0 +1,6 @@
+-- Venue location for game cards. The venue name already lives in `venue`;
+-- these add where it is. Both nullable — api-sports omits the venue node
+-- for TBD / neutral-site games and only some sports report a state.
+
+ALTER TABLE games ADD COLUMN IF NOT EXISTS venue_city TEXT;
+ALTER TABLE games ADD COLUMN IF NOT EXISTS venue_state TEXT;
@@ -0,0 +1,4 @@
+-- dead_letter is shared with the other ingestion services, so rolling back
+-- only removes this service's rows. Drop the table by hand once every
+-- service has rolled back past its dead_letter migration.
+DELETE FROM dead_letter WHERE service = 'sports';
@@ -0,0 +1,26 @@
+-- Dead-letter queue for upstream records the ingestion services skip for a
+-- recoverable reason (malformed game, article without a guid/title, bogus
+-- symbol). Shared by sports, rss and finance — each service ships this same
+-- idempotent migration and tags its rows with `service`, so whichever runs
+-- first creates the table.
+--
+-- One row per (service, source, reason): a repeat bumps `occurrences` and
+-- replaces `raw_payload` with the latest sample, so a feed that varies its
+-- bad payloads can't grow the table. Each service prunes its rows once
+-- `last_seen_at` is old enough.
+
+CREATE TABLE IF NOT EXISTS dead_letter (
+    id           BIGSERIAL PRIMARY KEY,
+    service      TEXT NOT NULL,
+    source       TEXT NOT NULL,
+    reason       TEXT NOT NULL,
+    raw_payload  TEXT NOT NULL,
+    occurrences  INTEGER NOT NULL DEFAULT 1,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS dead_letter_service_source_reason_idx
+    ON dead_letter (service, source, reason);
+CREATE INDEX IF NOT EXISTS dead_letter_service_last_seen_idx
+    ON dead_letter (service, last_seen_at DESC);
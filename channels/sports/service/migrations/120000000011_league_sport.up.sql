@@ -0,0 +1,17 @@
+-- Broad sport per league (basketball, football, hockey, baseball, soccer) so
+-- clients can group leagues without knowing api-sports.io host names.
+-- `sport_api` says which API serves a league; `sport` is what a person would
+-- call the game. NULL for sports that don't fold into one of those groups.
+
+ALTER TABLE tracked_leagues ADD COLUMN IF NOT EXISTS sport VARCHAR(50);
+
+-- Backfill rows that only exist in the database; leagues.json rows are
+-- rewritten on the next startup seed anyway.
+UPDATE tracked_leagues SET sport = CASE sport_api
+    WHEN 'basketball' THEN 'basketball'
+    WHEN 'american-football' THEN 'football'
+    WHEN 'hockey' THEN 'hockey'
+    WHEN 'baseball' THEN 'baseball'
+    WHEN 'football' THEN 'soccer'
+END
+WHERE sport IS NULL;
@@ -0,0 +1 @@
+ALTER TABLE tracked_leagues DROP COLUMN IF EXISTS priority;
@@ -0,0 +1,3 @@
+-- Poll order within each cycle: higher priority leagues are polled (and
+-- draw on the shared rate-limit budget) first. Ties fall back to name.
+ALTER TABLE tracked_leagues ADD COLUMN IF NOT EXISTS priority INTEGER NOT NULL DEFAULT 0;
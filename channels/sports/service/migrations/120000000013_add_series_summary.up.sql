@@ -0,0 +1,4 @@
+-- Playoff context for game cards, e.g. "Divisional Round". NULL for
+-- regular-season games and for sports whose payloads carry no round.
+
+ALTER TABLE games ADD COLUMN IF NOT EXISTS series_summary TEXT;
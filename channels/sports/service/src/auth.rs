@@ -0,0 +1,87 @@
+//! Bearer-token guard for the operator endpoints that change state, such as
+//! `POST /reseed`. The read-only routes (`/health*`, `/config`,
+//! `/dead-letters`) stay open to the cluster; anything that writes requires
+//! `Authorization: Bearer $INGEST_API_TOKEN`. With the variable unset those
+//! endpoints answer 503 rather than running unauthenticated.
+//!
+//! Copied into each Rust service (`finance`, `sports`, `rss`) alongside
+//! `init.rs`, for the same module-isolation reason.
+
+use axum::{
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    Json,
+};
+use serde_json::{json, Value};
+
+/// Environment variable holding the shared operator token.
+pub const API_TOKEN_ENV: &str = "INGEST_API_TOKEN";
+
+/// Rejection returned by [`require_api_token`], ready to hand back from a
+/// handler.
+pub type AuthRejection = (StatusCode, Json<Value>);
+
+/// Check the request's bearer token against `INGEST_API_TOKEN`.
+pub fn require_api_token(headers: &HeaderMap) -> Result<(), AuthRejection> {
+    let expected = std::env::var(API_TOKEN_ENV).ok();
+    check_bearer(headers, expected.as_deref())
+}
+
+/// Check the request's bearer token against `expected`. `None` or a blank
+/// token means the endpoint is disabled.
+pub fn check_bearer(headers: &HeaderMap, expected: Option<&str>) -> Result<(), AuthRejection> {
+    let Some(expected) = expected.map(str::trim).filter(|t| !t.is_empty()) else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error": format!("{API_TOKEN_ENV} is not set; endpoint disabled")})),
+        ));
+    };
+    let provided = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({"error": "missing or invalid bearer token"})),
+        )),
+    }
+}
+
+/// Compare without short-circuiting on the first differing byte, so response
+/// timing doesn't reveal how much of a guess was right.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {token}")).unwrap());
+        headers
+    }
+
+    #[test]
+    fn accepts_matching_token() {
+        assert!(check_bearer(&bearer("s3cret"), Some("s3cret")).is_ok());
+    }
+
+    #[test]
+    fn rejects_missing_or_wrong_token() {
+        let err = check_bearer(&HeaderMap::new(), Some("s3cret")).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+        let err = check_bearer(&bearer("s3cre"), Some("s3cret")).unwrap_err();
+        assert_eq!(err.0, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn disabled_without_configured_token() {
+        assert_eq!(check_bearer(&bearer(""), None).unwrap_err().0, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(check_bearer(&bearer(""), Some("  ")).unwrap_err().0, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
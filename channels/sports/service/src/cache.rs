@@ -0,0 +1,149 @@
+//! Short-lived response cache for the read endpoints (`/games/live`,
+//! `/games/team`), so a burst of clients around a big game costs one query
+//! per key per TTL instead of one per request.
+//!
+//! Lookups for the same key are coalesced: the first caller to find an
+//! entry missing or expired runs the query while the others wait on that
+//! entry's lock, then read what it stored.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex;
+
+/// Keys kept before idle entries are dropped. `/games/team` is keyed by the
+/// searched name, so the key space is otherwise unbounded.
+pub const MAX_CACHED_KEYS: usize = 256;
+
+/// Whether a response came from the cache, sent as the `X-Cache` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+impl CacheStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hit => "HIT",
+            Self::Miss => "MISS",
+        }
+    }
+}
+
+type Slot<T> = Arc<Mutex<Option<(Instant, T)>>>;
+
+pub struct ResponseCache<T> {
+    slots: std::sync::Mutex<HashMap<String, Slot<T>>>,
+}
+
+impl<T: Clone> Default for ResponseCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> ResponseCache<T> {
+    pub fn new() -> Self {
+        Self { slots: std::sync::Mutex::new(HashMap::new()) }
+    }
+
+    /// The value stored under `key` if it is younger than `ttl`, otherwise
+    /// the result of `fetch`, which is stored on success. Errors are not
+    /// cached. A zero `ttl` bypasses the cache entirely.
+    pub async fn get_or_fetch<E, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        fetch: F,
+    ) -> Result<(T, CacheStatus), E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if ttl.is_zero() {
+            return fetch().await.map(|value| (value, CacheStatus::Miss));
+        }
+
+        let slot = self.slot(key);
+        let mut entry = slot.lock().await;
+        if let Some((stored_at, value)) = entry.as_ref()
+            && stored_at.elapsed() < ttl
+        {
+            return Ok((value.clone(), CacheStatus::Hit));
+        }
+        let value = fetch().await?;
+        *entry = Some((Instant::now(), value.clone()));
+        Ok((value, CacheStatus::Miss))
+    }
+
+    fn slot(&self, key: &str) -> Slot<T> {
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+        if !slots.contains_key(key) && slots.len() >= MAX_CACHED_KEYS {
+            // Keep only entries a request is currently waiting on.
+            slots.retain(|_, slot| Arc::strong_count(slot) > 1);
+        }
+        slots.entry(key.to_string()).or_default().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn second_lookup_within_ttl_is_a_hit() {
+        let cache = ResponseCache::new();
+        let ttl = Duration::from_secs(60);
+
+        let first = cache.get_or_fetch("k", ttl, || async { Ok::<_, ()>(1) }).await;
+        let second = cache.get_or_fetch("k", ttl, || async { Ok::<_, ()>(2) }).await;
+        let other = cache.get_or_fetch("other", ttl, || async { Ok::<_, ()>(3) }).await;
+
+        assert_eq!(first, Ok((1, CacheStatus::Miss)));
+        assert_eq!(second, Ok((1, CacheStatus::Hit)));
+        assert_eq!(other, Ok((3, CacheStatus::Miss)));
+    }
+
+    #[tokio::test]
+    async fn errors_and_zero_ttl_are_not_cached() {
+        let cache = ResponseCache::new();
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(cache.get_or_fetch("k", ttl, || async { Err::<i32, _>("db down") }).await, Err("db down"));
+        assert_eq!(cache.get_or_fetch("k", ttl, || async { Ok::<_, &str>(1) }).await, Ok((1, CacheStatus::Miss)));
+
+        let off = Duration::ZERO;
+        assert_eq!(cache.get_or_fetch("z", off, || async { Ok::<_, ()>(1) }).await, Ok((1, CacheStatus::Miss)));
+        assert_eq!(cache.get_or_fetch("z", off, || async { Ok::<_, ()>(2) }).await, Ok((2, CacheStatus::Miss)));
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_share_one_fetch() {
+        let cache = Arc::new(ResponseCache::new());
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let lookups = (0..8).map(|_| {
+            let cache = cache.clone();
+            let fetches = fetches.clone();
+            tokio::spawn(async move {
+                cache
+                    .get_or_fetch("live", Duration::from_secs(10), || async move {
+                        fetches.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        Ok::<_, ()>(42)
+                    })
+                    .await
+            })
+        });
+        for lookup in lookups.collect::<Vec<_>>() {
+            assert_eq!(lookup.await.unwrap().map(|(v, _)| v), Ok(42));
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+}
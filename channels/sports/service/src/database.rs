@@ -1,10 +1,12 @@
-use std::{env, time::Duration, sync::Arc};
+use std::{collections::HashMap, env, time::Duration, sync::Arc};
 use anyhow::{Context, Result};
 use sqlx::postgres::PgPoolOptions;
 pub use sqlx::PgPool;
 use sqlx::{FromRow, query, query_as};
-use chrono::Utc;
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use crate::retry::with_db_retry;
+use crate::text::{truncate_bytes_safe, truncate_chars};
+use serde::{Deserialize, Serialize};
 
 /// Build the sqlx migrator for this service.
 ///
@@ -42,28 +44,17 @@ fn migrator() -> sqlx::migrate::Migrator {
 pub const SPORTS_MIGRATION_MIN: i64 = 120_000_000_000;
 pub const SPORTS_MIGRATION_MAX: i64 = 129_999_999_999;
 
-pub async fn initialize_pool() -> Result<PgPool> {
-    let pool_options = PgPoolOptions::new()
-        // Pool sizing rationale: sports runs parallel per-league polls and
-        // each one can spawn several concurrent upserts. Ten connections
-        // was producing occasional `acquire_timeout` pressure when several
-        // leagues completed their fetch simultaneously; 20 gives headroom
-        // without straining the Postgres connection budget.
-        .max_connections(20)
-        // Keep one warm connection so the first query after an idle period
-        // doesn't eat the TLS/auth handshake latency.
-        .min_connections(1)
-        .acquire_timeout(Duration::from_secs(10))
-        .idle_timeout(Duration::from_secs(30));
-
-    let database_url = if let Ok(url) = env::var("DATABASE_URL") {
+/// Connection URL from `DATABASE_URL`, or assembled from the `DB_*`
+/// variables when that isn't set.
+pub fn database_url() -> Result<String> {
+    if let Ok(url) = env::var("DATABASE_URL") {
         let mut url = url.trim().trim_matches('"').trim_matches('\'').to_string();
         if url.starts_with("postgres:") && !url.starts_with("postgres://") {
             url = url.replacen("postgres:", "postgres://", 1);
         } else if url.starts_with("postgresql:") && !url.starts_with("postgresql://") {
             url = url.replacen("postgresql:", "postgresql://", 1);
         }
-        url
+        Ok(url)
     } else {
         let get_env_var = |key: &str| -> Result<String> {
             env::var(key).with_context(|| format!("Missing environment variable: {}", key))
@@ -82,9 +73,40 @@ pub async fn initialize_pool() -> Result<PgPool> {
         // should see a connect failure, not magical rewriting.
         let port: u16 = port_str.parse().context("DB_PORT must be a valid u16 integer")?;
 
-        format!("postgres://{}:{}@{}:{}/{}", user, password, raw_host, port, database)
-    };
+        Ok(format!("postgres://{}:{}@{}:{}/{}", user, password, raw_host, port, database))
+    }
+}
+
+/// Whether `err` is the pool giving up after
+/// [`db_acquire_timeout`](crate::db_acquire_timeout) with every connection
+/// still checked out.
+pub fn is_pool_timeout(err: &anyhow::Error) -> bool {
+    matches!(err.downcast_ref::<sqlx::Error>(), Some(sqlx::Error::PoolTimedOut))
+}
+
+pub async fn initialize_pool() -> Result<PgPool> {
+    let size = crate::db_pool_size();
+    let pool_options = PgPoolOptions::new()
+        // Pool sizing rationale: sports runs parallel per-league polls and
+        // each one can spawn several concurrent upserts. Ten connections
+        // was producing occasional `acquire_timeout` pressure when several
+        // leagues completed their fetch simultaneously; the default of 20
+        // gives headroom without straining the Postgres connection budget.
+        .max_connections(size.max_connections)
+        // By default keep one warm connection so the first query after an
+        // idle period doesn't eat the TLS/auth handshake latency.
+        .min_connections(size.min_connections)
+        // Bounded so an exhausted pool fails the query with `PoolTimedOut`
+        // (see `is_pool_timeout`) instead of parking the poll loop.
+        .acquire_timeout(crate::db_acquire_timeout())
+        .idle_timeout(Duration::from_secs(30));
+
+    let database_url = database_url()?;
 
+    eprintln!(
+        "[DB] Pool: max {} connections, min {}",
+        size.max_connections, size.min_connections
+    );
     eprintln!("[DB] Connecting to database...");
     let pool = tokio::time::timeout(
         Duration::from_secs(15),
@@ -174,6 +196,37 @@ pub struct LeagueConfig {
     pub season_format: Option<String>,
     #[serde(default)]
     pub offseason_months: Option<Vec<i32>>,
+    /// Broad sport for grouping. Derived from `sport_api` when absent.
+    #[serde(default)]
+    pub sport: Option<String>,
+    /// Higher values are polled first each cycle.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl LeagueConfig {
+    /// `sport` as configured, else [`sport_for_api`](crate::sport_for_api).
+    pub fn resolved_sport(&self) -> Option<String> {
+        self.sport
+            .clone()
+            .or_else(|| crate::sport_for_api(&self.sport_api).map(str::to_string))
+    }
+
+    /// Whether `row` already holds exactly what seeding this config writes.
+    pub fn matches(&self, row: &TrackedLeague) -> bool {
+        self.name == row.name
+            && self.sport_api == row.sport_api
+            && self.api_host == row.api_host
+            && self.league_id == row.league_id
+            && self.category == row.category
+            && self.country == row.country
+            && self.logo_url == row.logo_url
+            && self.season == row.season
+            && self.season_format == row.season_format
+            && self.offseason_months == row.offseason_months
+            && self.resolved_sport() == row.sport
+            && self.priority == row.priority
+    }
 }
 
 /// Stored league row read back from the database.
@@ -189,6 +242,8 @@ pub struct TrackedLeague {
     pub season: Option<String>,
     pub season_format: Option<String>,
     pub offseason_months: Option<Vec<i32>>,
+    pub sport: Option<String>,
+    pub priority: i32,
 }
 
 // =============================================================================
@@ -211,6 +266,13 @@ pub struct CleanedData {
     pub timer: Option<String>,
     pub venue: Option<String>,
     pub season: Option<String>,
+    /// Location of `venue` (which holds the venue name). Absent for TBD
+    /// and some neutral-site games.
+    pub venue_city: Option<String>,
+    pub venue_state: Option<String>,
+    /// Playoff context, e.g. "Divisional Round". `None` outside the
+    /// postseason and for sports whose payloads carry no round.
+    pub series_summary: Option<String>,
 }
 
 #[derive(Debug)]
@@ -218,6 +280,7 @@ pub struct Team {
     pub name: String,
     pub logo: Option<String>,
     pub score: Option<i32>,
+    /// Abbreviation, e.g. "KC".
     pub code: Option<String>,
 }
 
@@ -227,9 +290,10 @@ pub struct Team {
 
 pub async fn get_tracked_leagues(pool: Arc<PgPool>) -> Vec<TrackedLeague> {
     let statement = "
-        SELECT name, sport_api, api_host, league_id, category, country, logo_url, season, season_format, offseason_months
+        SELECT name, sport_api, api_host, league_id, category, country, logo_url, season, season_format, offseason_months, sport, priority
         FROM tracked_leagues
         WHERE is_enabled = TRUE
+        ORDER BY priority DESC, name
     ";
     let res: Result<Vec<TrackedLeague>, sqlx::Error> = async {
         let mut connection = pool.acquire().await?;
@@ -246,10 +310,18 @@ pub async fn get_tracked_leagues(pool: Arc<PgPool>) -> Vec<TrackedLeague> {
     }
 }
 
-pub async fn seed_tracked_leagues(pool: Arc<PgPool>, leagues: Vec<LeagueConfig>) -> Result<()> {
+/// Upsert every config league in one transaction: either all of them land
+/// or, if any statement fails (or the process dies first), none do. Returns
+/// how many were written — 0 when every league already matched its row and
+/// the transaction was skipped.
+pub async fn seed_tracked_leagues(pool: Arc<PgPool>, leagues: Vec<LeagueConfig>) -> Result<usize> {
+    if leagues_already_seeded(&pool, &leagues).await? {
+        return Ok(0);
+    }
+
     let statement = "
-        INSERT INTO tracked_leagues (name, sport_api, api_host, league_id, category, country, logo_url, season, season_format, offseason_months)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        INSERT INTO tracked_leagues (name, sport_api, api_host, league_id, category, country, logo_url, season, season_format, offseason_months, sport, priority)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
         ON CONFLICT (name) DO UPDATE SET
             sport_api = EXCLUDED.sport_api,
             api_host = EXCLUDED.api_host,
@@ -259,11 +331,63 @@ pub async fn seed_tracked_leagues(pool: Arc<PgPool>, leagues: Vec<LeagueConfig>)
             logo_url = EXCLUDED.logo_url,
             season = EXCLUDED.season,
             season_format = EXCLUDED.season_format,
-            offseason_months = EXCLUDED.offseason_months
+            offseason_months = EXCLUDED.offseason_months,
+            sport = EXCLUDED.sport,
+            priority = EXCLUDED.priority
+    ";
+    let mut tx = pool.begin().await?;
+    for league in &leagues {
+        query(statement)
+            .bind(&league.name)
+            .bind(&league.sport_api)
+            .bind(&league.api_host)
+            .bind(league.league_id)
+            .bind(&league.category)
+            .bind(&league.country)
+            .bind(&league.logo_url)
+            .bind(&league.season)
+            .bind(&league.season_format)
+            .bind(&league.offseason_months)
+            .bind(league.resolved_sport())
+            .bind(league.priority)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to seed league {}", league.name))?;
+    }
+    tx.commit().await?;
+    Ok(leagues.len())
+}
+
+/// Whether each config league already has a row identical to what the
+/// upsert would write.
+async fn leagues_already_seeded(pool: &PgPool, leagues: &[LeagueConfig]) -> Result<bool> {
+    let names: Vec<&str> = leagues.iter().map(|l| l.name.as_str()).collect();
+    let stored: Vec<TrackedLeague> = query_as("
+        SELECT name, sport_api, api_host, league_id, category, country, logo_url, season, season_format, offseason_months, sport, priority
+        FROM tracked_leagues
+        WHERE name = ANY($1)
+    ")
+    .bind(&names)
+    .fetch_all(pool)
+    .await?;
+
+    let stored: HashMap<&str, &TrackedLeague> = stored.iter().map(|row| (row.name.as_str(), row)).collect();
+    Ok(leagues.iter().all(|league| stored.get(league.name.as_str()).is_some_and(|row| league.matches(row))))
+}
+
+
+/// Insert config leagues that aren't tracked yet, leaving existing rows
+/// (including disabled ones) untouched. Returns how many were added.
+pub async fn reseed_tracked_leagues(pool: &Arc<PgPool>, leagues: &[LeagueConfig]) -> Result<u64> {
+    let statement = "
+        INSERT INTO tracked_leagues (name, sport_api, api_host, league_id, category, country, logo_url, season, season_format, offseason_months, sport, priority)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ON CONFLICT (name) DO NOTHING
     ";
     let mut connection = pool.acquire().await?;
+    let mut added = 0;
     for league in leagues {
-        query(statement)
+        added += query(statement)
             .bind(&league.name)
             .bind(&league.sport_api)
             .bind(&league.api_host)
@@ -274,10 +398,13 @@ pub async fn seed_tracked_leagues(pool: Arc<PgPool>, leagues: Vec<LeagueConfig>)
             .bind(&league.season)
             .bind(&league.season_format)
             .bind(&league.offseason_months)
+            .bind(league.resolved_sport())
+            .bind(league.priority)
             .execute(&mut *connection)
-            .await?;
+            .await?
+            .rows_affected();
     }
-    Ok(())
+    Ok(added)
 }
 
 /// Disable any tracked_leagues rows not present in the config file.
@@ -322,23 +449,18 @@ pub async fn record_poll_success(pool: &Arc<PgPool>, league_name: &str) {
     }
 }
 
+/// Longest `last_poll_error` kept, in bytes.
+const MAX_POLL_ERROR_BYTES: usize = 1024;
+
 /// Record a failed poll. Updates `last_polled_at` and `last_poll_error`,
 /// but does NOT touch `last_poll_success_at` — that timestamp must only
 /// move forward on actual successes so staleness detection works.
 pub async fn record_poll_error(pool: &Arc<PgPool>, league_name: &str, err_msg: &str) {
     // Truncate excessively long error messages to keep the row small.
     // 1 KiB is plenty to see what went wrong; anything longer is noise.
-    // Walk char boundaries so we never split a multi-byte UTF-8 sequence —
-    // panicking inside error-handling code would lose the original failure.
-    let truncated: &str = if err_msg.len() > 1024 {
-        let mut end = 1024;
-        while end > 0 && !err_msg.is_char_boundary(end) {
-            end -= 1;
-        }
-        &err_msg[..end]
-    } else {
-        err_msg
-    };
+    // The cut must respect char boundaries — panicking inside
+    // error-handling code would lose the original failure.
+    let truncated = truncate_bytes_safe(err_msg, MAX_POLL_ERROR_BYTES);
 
     let res = async {
         let mut conn = pool.acquire().await?;
@@ -361,7 +483,8 @@ pub async fn record_poll_error(pool: &Arc<PgPool>, league_name: &str, err_msg: &
 
 /// Return distinct league names that have live games from yesterday (UTC).
 /// Used by poll_live to decide whether to also query yesterday's date.
-pub async fn get_live_yesterday_leagues(pool: &Arc<PgPool>) -> Vec<String> {
+/// `live_states` is the configured set of states that count as live.
+pub async fn get_live_yesterday_leagues(pool: &Arc<PgPool>, live_states: &[String]) -> Vec<String> {
     let today_start = Utc::now()
         .date_naive()
         .and_hms_opt(0, 0, 0)
@@ -371,9 +494,10 @@ pub async fn get_live_yesterday_leagues(pool: &Arc<PgPool>) -> Vec<String> {
     let result: Result<Vec<(String,)>, sqlx::Error> = async {
         let mut conn = pool.acquire().await?;
         let rows = sqlx::query_as(
-            "SELECT DISTINCT league FROM games WHERE state = 'in' AND start_time < $1"
+            "SELECT DISTINCT league FROM games WHERE state = ANY($2) AND start_time < $1"
         )
         .bind(today_utc)
+        .bind(live_states)
         .fetch_all(&mut *conn)
         .await?;
         Ok(rows)
@@ -388,23 +512,26 @@ pub async fn get_live_yesterday_leagues(pool: &Arc<PgPool>) -> Vec<String> {
     }
 }
 
-/// Delete stale games using per-state thresholds.
+/// Delete stale games using per-state thresholds. Returns rows deleted.
 ///
-/// - `final` / `postponed`: 12 hours past `start_time` — they're done.
+/// - `final` / `postponed`: `final_retention_hours` past `start_time` —
+///   they're done (`SPORTS_FINAL_RETENTION_HOURS`, 12 by default).
 /// - `pre`:  7 days past `start_time` — survives short polling outages.
 ///           A `pre` row this old means the API stopped returning the fixture
 ///           entirely; safe to prune.
-/// - `in`:   24 hours since `updated_at`. A legitimately long game (MLB
-///           extras, NFL weather delay, F1 red-flag) can exceed 4h, so we
-///           prune only after a full day of no updates.
-pub async fn cleanup_old_games(pool: &Arc<PgPool>) -> Result<u64> {
+/// - `in`:   24 hours since `updated_at`, i.e. since the row last changed
+///           (`upsert_game` skips identical writes). A legitimately long
+///           game (MLB extras, NFL weather delay, F1 red-flag) can exceed
+///           4h, so we prune only after a full day of no updates.
+pub async fn cleanup_old_games(pool: &Arc<PgPool>, final_retention_hours: i32) -> Result<u64> {
     let mut connection = pool.acquire().await?;
     let result = query(
         "DELETE FROM games WHERE
-            (state IN ('final', 'postponed') AND start_time < NOW() - INTERVAL '12 hours')
+            (state IN ('final', 'postponed') AND start_time < NOW() - make_interval(hours => $1))
             OR (state = 'pre' AND start_time < NOW() - INTERVAL '7 days')
             OR (state = 'in' AND updated_at < NOW() - INTERVAL '24 hours')"
     )
+    .bind(final_retention_hours)
     .execute(&mut *connection)
     .await?;
     Ok(result.rows_affected())
@@ -414,16 +541,39 @@ pub async fn cleanup_old_games(pool: &Arc<PgPool>) -> Result<u64> {
 // Game upsert
 // =============================================================================
 
-pub async fn upsert_game(pool: Arc<PgPool>, game: CleanedData) -> Result<()> {
+/// What `upsert_game` did with a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    Inserted,
+    Updated,
+    /// The row already held exactly this data; nothing was written and
+    /// `updated_at` kept its value.
+    Unchanged,
+}
+
+/// Team names live in `VARCHAR(100)` columns. Postgres counts characters
+/// there, so an over-long name from upstream is cut to fit rather than
+/// failing the whole row.
+pub const MAX_TEAM_NAME_CHARS: usize = 100;
+
+/// Width of the `VARCHAR(10)` team code columns.
+pub const MAX_TEAM_CODE_CHARS: usize = 10;
+
+/// Insert or update a game. The update only runs when some column would
+/// actually change, so `updated_at` means "last changed" rather than "last
+/// polled". Polls that change nothing also stop churning the table and
+/// firing CDC events. Deadlocks and dropped connections are retried.
+pub async fn upsert_game(pool: Arc<PgPool>, game: CleanedData) -> Result<UpsertOutcome> {
     let statement = "
         INSERT INTO games (
             league, sport, external_game_id, link,
             home_team_name, home_team_logo, home_team_score, home_team_code,
             away_team_name, away_team_logo, away_team_score, away_team_code,
             start_time, short_detail, state,
-            status_short, status_long, timer, venue, season
+            status_short, status_long, timer, venue, season,
+            venue_city, venue_state, series_summary
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
         ON CONFLICT (league, external_game_id)
         DO UPDATE SET
             sport = EXCLUDED.sport,
@@ -444,33 +594,217 @@ pub async fn upsert_game(pool: Arc<PgPool>, game: CleanedData) -> Result<()> {
             timer = EXCLUDED.timer,
             venue = EXCLUDED.venue,
             season = EXCLUDED.season,
-            updated_at = CURRENT_TIMESTAMP;
+            venue_city = EXCLUDED.venue_city,
+            venue_state = EXCLUDED.venue_state,
+            series_summary = EXCLUDED.series_summary,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE (
+            games.sport, games.link,
+            games.home_team_name, games.home_team_logo, games.home_team_score, games.home_team_code,
+            games.away_team_name, games.away_team_logo, games.away_team_score, games.away_team_code,
+            games.start_time, games.short_detail, games.state,
+            games.status_short, games.status_long, games.timer, games.venue, games.season,
+            games.venue_city, games.venue_state, games.series_summary
+        ) IS DISTINCT FROM (
+            EXCLUDED.sport, EXCLUDED.link,
+            EXCLUDED.home_team_name, EXCLUDED.home_team_logo, EXCLUDED.home_team_score, EXCLUDED.home_team_code,
+            EXCLUDED.away_team_name, EXCLUDED.away_team_logo, EXCLUDED.away_team_score, EXCLUDED.away_team_code,
+            EXCLUDED.start_time, EXCLUDED.short_detail, EXCLUDED.state,
+            EXCLUDED.status_short, EXCLUDED.status_long, EXCLUDED.timer, EXCLUDED.venue, EXCLUDED.season,
+            EXCLUDED.venue_city, EXCLUDED.venue_state, EXCLUDED.series_summary
+        )
+        RETURNING (xmax = 0) AS inserted;
     ";
-    let mut connection = pool.acquire().await?;
-    query(statement)
-        .bind(&game.league)
-        .bind(&game.sport)
-        .bind(game.external_game_id)
-        .bind(game.link)
-        .bind(game.home_team.name)
-        .bind(game.home_team.logo)
-        .bind(game.home_team.score)
-        .bind(game.home_team.code)
-        .bind(game.away_team.name)
-        .bind(game.away_team.logo)
-        .bind(game.away_team.score)
-        .bind(game.away_team.code)
-        .bind(game.start_time)
-        .bind(game.short_detail)
-        .bind(game.state)
-        .bind(game.status_short)
-        .bind(game.status_long)
-        .bind(game.timer)
-        .bind(game.venue)
-        .bind(game.season)
-        .execute(&mut *connection)
-        .await?;
-    Ok(())
+    // No row back means the WHERE above skipped an identical update.
+    // `xmax = 0` is true only for a freshly inserted tuple.
+    let (pool, game) = (&pool, &game);
+    let inserted: Option<(bool,)> = with_db_retry("upsert_game", || async move {
+        let mut connection = pool.acquire().await?;
+        query_as(statement)
+            .bind(&game.league)
+            .bind(&game.sport)
+            .bind(&game.external_game_id)
+            .bind(&game.link)
+            .bind(&game.home_team.name)
+            .bind(&game.home_team.logo)
+            .bind(game.home_team.score)
+            .bind(&game.home_team.code)
+            .bind(&game.away_team.name)
+            .bind(&game.away_team.logo)
+            .bind(game.away_team.score)
+            .bind(&game.away_team.code)
+            .bind(game.start_time)
+            .bind(&game.short_detail)
+            .bind(&game.state)
+            .bind(&game.status_short)
+            .bind(&game.status_long)
+            .bind(&game.timer)
+            .bind(&game.venue)
+            .bind(&game.season)
+            .bind(&game.venue_city)
+            .bind(&game.venue_state)
+            .bind(&game.series_summary)
+            .fetch_optional(&mut *connection)
+            .await
+    }).await?;
+    Ok(match inserted {
+        Some((true,)) => UpsertOutcome::Inserted,
+        Some((false,)) => UpsertOutcome::Updated,
+        None => UpsertOutcome::Unchanged,
+    })
+}
+
+// =============================================================================
+// Game export
+// =============================================================================
+
+/// A `games` row as served by the CSV / iCalendar exports.
+#[derive(Debug, Serialize, FromRow)]
+pub struct ExportGame {
+    pub league: String,
+    pub sport: String,
+    pub external_game_id: String,
+    pub link: Option<String>,
+    pub home_team_name: String,
+    pub home_team_score: Option<i32>,
+    pub away_team_name: String,
+    pub away_team_score: Option<i32>,
+    pub start_time: DateTime<Utc>,
+    pub state: String,
+    pub venue: Option<String>,
+    pub venue_city: Option<String>,
+    pub venue_state: Option<String>,
+}
+
+/// Games for export in start-time order, optionally limited to one league.
+/// `upcoming_only` keeps just `state = 'pre'` games.
+pub async fn get_export_games(pool: &Arc<PgPool>, league: Option<&str>, upcoming_only: bool) -> Result<Vec<ExportGame>> {
+    let mut conn = pool.acquire().await?;
+    let rows = query_as::<_, ExportGame>(
+        "SELECT league, sport, external_game_id, link,
+                home_team_name, home_team_score, away_team_name, away_team_score,
+                start_time, state, venue, venue_city, venue_state
+         FROM games
+         WHERE ($1::TEXT IS NULL OR league = $1)
+           AND (NOT $2 OR state = 'pre')
+         ORDER BY start_time ASC, id ASC"
+    )
+    .bind(league)
+    .bind(upcoming_only)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(rows)
+}
+
+// =============================================================================
+// Live games
+// =============================================================================
+
+/// An in-progress game with its current score and game clock, for
+/// `GET /games/live`.
+#[derive(Debug, Serialize, FromRow)]
+pub struct LiveGame {
+    pub league: String,
+    pub sport: String,
+    pub external_game_id: String,
+    pub link: Option<String>,
+    pub home_team_name: String,
+    pub home_team_logo: Option<String>,
+    pub home_team_score: Option<i32>,
+    pub home_team_code: Option<String>,
+    pub away_team_name: String,
+    pub away_team_logo: Option<String>,
+    pub away_team_score: Option<i32>,
+    pub away_team_code: Option<String>,
+    pub start_time: DateTime<Utc>,
+    /// Current period as api-sports reports it, e.g. `Q3`, `2H`, `P2`.
+    pub status_short: Option<String>,
+    pub status_long: Option<String>,
+    /// Game clock within the period, e.g. `07:42`; not every sport has one.
+    pub timer: Option<String>,
+    pub short_detail: Option<String>,
+    pub series_summary: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Every game whose state is one of `live_states` (the configured set that
+/// counts as live), ordered by league then kickoff.
+pub async fn get_live_games_detailed(pool: &Arc<PgPool>, live_states: &[String]) -> Result<Vec<LiveGame>> {
+    let mut conn = pool.acquire().await?;
+    let rows = query_as::<_, LiveGame>(
+        "SELECT league, sport, external_game_id, link,
+                home_team_name, home_team_logo, home_team_score,
+                home_team_code,
+                away_team_name, away_team_logo, away_team_score,
+                away_team_code,
+                start_time, status_short, status_long, timer, short_detail, series_summary, updated_at
+         FROM games
+         WHERE state = ANY($1)
+         ORDER BY league ASC, start_time ASC, id ASC"
+    )
+    .bind(live_states)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(rows)
+}
+
+// =============================================================================
+// Games by team
+// =============================================================================
+
+/// Most games [`get_games_by_team`] returns; a short name like "United"
+/// can match a lot of clubs.
+pub const MAX_TEAM_GAMES: i64 = 200;
+
+/// A game involving the searched team, for `GET /games/team`.
+#[derive(Debug, Serialize, FromRow)]
+pub struct TeamGame {
+    pub league: String,
+    pub sport: String,
+    pub external_game_id: String,
+    pub link: Option<String>,
+    pub home_team_name: String,
+    pub home_team_logo: Option<String>,
+    pub home_team_score: Option<i32>,
+    pub home_team_code: Option<String>,
+    pub away_team_name: String,
+    pub away_team_logo: Option<String>,
+    pub away_team_score: Option<i32>,
+    pub away_team_code: Option<String>,
+    pub start_time: DateTime<Utc>,
+    pub state: String,
+    pub short_detail: Option<String>,
+    pub venue: Option<String>,
+    pub series_summary: Option<String>,
+}
+
+/// Games in any league where `name` appears in the home or away team name,
+/// case-insensitively, in start-time order. The table only holds recent
+/// results and the upcoming schedule, so that is what comes back.
+///
+/// `strpos` rather than `ILIKE` so `%` and `_` in the name are literal. With
+/// `games` pruned to a few weeks of fixtures a scan is cheap, so there is no
+/// trigram index behind this.
+pub async fn get_games_by_team(pool: &Arc<PgPool>, name: &str) -> Result<Vec<TeamGame>> {
+    let mut conn = pool.acquire().await?;
+    let rows = query_as::<_, TeamGame>(
+        "SELECT league, sport, external_game_id, link,
+                home_team_name, home_team_logo, home_team_score,
+                home_team_code,
+                away_team_name, away_team_logo, away_team_score,
+                away_team_code,
+                start_time, state, short_detail, venue, series_summary
+         FROM games
+         WHERE strpos(lower(home_team_name), lower($1)) > 0
+            OR strpos(lower(away_team_name), lower($1)) > 0
+         ORDER BY start_time ASC, id ASC
+         LIMIT $2"
+    )
+    .bind(name)
+    .bind(MAX_TEAM_GAMES)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(rows)
 }
 
 // =============================================================================
@@ -523,7 +857,7 @@ pub async fn upsert_standing(pool: &Arc<PgPool>, s: StandingData) -> Result<()>
              points_against = EXCLUDED.points_against, streak = EXCLUDED.streak,
              updated_at = CURRENT_TIMESTAMP"
     )
-    .bind(&s.league).bind(&s.team_name).bind(&s.team_code).bind(&s.team_logo)
+    .bind(&s.league).bind(truncate_chars(&s.team_name, MAX_TEAM_NAME_CHARS)).bind(&s.team_code).bind(&s.team_logo)
     .bind(s.rank).bind(s.wins).bind(s.losses).bind(s.draws).bind(s.points)
     .bind(s.games_played).bind(s.goal_diff).bind(&s.description).bind(&s.form)
     .bind(&s.group_name).bind(&s.season).bind(&s.sport_api).bind(&s.pct)
@@ -558,9 +892,97 @@ pub async fn upsert_team(pool: &Arc<PgPool>, t: TeamData) -> Result<()> {
             name = EXCLUDED.name, code = EXCLUDED.code, logo = EXCLUDED.logo,
             country = EXCLUDED.country, updated_at = CURRENT_TIMESTAMP"
     )
-    .bind(&t.league).bind(t.external_id).bind(&t.name).bind(&t.code)
+    .bind(&t.league).bind(t.external_id).bind(truncate_chars(&t.name, MAX_TEAM_NAME_CHARS)).bind(&t.code)
     .bind(&t.logo).bind(&t.country).bind(&t.season)
     .execute(&mut *conn)
     .await?;
     Ok(())
 }
+
+// =============================================================================
+// Dead letters — upstream records skipped during parsing, kept for inspection
+// =============================================================================
+
+/// `dead_letter.service` value for rows written by this service.
+const DEAD_LETTER_SERVICE: &str = "sports";
+
+/// Payloads are capped so one enormous fixture can't bloat the table.
+const MAX_DEAD_LETTER_PAYLOAD_BYTES: usize = 16 * 1024;
+
+/// Dead letters not seen again for this long are pruned by the schedule
+/// poll's cleanup.
+pub const DEAD_LETTER_RETENTION_DAYS: i32 = 14;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct DeadLetter {
+    pub id: i64,
+    pub source: String,
+    pub reason: String,
+    pub raw_payload: String,
+    pub occurrences: i32,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+/// Park a record the poller couldn't use. `source` is the league it came
+/// from. A repeat of the same league and reason bumps `occurrences` and
+/// keeps the latest payload instead of adding a row. Failures are logged,
+/// never returned — losing a dead letter must not fail the poll that
+/// produced it.
+pub async fn record_dead_letter(pool: &Arc<PgPool>, source: &str, reason: &str, raw_payload: &str) {
+    let payload = truncate_bytes_safe(raw_payload, MAX_DEAD_LETTER_PAYLOAD_BYTES);
+
+    let res = async {
+        let mut conn = pool.acquire().await?;
+        query(
+            "INSERT INTO dead_letter (service, source, reason, raw_payload)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (service, source, reason) DO UPDATE SET
+                occurrences = dead_letter.occurrences + 1,
+                raw_payload = EXCLUDED.raw_payload,
+                last_seen_at = CURRENT_TIMESTAMP"
+        )
+        .bind(DEAD_LETTER_SERVICE)
+        .bind(source)
+        .bind(reason)
+        .bind(payload)
+        .execute(&mut *conn)
+        .await?;
+        Ok::<_, sqlx::Error>(())
+    }.await;
+    if let Err(e) = res {
+        log::warn!("Failed to record dead letter from {} ({}): {}", source, reason, e);
+    }
+}
+
+/// Delete this service's dead letters last seen more than `retention_days`
+/// ago. Returns how many rows went.
+pub async fn prune_dead_letters(pool: &Arc<PgPool>, retention_days: i32) -> Result<u64> {
+    let mut conn = pool.acquire().await?;
+    let result = query(
+        "DELETE FROM dead_letter
+         WHERE service = $1 AND last_seen_at < NOW() - make_interval(days => $2)"
+    )
+    .bind(DEAD_LETTER_SERVICE)
+    .bind(retention_days)
+    .execute(&mut *conn)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Most recently seen dead letters for this service, newest first.
+pub async fn get_dead_letters(pool: &Arc<PgPool>, limit: i64) -> Result<Vec<DeadLetter>> {
+    let mut conn = pool.acquire().await?;
+    let rows = query_as::<_, DeadLetter>(
+        "SELECT id, source, reason, raw_payload, occurrences, created_at, last_seen_at
+         FROM dead_letter
+         WHERE service = $1
+         ORDER BY last_seen_at DESC
+         LIMIT $2"
+    )
+    .bind(DEAD_LETTER_SERVICE)
+    .bind(limit)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(rows)
+}
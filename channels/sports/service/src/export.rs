@@ -0,0 +1,210 @@
+//! Renderers for the `/games.ics` and `/games.csv` exports.
+//!
+//! Both take rows from [`get_export_games`](crate::database::get_export_games)
+//! and return the full document as a string; the feeds are small enough
+//! (one league's fixtures at most a few hundred rows) that streaming isn't
+//! worth it.
+
+use chrono::{DateTime, Utc};
+
+use crate::database::ExportGame;
+use crate::text::truncate_bytes_safe;
+
+/// api-sports.io gives no end time, so calendar entries get a nominal
+/// length that covers most games in the tracked sports.
+const EVENT_DURATION: &str = "PT3H";
+
+/// RFC 5545 caps content lines at 75 octets, excluding the CRLF.
+const ICS_LINE_OCTETS: usize = 75;
+
+/// An iCalendar feed with one VEVENT per game, summarised as "Away @ Home".
+/// `now` stamps every event's DTSTAMP.
+pub fn games_ics(games: &[ExportGame], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    for line in ["BEGIN:VCALENDAR", "VERSION:2.0", "PRODID:-//MyScrollr//Sports//EN", "CALSCALE:GREGORIAN"] {
+        push_ics_line(&mut out, line);
+    }
+    let stamp = ics_timestamp(now);
+    for game in games {
+        push_ics_line(&mut out, "BEGIN:VEVENT");
+        push_ics_line(&mut out, &format!(
+            "UID:{}@sports.myscrollr",
+            ics_escape(&format!("{}-{}", game.league, game.external_game_id)),
+        ));
+        push_ics_line(&mut out, &format!("DTSTAMP:{stamp}"));
+        push_ics_line(&mut out, &format!("DTSTART:{}", ics_timestamp(game.start_time)));
+        push_ics_line(&mut out, &format!("DURATION:{EVENT_DURATION}"));
+        push_ics_line(&mut out, &format!(
+            "SUMMARY:{}",
+            ics_escape(&format!("{} @ {}", game.away_team_name, game.home_team_name)),
+        ));
+        if let Some(location) = location(game) {
+            push_ics_line(&mut out, &format!("LOCATION:{}", ics_escape(&location)));
+        }
+        push_ics_line(&mut out, &format!("CATEGORIES:{}", ics_escape(&game.league)));
+        if let Some(link) = game.link.as_deref().filter(|l| !l.is_empty()) {
+            push_ics_line(&mut out, &format!("URL:{link}"));
+        }
+        push_ics_line(&mut out, "END:VEVENT");
+    }
+    push_ics_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// The games as CSV with a header row. `start_time` is RFC 3339 in UTC.
+pub fn games_csv(games: &[ExportGame]) -> String {
+    let mut out = String::from(
+        "league,sport,start_time,state,away_team,away_score,home_team,home_score,venue,venue_city,venue_state,link\r\n",
+    );
+    for game in games {
+        let fields = [
+            csv_field(&game.league),
+            csv_field(&game.sport),
+            game.start_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            csv_field(&game.state),
+            csv_field(&game.away_team_name),
+            game.away_team_score.map(|s| s.to_string()).unwrap_or_default(),
+            csv_field(&game.home_team_name),
+            game.home_team_score.map(|s| s.to_string()).unwrap_or_default(),
+            csv_field(game.venue.as_deref().unwrap_or_default()),
+            csv_field(game.venue_city.as_deref().unwrap_or_default()),
+            csv_field(game.venue_state.as_deref().unwrap_or_default()),
+            csv_field(game.link.as_deref().unwrap_or_default()),
+        ];
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// "Venue, City, State", skipping whichever parts are missing.
+fn location(game: &ExportGame) -> Option<String> {
+    let parts: Vec<&str> = [&game.venue, &game.venue_city, &game.venue_state]
+        .into_iter()
+        .filter_map(|p| p.as_deref().map(str::trim).filter(|p| !p.is_empty()))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+fn ics_timestamp(t: DateTime<Utc>) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11): backslash, semicolon and comma
+/// get a backslash, line breaks become a literal `\n`.
+fn ics_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Append `line` with CRLF, folding it onto continuation lines (CRLF plus
+/// a space) so no line exceeds [`ICS_LINE_OCTETS`]. Folds only on char
+/// boundaries so multi-byte team names stay valid UTF-8.
+fn push_ics_line(out: &mut String, line: &str) {
+    let mut rest = line;
+    let mut limit = ICS_LINE_OCTETS;
+    while rest.len() > limit {
+        let head = truncate_bytes_safe(rest, limit);
+        out.push_str(head);
+        out.push_str("\r\n ");
+        rest = &rest[head.len()..];
+        // The leading space of a continuation line counts toward its length.
+        limit = ICS_LINE_OCTETS - 1;
+    }
+    out.push_str(rest);
+    out.push_str("\r\n");
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn game(away: &str, home: &str) -> ExportGame {
+        ExportGame {
+            league: "NFL".to_string(),
+            sport: "american-football".to_string(),
+            external_game_id: "1234".to_string(),
+            link: None,
+            home_team_name: home.to_string(),
+            home_team_score: None,
+            away_team_name: away.to_string(),
+            away_team_score: None,
+            start_time: Utc.with_ymd_and_hms(2026, 9, 13, 17, 0, 0).unwrap(),
+            state: "pre".to_string(),
+            venue: Some("Arrowhead Stadium".to_string()),
+            venue_city: Some("Kansas City".to_string()),
+            venue_state: None,
+        }
+    }
+
+    #[test]
+    fn test_games_ics_event() {
+        let now = Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap();
+        let ics = games_ics(&[game("Ravens", "Chiefs")], now);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nUID:NFL-1234@sports.myscrollr\r\n"));
+        assert!(ics.contains("\r\nDTSTAMP:20260901T000000Z\r\n"));
+        assert!(ics.contains("\r\nDTSTART:20260913T170000Z\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Ravens @ Chiefs\r\n"));
+        assert!(ics.contains("\r\nLOCATION:Arrowhead Stadium\\, Kansas City\r\n"));
+    }
+
+    #[test]
+    fn test_ics_escapes_team_names() {
+        assert_eq!(ics_escape(r"A;B,C\D"), r"A\;B\,C\\D");
+        assert_eq!(ics_escape("line1\r\nline2"), r"line1\nline2");
+
+        let now = Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap();
+        let ics = games_ics(&[game("Brighton & Hove, Albion", "Saint-Étienne; B")], now);
+        assert!(ics.contains("\r\nSUMMARY:Brighton & Hove\\, Albion @ Saint-Étienne\\; B\r\n"));
+    }
+
+    #[test]
+    fn test_ics_folds_long_lines() {
+        let mut out = String::new();
+        let long = format!("SUMMARY:{}", "é".repeat(60));
+        push_ics_line(&mut out, &long);
+        for line in out.split("\r\n").filter(|l| !l.is_empty()) {
+            assert!(line.len() <= ICS_LINE_OCTETS, "{} octets: {line}", line.len());
+        }
+        // Unfolding (drop CRLF + space) restores the original line.
+        assert_eq!(out.replace("\r\n ", "").trim_end(), long);
+    }
+
+    #[test]
+    fn test_games_csv() {
+        let mut final_game = game("Ravens", "Kansas City \"Chiefs\"");
+        final_game.state = "final".to_string();
+        final_game.away_team_score = Some(20);
+        final_game.home_team_score = Some(27);
+        let csv = games_csv(&[final_game]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "league,sport,start_time,state,away_team,away_score,home_team,home_score,venue,venue_city,venue_state,link");
+        assert_eq!(
+            lines[1],
+            "NFL,american-football,2026-09-13T17:00:00Z,final,Ravens,20,\"Kansas City \"\"Chiefs\"\"\",27,Arrowhead Stadium,Kansas City,,"
+        );
+    }
+}
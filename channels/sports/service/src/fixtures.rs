@@ -0,0 +1,237 @@
+//! Regression corpus of game payloads the parsers couldn't handle.
+//!
+//! With `CAPTURE_FIXTURES=true`, every game `parse_game` rejects (the same
+//! ones that go to the dead-letter table) is also written to
+//! [`FIXTURES_DIR`] as a [`Fixture`], so a new upstream shape can be
+//! reproduced with the exact payload. The directory keeps at most
+//! [`MAX_FIXTURE_FILES`], dropping the oldest first.
+//!
+//! Fixtures worth keeping are copied into this crate's `fixtures/`, where
+//! `tests/replay_fixtures.rs` feeds each one back through the parsers.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::database::{CleanedData, TrackedLeague};
+use crate::log::{info, warn};
+
+/// Where captured fixtures are written, relative to the working directory.
+pub const FIXTURES_DIR: &str = "./fixtures";
+
+/// Fixtures kept in [`FIXTURES_DIR`] before the oldest are removed.
+pub const MAX_FIXTURE_FILES: usize = 200;
+
+/// Payloads larger than this are not captured; a whole-season F1 response
+/// item is a few KB, so anything near this is not a single game.
+pub const MAX_FIXTURE_BYTES: usize = 256 * 1024;
+
+/// One captured payload plus enough of its league to pick the parser again.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Fixture {
+    pub league: String,
+    pub sport_api: String,
+    pub league_id: i32,
+    pub category: String,
+    pub reason: String,
+    pub captured_at: DateTime<Utc>,
+    pub item: serde_json::Value,
+}
+
+impl Fixture {
+    pub fn new(league: &TrackedLeague, reason: &str, item: &serde_json::Value) -> Self {
+        Self {
+            league: league.name.clone(),
+            sport_api: league.sport_api.clone(),
+            league_id: league.league_id,
+            category: league.category.clone(),
+            reason: reason.to_string(),
+            captured_at: Utc::now(),
+            item: item.clone(),
+        }
+    }
+
+    /// Run the stored payload through the parser its league would use.
+    pub fn replay(&self) -> Option<CleanedData> {
+        let league = TrackedLeague {
+            name: self.league.clone(),
+            sport_api: self.sport_api.clone(),
+            api_host: String::new(),
+            league_id: self.league_id,
+            category: self.category.clone(),
+            country: None,
+            logo_url: None,
+            season: None,
+            season_format: None,
+            offseason_months: None,
+            sport: None,
+            priority: 0,
+        };
+        crate::parse_game(&self.item, &league)
+    }
+}
+
+/// Whether rejected payloads are captured. Read once from
+/// `CAPTURE_FIXTURES`; off unless set to `true` / `1` / `yes` / `on`.
+pub fn capture_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        let enabled = parse_capture_fixtures(env::var("CAPTURE_FIXTURES").ok().as_deref());
+        if enabled {
+            info!("Capturing unparseable games to {} (max {} files)", FIXTURES_DIR, MAX_FIXTURE_FILES);
+        }
+        enabled
+    })
+}
+
+/// Parse the `CAPTURE_FIXTURES` flag; unset, blank and junk are off.
+pub fn parse_capture_fixtures(raw: Option<&str>) -> bool {
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return false;
+    };
+    match raw.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => true,
+        "0" | "false" | "no" | "off" => false,
+        _ => {
+            warn!("Invalid CAPTURE_FIXTURES={:?}, not capturing fixtures", raw);
+            false
+        }
+    }
+}
+
+/// Capture `item` to [`FIXTURES_DIR`] when [`capture_enabled`]. Failures are
+/// logged and otherwise ignored — capture must never affect polling.
+pub fn capture(league: &TrackedLeague, reason: &str, item: &serde_json::Value) {
+    if !capture_enabled() {
+        return;
+    }
+    match write_fixture(Path::new(FIXTURES_DIR), &Fixture::new(league, reason, item), MAX_FIXTURE_FILES) {
+        Ok(Some(path)) => info!("[{}] Captured fixture {}", league.name, path.display()),
+        Ok(None) => warn!("[{}] Payload over {} bytes, not captured", league.name, MAX_FIXTURE_BYTES),
+        Err(e) => warn!("[{}] Failed to capture fixture: {}", league.name, e),
+    }
+}
+
+/// Write `fixture` into `dir`, then delete the oldest fixtures beyond
+/// `max_files`. `None` when the payload exceeds [`MAX_FIXTURE_BYTES`].
+///
+/// File names start with a zero-padded millisecond timestamp and a
+/// per-process sequence number, so name order is capture order.
+pub fn write_fixture(dir: &Path, fixture: &Fixture, max_files: usize) -> io::Result<Option<PathBuf>> {
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+
+    let body = serde_json::to_vec_pretty(fixture)?;
+    if body.len() > MAX_FIXTURE_BYTES {
+        return Ok(None);
+    }
+    fs::create_dir_all(dir)?;
+    let name = format!(
+        "{:013}-{:06}-{}.json",
+        fixture.captured_at.timestamp_millis(),
+        SEQ.fetch_add(1, Ordering::Relaxed) % 1_000_000,
+        fixture.sport_api,
+    );
+    let path = dir.join(name);
+    fs::write(&path, body)?;
+
+    let mut existing = list_fixtures(dir)?;
+    if existing.len() > max_files {
+        let excess = existing.len() - max_files;
+        for old in existing.drain(..excess) {
+            fs::remove_file(old)?;
+        }
+    }
+    Ok(Some(path))
+}
+
+/// The `.json` files in `dir`, oldest first.
+pub fn list_fixtures(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn league() -> TrackedLeague {
+        TrackedLeague {
+            name: "NBA".to_string(),
+            sport_api: "basketball".to_string(),
+            api_host: "v1.basketball.api-sports.io".to_string(),
+            league_id: 12,
+            category: "basketball".to_string(),
+            country: None,
+            logo_url: None,
+            season: None,
+            season_format: None,
+            offseason_months: None,
+            sport: None,
+            priority: 0,
+        }
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("sports-fixtures-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_parse_capture_fixtures() {
+        assert!(!parse_capture_fixtures(None));
+        assert!(!parse_capture_fixtures(Some("")));
+        assert!(parse_capture_fixtures(Some(" TRUE ")));
+        assert!(parse_capture_fixtures(Some("1")));
+        assert!(!parse_capture_fixtures(Some("off")));
+        assert!(!parse_capture_fixtures(Some("maybe")));
+    }
+
+    #[test]
+    fn test_fixture_round_trips_and_replays() {
+        let dir = scratch_dir("roundtrip");
+        let item = serde_json::json!({"id": 1, "teams": {"home": {"id": 5}}});
+        let path = write_fixture(&dir, &Fixture::new(&league(), "unparseable game", &item), 10)
+            .unwrap()
+            .expect("small payload is captured");
+
+        let fixture: Fixture = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(fixture.sport_api, "basketball");
+        assert_eq!(fixture.item, item);
+        assert!(fixture.replay().is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_oldest_fixtures_are_rotated_out() {
+        let dir = scratch_dir("rotate");
+        let mut written = Vec::new();
+        for id in 0..5 {
+            let item = serde_json::json!({"id": id});
+            written.push(write_fixture(&dir, &Fixture::new(&league(), "unparseable game", &item), 3).unwrap().unwrap());
+        }
+
+        assert_eq!(list_fixtures(&dir).unwrap(), written[2..].to_vec());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_oversized_payload_is_not_captured() {
+        let dir = scratch_dir("oversized");
+        let item = serde_json::json!({"blob": "x".repeat(MAX_FIXTURE_BYTES)});
+        assert!(write_fixture(&dir, &Fixture::new(&league(), "unparseable game", &item), 3).unwrap().is_none());
+        assert!(!dir.exists());
+    }
+}
@@ -1,23 +1,32 @@
 use anyhow::{Context, Result};
-use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use axum::{body::Bytes, extract::{Query, State}, http::{header, HeaderMap, HeaderName, StatusCode}, response::IntoResponse, routing::{get, post}, Json, Router};
 use dotenv::dotenv;
-use serde::Serialize;
-use std::{sync::Arc, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::{collections::{BTreeMap, HashSet}, sync::{Arc, OnceLock}, time::{Duration, Instant}};
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 use sports_service::{
-    database::initialize_pool,
+    auth::require_api_token,
+    cache::{CacheStatus, ResponseCache},
+    database::{get_dead_letters, get_export_games, get_games_by_team, get_live_games_detailed, initialize_pool, is_pool_timeout, DeadLetter, LiveGame, PgPool, TeamGame, MAX_TEAM_NAME_CHARS},
+    export::{games_csv, games_ics},
     init::{fatal, spawn_supervised, ReadinessGate, ReadinessSnapshot},
-    init_sports_service,
+    cache_ttls, configured_leagues, day_rollover, db_acquire_timeout, db_pool_size, fast_poll_live_only, final_retention_hours, init_sports_service, live_states, redact_url, reseed_leagues, resolve_config_path,
     log::init_async_logger,
     poll_live, poll_schedule, poll_standings, poll_teams,
-    RateLimiter, SportsHealth,
+    types::PollIntervals,
+    HttpTimeouts, RateLimiter, ReseedSummary, SportsConfig, SportsHealth, LEAGUES_CONFIG_PATH,
 };
 
 #[derive(Clone)]
 struct AppState {
     health: Arc<Mutex<SportsHealth>>,
     readiness: Arc<ReadinessGate>,
+    config: Arc<Mutex<SportsConfig>>,
+    /// Set once the background init has connected to Postgres.
+    pool: Arc<OnceLock<Arc<PgPool>>>,
+    /// Serialized `/games/live` and `/games/team` bodies.
+    games_cache: Arc<ResponseCache<Bytes>>,
 }
 
 #[derive(Serialize)]
@@ -30,11 +39,20 @@ struct ReadyPayload {
 /// Interval for the schedule poll (upcoming games + cleanup).
 const SCHEDULE_POLL_SECS: u64 = 30 * 60; // 30 minutes
 
-/// Fastest "live" poll interval. Actual interval is adaptive (30s when
+/// Live poll interval while any league has a game in progress.
+const LIVE_POLL_MIN_INTERVAL_SECS: u64 = 30;
+
+/// Slowest "live" poll interval. Actual interval is adaptive (30s when
 /// leagues_live > 0, 60s otherwise) but for staleness calculations we use
 /// the widest reasonable gap.
 const LIVE_POLL_MAX_INTERVAL_SECS: u64 = 60;
 
+/// Interval for the standings poll.
+const STANDINGS_POLL_SECS: u64 = 24 * 60 * 60; // daily
+
+/// Interval for the teams poll.
+const TEAMS_POLL_SECS: u64 = 7 * 24 * 60 * 60; // weekly
+
 /// Maximum acceptable staleness before `/health/ready` returns 503. The
 /// staleness threshold is deliberately 2x the widest expected gap between
 /// successful polls, so transient rate-limits or a slow external API don't
@@ -52,6 +70,15 @@ const READINESS_BRIDGE_INTERVAL: Duration = Duration::from_secs(10);
 /// the per-league budget allocation.
 const SPORTS_DAILY_QUOTA: u32 = 7500;
 
+/// Rows returned by `/dead-letters` when `?limit=` is absent, and the most
+/// it will return when present.
+const DEAD_LETTERS_DEFAULT_LIMIT: i64 = 100;
+const DEAD_LETTERS_MAX_LIMIT: i64 = 1000;
+
+/// Shortest `?name=` `/games/team` accepts; a single letter matches nearly
+/// every game.
+const TEAM_SEARCH_MIN_CHARS: usize = 2;
+
 /// Initialize Sentry. The returned guard MUST live for the lifetime of
 /// the process — Drop flushes pending events on shutdown. Sentry MUST
 /// initialize before the Tokio runtime starts (the crate's docs forbid
@@ -151,14 +178,26 @@ async fn run_service() -> Result<()> {
     // to talk to, but the readiness probe at /health/ready will return 503
     // until the init task calls `readiness.mark_ready()` AND the first
     // poll cycle completes.
+    let config = Arc::new(Mutex::new(startup_config()));
+    let pool_cell = Arc::new(OnceLock::new());
     let state = AppState {
         health: health.clone(),
         readiness: readiness.clone(),
+        config: config.clone(),
+        pool: pool_cell.clone(),
+        games_cache: Arc::new(ResponseCache::new()),
     };
     let app = Router::new()
         .route("/health", get(health_ready_handler))
         .route("/health/live", get(health_live_handler))
         .route("/health/ready", get(health_ready_handler))
+        .route("/config", get(config_handler))
+        .route("/dead-letters", get(dead_letters_handler))
+        .route("/reseed", post(reseed_handler))
+        .route("/games.ics", get(games_ics_handler))
+        .route("/games.csv", get(games_csv_handler))
+        .route("/games/live", get(live_games_handler))
+        .route("/games/team", get(team_games_handler))
         .with_state(state);
 
     let port = std::env::var("PORT").unwrap_or_else(|_| "3002".to_string());
@@ -198,6 +237,8 @@ async fn run_service() -> Result<()> {
             }
         };
 
+        let _ = pool_cell.set(pool.clone());
+
         // ── Initialize service (tables, migrations, seeding) ─────────────
         let (client, leagues) = match init_sports_service(&pool).await {
             Ok(result) => result,
@@ -221,6 +262,12 @@ async fn run_service() -> Result<()> {
         // Premier League polls.
         let rate_limiter = Arc::new(RateLimiter::new_per_league(&leagues, SPORTS_DAILY_QUOTA));
 
+        {
+            let mut config = config.lock().await;
+            config.leagues = configured_leagues(&leagues);
+            config.leagues_loaded_at = Some(chrono::Utc::now());
+        }
+
         let client = Arc::new(client);
         let leagues = Arc::new(leagues);
 
@@ -260,6 +307,12 @@ async fn run_service() -> Result<()> {
         let cancel_live = cancel_bg.clone();
         spawn_supervised("sports-live-poll", async move {
             println!("Starting live poll loop (adaptive intervals)...");
+            // With SPORTS_FAST_POLL_LIVE_ONLY, cycles in between full polls
+            // only revisit the leagues that had live games; everything else
+            // is picked up again once LIVE_POLL_MAX_INTERVAL_SECS has passed.
+            let live_only = fast_poll_live_only();
+            let mut live_leagues: HashSet<String> = HashSet::new();
+            let mut last_full_poll: Option<Instant> = None;
             loop {
                 tokio::select! {
                     _ = cancel_live.cancelled() => {
@@ -267,15 +320,23 @@ async fn run_service() -> Result<()> {
                         break;
                     }
                     _ = async {
-                        poll_live(&pool_live, &client_live, &leagues_live, &health_live, &rl_live).await;
+                        let full = !live_only
+                            || live_leagues.is_empty()
+                            || last_full_poll.is_none_or(|t| t.elapsed() >= Duration::from_secs(LIVE_POLL_MAX_INTERVAL_SECS));
+                        let only = (!full).then_some(&live_leagues);
+                        let polled_at = Instant::now();
+                        live_leagues = poll_live(&pool_live, &client_live, &leagues_live, only, &health_live, &rl_live).await;
+                        if full {
+                            last_full_poll = Some(polled_at);
+                        }
 
                         // Adaptive interval: poll more frequently when there are live games
                         let interval = {
                             let h = health_live.lock().await;
                             if h.leagues_live > 0 {
-                                30  // 30s when live games are happening
+                                LIVE_POLL_MIN_INTERVAL_SECS  // when live games are happening
                             } else {
-                                60  // 1 min when no live games
+                                LIVE_POLL_MAX_INTERVAL_SECS  // when no live games
                             }
                         };
 
@@ -289,12 +350,13 @@ async fn run_service() -> Result<()> {
         let pool_sched = pool.clone();
         let client_sched = client.clone();
         let leagues_sched = leagues.clone();
+        let health_sched = health_bg.clone();
         let rl_sched = rate_limiter.clone();
         let cancel_sched = cancel_bg.clone();
         spawn_supervised("sports-schedule-poll", async move {
             println!("Starting schedule poll loop (every {} min)...", SCHEDULE_POLL_SECS / 60);
             // Run immediately on startup to populate the schedule
-            poll_schedule(&pool_sched, &client_sched, &leagues_sched, &rl_sched).await;
+            poll_schedule(&pool_sched, &client_sched, &leagues_sched, &health_sched, &rl_sched).await;
             loop {
                 tokio::select! {
                     _ = cancel_sched.cancelled() => {
@@ -303,7 +365,7 @@ async fn run_service() -> Result<()> {
                     }
                     _ = async {
                         tokio::time::sleep(std::time::Duration::from_secs(SCHEDULE_POLL_SECS)).await;
-                        poll_schedule(&pool_sched, &client_sched, &leagues_sched, &rl_sched).await;
+                        poll_schedule(&pool_sched, &client_sched, &leagues_sched, &health_sched, &rl_sched).await;
                     } => {}
                 }
             }
@@ -325,7 +387,7 @@ async fn run_service() -> Result<()> {
                         break;
                     }
                     _ = async {
-                        tokio::time::sleep(std::time::Duration::from_secs(86400)).await;
+                        tokio::time::sleep(std::time::Duration::from_secs(STANDINGS_POLL_SECS)).await;
                         poll_standings(&pool_standings, &client_standings, &leagues_standings, &rl_standings).await;
                     } => {}
                 }
@@ -348,7 +410,7 @@ async fn run_service() -> Result<()> {
                         break;
                     }
                     _ = async {
-                        tokio::time::sleep(std::time::Duration::from_secs(604800)).await;
+                        tokio::time::sleep(std::time::Duration::from_secs(TEAMS_POLL_SECS)).await;
                         poll_teams(&pool_teams, &client_teams, &leagues_teams, &rl_teams).await;
                     } => {}
                 }
@@ -419,6 +481,255 @@ async fn shutdown_signal() {
     }
 }
 
+/// Everything in [`SportsConfig`] except the league list, which init fills
+/// in once it has loaded it.
+fn startup_config() -> SportsConfig {
+    let timeouts = HttpTimeouts::from_env();
+    let cache = cache_ttls();
+    SportsConfig {
+        config_file: resolve_config_path(LEAGUES_CONFIG_PATH),
+        api_base_url: std::env::var("API_SPORTS_BASE_URL").ok().map(|u| redact_url(&u)),
+        api_key_configured: std::env::var("API_SPORTS_KEY").is_ok_and(|k| !k.trim().is_empty()),
+        live_states: live_states().to_vec(),
+        final_retention_hours: final_retention_hours(),
+        http_connect_timeout_secs: timeouts.connect.as_secs(),
+        http_timeout_secs: timeouts.request.as_secs(),
+        db_acquire_timeout_secs: db_acquire_timeout().as_secs(),
+        db_max_connections: db_pool_size().max_connections,
+        db_min_connections: db_pool_size().min_connections,
+        daily_quota_per_host: SPORTS_DAILY_QUOTA,
+        poll_intervals_secs: PollIntervals {
+            live: LIVE_POLL_MIN_INTERVAL_SECS,
+            live_idle: LIVE_POLL_MAX_INTERVAL_SECS,
+            schedule: SCHEDULE_POLL_SECS,
+            standings: STANDINGS_POLL_SECS,
+            teams: TEAMS_POLL_SECS,
+        },
+        live_cache_ttl_secs: cache.live.as_secs(),
+        cache_ttl_secs: cache.other.as_secs(),
+        day_rollover_hour: day_rollover().hour,
+        day_timezone: day_rollover().timezone.to_string(),
+        leagues_loaded_at: None,
+        leagues: Vec::new(),
+    }
+}
+
+/// Effective configuration — which leagues this pod is polling and with
+/// what settings. Secrets are never part of [`SportsConfig`].
+async fn config_handler(State(state): State<AppState>) -> Json<SportsConfig> {
+    Json(state.config.lock().await.clone())
+}
+
+#[derive(Deserialize)]
+struct DeadLettersQuery {
+    limit: Option<i64>,
+}
+
+/// Most recently seen records the pollers skipped as malformed, newest
+/// first. 503 until the DB pool is up.
+async fn dead_letters_handler(
+    State(state): State<AppState>,
+    Query(params): Query<DeadLettersQuery>,
+) -> Result<Json<Vec<DeadLetter>>, (StatusCode, Json<serde_json::Value>)> {
+    let pool = ready_pool(&state)?;
+    let limit = params
+        .limit
+        .unwrap_or(DEAD_LETTERS_DEFAULT_LIMIT)
+        .clamp(1, DEAD_LETTERS_MAX_LIMIT);
+    match get_dead_letters(pool, limit).await {
+        Ok(rows) => Ok(Json(rows)),
+        Err(e) => Err(db_error(&state.health, e).await),
+    }
+}
+
+#[derive(Deserialize)]
+struct ExportQuery {
+    league: Option<String>,
+}
+
+/// Upcoming (`pre`) games as an iCalendar feed, one event per game.
+/// `?league=` narrows it to one league. 503 until the DB pool is up.
+async fn games_ics_handler(
+    State(state): State<AppState>,
+    Query(params): Query<ExportQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let pool = ready_pool(&state)?;
+    let games = match get_export_games(pool, params.league.as_deref(), true).await {
+        Ok(games) => games,
+        Err(e) => return Err(db_error(&state.health, e).await),
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"games.ics\""),
+        ],
+        games_ics(&games, chrono::Utc::now()),
+    ))
+}
+
+/// Every stored game as CSV, optionally for one `?league=`. 503 until the
+/// DB pool is up.
+async fn games_csv_handler(
+    State(state): State<AppState>,
+    Query(params): Query<ExportQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let pool = ready_pool(&state)?;
+    let games = match get_export_games(pool, params.league.as_deref(), false).await {
+        Ok(games) => games,
+        Err(e) => return Err(db_error(&state.health, e).await),
+    };
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"games.csv\""),
+        ],
+        games_csv(&games),
+    ))
+}
+
+/// Body of `GET /games/live`.
+#[derive(Serialize)]
+struct LiveGamesPayload {
+    total: usize,
+    /// In-progress games keyed by league name.
+    leagues: BTreeMap<String, Vec<LiveGame>>,
+}
+
+/// Every in-progress game with its score, period and clock, grouped by
+/// league. Cached for `SPORTS_LIVE_CACHE_TTL_SECS`. 503 until the DB pool
+/// is up.
+async fn live_games_handler(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let pool = ready_pool(&state)?;
+    let health = &state.health;
+    let (body, status) = state
+        .games_cache
+        .get_or_fetch("live", cache_ttls().live, || async move {
+            let games = match get_live_games_detailed(pool, live_states()).await {
+                Ok(games) => games,
+                Err(e) => return Err(db_error(health, e).await),
+            };
+            let total = games.len();
+            let mut leagues: BTreeMap<String, Vec<LiveGame>> = BTreeMap::new();
+            for game in games {
+                leagues.entry(game.league.clone()).or_default().push(game);
+            }
+            json_bytes(&LiveGamesPayload { total, leagues })
+        })
+        .await?;
+    Ok(cached_json(body, status))
+}
+
+#[derive(Deserialize)]
+struct TeamGamesQuery {
+    name: Option<String>,
+}
+
+/// Body of `GET /games/team`.
+#[derive(Serialize)]
+struct TeamGamesPayload {
+    total: usize,
+    games: Vec<TeamGame>,
+}
+
+/// Upcoming and recent games in any league whose home or away team name
+/// contains `?name=` (case-insensitive), in start-time order. 400 for a
+/// name shorter than [`TEAM_SEARCH_MIN_CHARS`] or longer than any stored
+/// team name; 503 until the DB pool is up. Cached per name for
+/// `SPORTS_CACHE_TTL_SECS`.
+async fn team_games_handler(
+    State(state): State<AppState>,
+    Query(params): Query<TeamGamesQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let name = params.name.as_deref().map(str::trim).unwrap_or_default();
+    let len = name.chars().count();
+    if !(TEAM_SEARCH_MIN_CHARS..=MAX_TEAM_NAME_CHARS).contains(&len) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": format!("name must be {TEAM_SEARCH_MIN_CHARS}-{MAX_TEAM_NAME_CHARS} characters")
+            })),
+        ));
+    }
+    let pool = ready_pool(&state)?;
+    let health = &state.health;
+    let key = format!("team:{}", name.to_lowercase());
+    let (body, status) = state
+        .games_cache
+        .get_or_fetch(&key, cache_ttls().other, || async move {
+            let games = match get_games_by_team(pool, name).await {
+                Ok(games) => games,
+                Err(e) => return Err(db_error(health, e).await),
+            };
+            json_bytes(&TeamGamesPayload { total: games.len(), games })
+        })
+        .await?;
+    Ok(cached_json(body, status))
+}
+
+fn json_bytes<T: Serialize>(payload: &T) -> Result<Bytes, (StatusCode, Json<serde_json::Value>)> {
+    serde_json::to_vec(payload)
+        .map(Bytes::from)
+        .map_err(|e| internal_error(e.into()))
+}
+
+/// A cached JSON body, tagged `X-Cache: HIT` or `MISS`.
+fn cached_json(body: Bytes, status: CacheStatus) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "application/json"),
+            (HeaderName::from_static("x-cache"), status.as_str()),
+        ],
+        body,
+    )
+}
+
+fn internal_error(e: anyhow::Error) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({"error": format!("{e:#}")})),
+    )
+}
+
+/// Like [`internal_error`], except an exhausted connection pool is a 503
+/// that says so and is recorded as a health error.
+async fn db_error(health: &Mutex<SportsHealth>, e: anyhow::Error) -> (StatusCode, Json<serde_json::Value>) {
+    if !is_pool_timeout(&e) {
+        return internal_error(e);
+    }
+    let reason = format!("No DB connection free within {}s", db_acquire_timeout().as_secs());
+    health.lock().await.record_error(reason.clone());
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({"error": reason})),
+    )
+}
+
+/// Add any league in `leagues.json` that isn't tracked yet, without touching
+/// existing rows. Requires the `INGEST_API_TOKEN` bearer token.
+async fn reseed_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ReseedSummary>, (StatusCode, Json<serde_json::Value>)> {
+    require_api_token(&headers)?;
+    let pool = ready_pool(&state)?;
+    match reseed_leagues(pool).await {
+        Ok(summary) => Ok(Json(summary)),
+        Err(e) => Err(db_error(&state.health, e).await),
+    }
+}
+
+/// The DB pool, or a 503 while the init task is still connecting.
+fn ready_pool(state: &AppState) -> Result<&Arc<PgPool>, (StatusCode, Json<serde_json::Value>)> {
+    state.pool.get().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "database not ready"})),
+        )
+    })
+}
+
 /// Liveness probe: 200 as long as the process is up.
 async fn health_live_handler() -> (StatusCode, Json<serde_json::Value>) {
     (StatusCode::OK, Json(serde_json::json!({"status": "alive"})))
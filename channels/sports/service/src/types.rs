@@ -1,7 +1,33 @@
 use chrono::{DateTime, Datelike, Utc};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU32, Ordering};
+use crate::latency::FetchLatency;
+
+/// Status changes kept in the health payload; the oldest is dropped first.
+const MAX_STATUS_TRANSITIONS: usize = 20;
+
+/// One change of `status`, e.g. `healthy` → `degraded`. Only edges are
+/// recorded, so a service that stays degraded for an hour adds one entry.
+#[derive(Serialize, Clone, Debug)]
+pub struct StatusTransition {
+    pub from: String,
+    pub to: String,
+    pub at: DateTime<Utc>,
+    /// The error that caused a move to `degraded`; `None` on recovery.
+    pub reason: Option<String>,
+}
+
+/// Errors kept in the health payload; the oldest is dropped first.
+const MAX_RECENT_ERRORS: usize = 20;
+
+/// One error passed to `record_error`, kept even when the status was
+/// already `degraded` so distinct causes don't collapse into `last_error`.
+#[derive(Serialize, Clone, Debug)]
+pub struct RecentError {
+    pub message: String,
+    pub at: DateTime<Utc>,
+}
 
 #[derive(Serialize, Clone)]
 pub struct SportsHealth {
@@ -12,6 +38,13 @@ pub struct SportsHealth {
     pub rate_limits: Option<HashMap<String, u32>>,
     pub error_count: u64,
     pub last_error: Option<String>,
+    /// Most recent status changes, oldest first.
+    pub transitions: VecDeque<StatusTransition>,
+    /// Most recent errors, oldest first. `last_error` is the newest of these.
+    pub recent_errors: VecDeque<RecentError>,
+    /// Time to fetch and parse a live-poll response from api-sports.io,
+    /// over the most recent requests.
+    pub fetch_latency: FetchLatency,
 }
 
 impl Default for SportsHealth {
@@ -30,20 +63,46 @@ impl SportsHealth {
             rate_limits: None,
             error_count: 0,
             last_error: None,
+            transitions: VecDeque::new(),
+            recent_errors: VecDeque::new(),
+            fetch_latency: FetchLatency::new(),
         }
     }
 
     pub fn record_success(&mut self, leagues_active: u32, leagues_live: u32) {
         self.last_poll = Some(Utc::now());
-        self.status = String::from("healthy");
+        self.set_status("healthy", None);
         self.leagues_active = leagues_active;
         self.leagues_live = leagues_live;
     }
 
     pub fn record_error(&mut self, error: String) {
         self.error_count += 1;
+        self.set_status("degraded", Some(error.clone()));
+        if self.recent_errors.len() == MAX_RECENT_ERRORS {
+            self.recent_errors.pop_front();
+        }
+        self.recent_errors.push_back(RecentError {
+            message: error.clone(),
+            at: Utc::now(),
+        });
         self.last_error = Some(error);
-        self.status = String::from("degraded");
+    }
+
+    /// Change `status`, logging the edge in `transitions` when it differs.
+    fn set_status(&mut self, to: &str, reason: Option<String>) {
+        if self.status == to {
+            return;
+        }
+        if self.transitions.len() == MAX_STATUS_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(StatusTransition {
+            from: std::mem::replace(&mut self.status, to.to_string()),
+            to: to.to_string(),
+            at: Utc::now(),
+            reason,
+        });
     }
 
     pub fn set_rate_limits(&mut self, limits: HashMap<String, u32>) {
@@ -55,6 +114,78 @@ impl SportsHealth {
     }
 }
 
+/// Effective running configuration, served at `GET /config` so an operator
+/// can see what this pod actually loaded. Only resolved, non-secret values
+/// go in here — the API key and database URL are never copied in.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SportsConfig {
+    /// Resolved path of `leagues.json`.
+    pub config_file: String,
+    /// `API_SPORTS_BASE_URL` override with credentials and query stripped.
+    pub api_base_url: Option<String>,
+    pub api_key_configured: bool,
+    pub live_states: Vec<String>,
+    pub final_retention_hours: i32,
+    pub http_connect_timeout_secs: u64,
+    pub http_timeout_secs: u64,
+    /// Wait for a pooled DB connection before a query fails.
+    pub db_acquire_timeout_secs: u64,
+    pub db_max_connections: u32,
+    pub db_min_connections: u32,
+    pub daily_quota_per_host: u32,
+    pub poll_intervals_secs: PollIntervals,
+    /// `/games/live` and other read endpoint cache TTLs; 0 means uncached.
+    pub live_cache_ttl_secs: u64,
+    pub cache_ttl_secs: u64,
+    /// Local hour the sports day rolls over at, in `day_timezone`.
+    pub day_rollover_hour: u32,
+    pub day_timezone: String,
+    /// Set once init has loaded the league list.
+    pub leagues_loaded_at: Option<DateTime<Utc>>,
+    pub leagues: Vec<ConfiguredLeague>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct PollIntervals {
+    pub live: u64,
+    pub live_idle: u64,
+    pub schedule: u64,
+    pub standings: u64,
+    pub teams: u64,
+}
+
+/// One tracked league as the poll loops see it.
+#[derive(Serialize, Clone, Debug)]
+pub struct ConfiguredLeague {
+    pub name: String,
+    pub sport_api: String,
+    pub api_host: String,
+    pub league_id: i32,
+    pub category: String,
+    pub season: Option<String>,
+    pub season_format: Option<String>,
+    pub offseason_months: Option<Vec<i32>>,
+    /// Broad sport for grouping (see `sport_for_api`).
+    pub sport: Option<String>,
+    /// Poll order; higher goes first.
+    pub priority: i32,
+    /// `"file"` when the league is in `leagues.json`, `"env"` when it is in
+    /// `SPORTS_LEAGUES` (used without the file), `"db"` when it only exists
+    /// as an enabled `tracked_leagues` row.
+    pub source: &'static str,
+}
+
+/// Outcome of `POST /reseed`.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ReseedSummary {
+    /// Config entries inserted because they weren't tracked yet.
+    pub added: u64,
+    /// Valid entries that were already tracked and were left as they are.
+    pub existing: u64,
+    /// Entries dropped by validation.
+    pub skipped: usize,
+}
+
 /// Per-sport-host rate limit tracker with per-league fair-share allocation.
 ///
 /// api-sports.io enforces budgets per `sport_api` host (basketball, football,
@@ -264,6 +395,51 @@ impl RateLimiter {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_health_records_only_status_edges() {
+        let mut health = SportsHealth::new();
+        health.record_success(3, 0);
+        health.record_success(3, 1);
+        health.record_error("NBA: timeout".to_string());
+        health.record_error("NHL: timeout".to_string());
+        health.record_success(3, 1);
+
+        let edges: Vec<(&str, &str)> = health
+            .transitions
+            .iter()
+            .map(|t| (t.from.as_str(), t.to.as_str()))
+            .collect();
+        assert_eq!(edges, [("starting", "healthy"), ("healthy", "degraded"), ("degraded", "healthy")]);
+        assert_eq!(health.transitions[1].reason.as_deref(), Some("NBA: timeout"));
+        assert_eq!(health.transitions[2].reason, None);
+    }
+
+    #[test]
+    fn test_health_transitions_are_capped() {
+        let mut health = SportsHealth::new();
+        for _ in 0..MAX_STATUS_TRANSITIONS {
+            health.record_success(1, 0);
+            health.record_error("boom".to_string());
+        }
+        assert_eq!(health.transitions.len(), MAX_STATUS_TRANSITIONS);
+        assert_eq!(health.transitions.back().unwrap().to, "degraded");
+    }
+
+    #[test]
+    fn test_health_keeps_recent_errors() {
+        let mut health = SportsHealth::new();
+        health.record_error("NBA: timeout".to_string());
+        health.record_error("NHL: 429".to_string());
+        health.record_success(3, 0);
+        for i in 0..MAX_RECENT_ERRORS {
+            health.record_error(format!("MLB: error {i}"));
+        }
+
+        assert_eq!(health.recent_errors.len(), MAX_RECENT_ERRORS);
+        assert_eq!(health.recent_errors.front().unwrap().message, "MLB: error 0");
+        assert_eq!(health.recent_errors.back().map(|e| e.message.as_str()), health.last_error.as_deref());
+    }
+
     #[test]
     fn test_rate_limiter_new() {
         let sports = vec!["basketball".to_string(), "football".to_string()];
@@ -339,6 +515,8 @@ mod tests {
             season: None,
             season_format: None,
             offseason_months: offseason,
+            sport: crate::sport_for_api(sport_api).map(str::to_string),
+            priority: 0,
         }
     }
 
@@ -1,5 +1,6 @@
 //! Cleanup behavior — verifies `cleanup_old_games` deletes only the
-//! rows we expect at the right thresholds (12h finished, 7d pre, 24h live).
+//! rows we expect at the right thresholds (12h finished by default, 7d pre,
+//! 24h live).
 //!
 //! Skips when DATABASE_URL is not set so unit-test runs in CI without
 //! a Postgres backend don't fail.
@@ -72,7 +73,7 @@ async fn test_cleanup_per_state_thresholds() {
     // 4 rows in `cases` are marked `should_survive = false` — assert the
     // returned count matches so a regression where the query under-deletes
     // but happens to clean up the rows we check would still fail the test.
-    let deleted = cleanup_old_games(&pool).await.unwrap();
+    let deleted = cleanup_old_games(&pool, 12).await.unwrap();
     let expected_deleted = cases.iter().filter(|(_, _, _, _, s)| !s).count() as u64;
     assert_eq!(deleted, expected_deleted, "cleanup_old_games deleted {} rows, expected {}", deleted, expected_deleted);
 
@@ -92,3 +93,40 @@ async fn test_cleanup_per_state_thresholds() {
     query("DELETE FROM tracked_leagues WHERE name = '__cleanup_test__'")
         .execute(&*pool).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_cleanup_respects_final_retention() {
+    let Some(pool) = skip_unless_db().await else { return };
+
+    query("INSERT INTO tracked_leagues (name, sport_api, api_host, league_id, category)
+           VALUES ('__retention_test__', 'football', 'localhost', 0, 'Test')
+           ON CONFLICT (name) DO NOTHING")
+        .execute(&*pool).await.unwrap();
+    query("DELETE FROM games WHERE league = '__retention_test__'")
+        .execute(&*pool).await.unwrap();
+
+    let now = Utc::now();
+    for (id, hours_ago) in [("final_13h", 13), ("final_30h", 30)] {
+        query(
+            "INSERT INTO games (league, sport, external_game_id, home_team_name, away_team_name,
+                                start_time, state, updated_at)
+             VALUES ('__retention_test__', 'football', $1, 'H', 'A', $2, 'final', $2)"
+        )
+        .bind(id)
+        .bind(now - chrono::Duration::hours(hours_ago))
+        .execute(&*pool).await.unwrap();
+    }
+
+    // A 24h window keeps the 13h-old result the default would have dropped.
+    cleanup_old_games(&pool, 24).await.unwrap();
+    let left: Vec<(String,)> = sqlx::query_as(
+        "SELECT external_game_id FROM games WHERE league = '__retention_test__'"
+    )
+    .fetch_all(&*pool).await.unwrap();
+    assert_eq!(left, vec![("final_13h".to_string(),)]);
+
+    query("DELETE FROM games WHERE league = '__retention_test__'")
+        .execute(&*pool).await.unwrap();
+    query("DELETE FROM tracked_leagues WHERE name = '__retention_test__'")
+        .execute(&*pool).await.unwrap();
+}
@@ -0,0 +1,66 @@
+//! Dead-letter bookkeeping — verifies repeats collapse on (source, reason)
+//! and that `prune_dead_letters` drops only rows not seen recently.
+//!
+//! Skips when DATABASE_URL is not set so unit-test runs in CI without
+//! a Postgres backend don't fail.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use sports_service::database::{get_dead_letters, initialize_pool, prune_dead_letters, record_dead_letter};
+use sqlx::query;
+
+const SOURCE: &str = "__dead_letter_test__";
+
+async fn skip_unless_db() -> Option<Arc<sqlx::PgPool>> {
+    if std::env::var("DATABASE_URL").is_err() && std::env::var("DB_HOST").is_err() {
+        eprintln!("Skipping dead letter test: no DATABASE_URL / DB_HOST set");
+        return None;
+    }
+    match initialize_pool().await {
+        Ok(p) => Some(Arc::new(p)),
+        Err(e) => {
+            eprintln!("Skipping dead letter test: could not connect: {e:#}");
+            None
+        }
+    }
+}
+
+async fn clear(pool: &sqlx::PgPool) {
+    query("DELETE FROM dead_letter WHERE source = $1")
+        .bind(SOURCE)
+        .execute(pool).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_repeats_collapse_and_old_rows_are_pruned() {
+    let Some(pool) = skip_unless_db().await else { return };
+    clear(&pool).await;
+
+    // Different payloads, same source and reason: one row, latest payload.
+    record_dead_letter(&pool, SOURCE, "unparseable game", r#"{"id":1}"#).await;
+    record_dead_letter(&pool, SOURCE, "unparseable game", r#"{"id":2}"#).await;
+    record_dead_letter(&pool, SOURCE, "duplicate competitors", r#"{"id":3}"#).await;
+
+    let rows: Vec<_> = get_dead_letters(&pool, 500).await.unwrap()
+        .into_iter()
+        .filter(|r| r.source == SOURCE)
+        .collect();
+    assert_eq!(rows.len(), 2);
+    let unparseable = rows.iter().find(|r| r.reason == "unparseable game").unwrap();
+    assert_eq!(unparseable.occurrences, 2);
+    assert_eq!(unparseable.raw_payload, r#"{"id":2}"#);
+
+    query("UPDATE dead_letter SET last_seen_at = NOW() - INTERVAL '30 days'
+           WHERE source = $1 AND reason = 'duplicate competitors'")
+        .bind(SOURCE)
+        .execute(&*pool).await.unwrap();
+
+    prune_dead_letters(&pool, 14).await.unwrap();
+    let left: Vec<(String,)> = sqlx::query_as("SELECT reason FROM dead_letter WHERE source = $1")
+        .bind(SOURCE)
+        .fetch_all(&*pool).await.unwrap();
+    assert_eq!(left, vec![("unparseable game".to_string(),)]);
+
+    clear(&pool).await;
+}
@@ -0,0 +1,70 @@
+//! Request timeouts — verifies a hung api-sports.io connection fails the
+//! league's poll and lands in `SportsHealth` instead of stalling the cycle.
+//!
+//! Needs no database: the pool is lazy and points nowhere, and every DB
+//! helper `poll_live` touches already degrades to a logged warning.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use std::time::Duration;
+use sports_service::database::TrackedLeague;
+use sports_service::{build_client, poll_live, ApiClient, HttpTimeouts, RateLimiter, SportsHealth};
+use sqlx::postgres::PgPoolOptions;
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+#[tokio::test]
+async fn test_hung_request_is_recorded_as_health_error() {
+    // Accept connections and never answer, like a wedged upstream.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+
+    let pool = Arc::new(
+        PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(200))
+            .connect_lazy("postgres://nobody@127.0.0.1:1/none")
+            .unwrap(),
+    );
+    let league = TrackedLeague {
+        name: "__timeout_test__".to_string(),
+        sport_api: "basketball".to_string(),
+        api_host: "localhost".to_string(),
+        league_id: 0,
+        category: "Test".to_string(),
+        country: None,
+        logo_url: None,
+        season: None,
+        season_format: None,
+        offseason_months: None,
+        sport: Some("basketball".to_string()),
+        priority: 0,
+    };
+    let leagues = vec![league];
+    let rate_limiter = Arc::new(RateLimiter::new_per_league(&leagues, 100));
+    let health = Arc::new(Mutex::new(SportsHealth::new()));
+    let client = ApiClient {
+        http: build_client("test-key", HttpTimeouts {
+            connect: Duration::from_millis(200),
+            request: Duration::from_millis(300),
+        }).unwrap(),
+        base_override: Some(format!("http://{addr}")),
+    };
+
+    tokio::time::timeout(
+        Duration::from_secs(10),
+        poll_live(&pool, &client, &leagues, None, &health, &rate_limiter),
+    )
+    .await
+    .expect("poll_live hung past the request timeout");
+
+    let health = health.lock().await;
+    assert_eq!(health.error_count, 1);
+    assert!(health.last_error.is_some(), "timeout was not recorded");
+}
@@ -0,0 +1,74 @@
+//! Connection acquire timeout — verifies that when no pooled connection can
+//! be had, `upsert_game` and the live-games query fail promptly with an
+//! error `is_pool_timeout` recognises, instead of waiting forever.
+//!
+//! Needs no database: the single-connection pool points at a listener that
+//! accepts and never answers, so every acquire runs into the timeout.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::Utc;
+use sports_service::database::{get_live_games_detailed, is_pool_timeout, upsert_game, CleanedData, Team};
+use sqlx::postgres::PgPoolOptions;
+use tokio::net::TcpListener;
+
+fn game() -> CleanedData {
+    let team = |name: &str| Team {
+        name: name.to_string(),
+        logo: None,
+        score: None,
+        code: None,
+    };
+    CleanedData {
+        league: "__pool_timeout_test__".to_string(),
+        sport: "football".to_string(),
+        external_game_id: "g1".to_string(),
+        link: None,
+        home_team: team("Home"),
+        away_team: team("Away"),
+        start_time: Utc::now(),
+        short_detail: None,
+        state: "pre".to_string(),
+        status_short: None,
+        status_long: None,
+        timer: None,
+        venue: None,
+        season: None,
+        venue_city: None,
+        venue_state: None,
+        series_summary: None,
+    }
+}
+
+#[tokio::test]
+async fn test_exhausted_pool_times_out_promptly() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+
+    let pool = Arc::new(
+        PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_millis(200))
+            .connect_lazy(&format!("postgres://nobody@{addr}/none"))
+            .unwrap(),
+    );
+
+    // Retries included, three 200ms acquires plus backoff is under a second.
+    let started = Instant::now();
+    let err = upsert_game(pool.clone(), game()).await.expect_err("upsert got a connection");
+    assert!(is_pool_timeout(&err), "unexpected error: {err:#}");
+    assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+
+    let started = Instant::now();
+    let err = get_live_games_detailed(&pool, &["in".to_string()]).await.expect_err("query got a connection");
+    assert!(is_pool_timeout(&err), "unexpected error: {err:#}");
+    assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+}
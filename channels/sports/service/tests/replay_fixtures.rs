@@ -0,0 +1,44 @@
+//! Parser regression corpus — replays every payload in `fixtures/` (see
+//! `sports_service::fixtures`) through the sport parsers. A fixture may
+//! still be unparseable; what must never happen is a panic. A few
+//! hand-written fixtures also pin fields the parsers must fill in.
+//!
+//! Needs no database or network.
+
+#![cfg(test)]
+
+use std::{fs, panic, path::Path};
+use sports_service::fixtures::{list_fixtures, Fixture};
+
+#[test]
+fn test_every_fixture_replays_without_panicking() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    let paths = list_fixtures(&dir).expect("fixtures/ should be readable");
+    assert!(!paths.is_empty(), "no fixtures in {}", dir.display());
+
+    let mut panicked = Vec::new();
+    for path in &paths {
+        let raw = fs::read(path).unwrap();
+        let fixture: Fixture = serde_json::from_slice(&raw)
+            .unwrap_or_else(|e| panic!("{} is not a fixture: {e}", path.display()));
+        if panic::catch_unwind(|| fixture.replay()).is_err() {
+            panicked.push(path.display().to_string());
+        }
+    }
+    assert!(panicked.is_empty(), "parser panicked on: {}", panicked.join(", "));
+}
+
+fn replay(name: &str) -> sports_service::database::CleanedData {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(name);
+    let fixture: Fixture = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    fixture.replay().unwrap_or_else(|| panic!("{name} should parse"))
+}
+
+#[test]
+fn test_series_summary_only_for_playoff_games() {
+    let playoff = replay("american-football-playoff-round.json");
+    assert_eq!(playoff.series_summary.as_deref(), Some("Divisional Round"));
+
+    let regular = replay("american-football-regular-season.json");
+    assert_eq!(regular.series_summary, None);
+}
@@ -0,0 +1,93 @@
+//! Startup seeding — verifies a league the database rejects partway
+//! through leaves none of the batch behind, and that re-seeding matching
+//! rows writes nothing.
+//!
+//! Skips when DATABASE_URL is not set so unit-test runs in CI without
+//! a Postgres backend don't fail.
+
+#![cfg(test)]
+
+use std::sync::Arc;
+use sports_service::database::{initialize_pool, seed_tracked_leagues, LeagueConfig};
+use sqlx::{query, query_scalar};
+
+// Each test owns its names; tests in one binary run concurrently.
+const CRASH_NAMES: [&str; 3] = ["__seed_crash_a__", "__seed_crash_b__", "__seed_crash_c__"];
+const NOOP_NAMES: [&str; 3] = ["__seed_noop_a__", "__seed_noop_b__", "__seed_noop_c__"];
+
+async fn skip_unless_db() -> Option<Arc<sqlx::PgPool>> {
+    if std::env::var("DATABASE_URL").is_err() && std::env::var("DB_HOST").is_err() {
+        eprintln!("Skipping seed test: no DATABASE_URL / DB_HOST set");
+        return None;
+    }
+    match initialize_pool().await {
+        Ok(p) => Some(Arc::new(p)),
+        Err(e) => {
+            eprintln!("Skipping seed test: could not connect: {e:#}");
+            None
+        }
+    }
+}
+
+fn league(name: &str) -> LeagueConfig {
+    LeagueConfig {
+        name: name.to_string(),
+        sport_api: "football".to_string(),
+        api_host: "v3.football.api-sports.io".to_string(),
+        league_id: 39,
+        category: "soccer".to_string(),
+        country: None,
+        logo_url: None,
+        season: None,
+        season_format: None,
+        offseason_months: Some(vec![6, 7]),
+        sport: None,
+        priority: 0,
+    }
+}
+
+async fn clear(pool: &sqlx::PgPool, names: &[&str]) {
+    query("DELETE FROM tracked_leagues WHERE name = ANY($1)")
+        .bind(names)
+        .execute(pool).await.unwrap();
+}
+
+async fn stored_count(pool: &sqlx::PgPool, names: &[&str]) -> i64 {
+    query_scalar("SELECT COUNT(*) FROM tracked_leagues WHERE name = ANY($1)")
+        .bind(names)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_failure_mid_seed_leaves_no_rows() {
+    let Some(pool) = skip_unless_db().await else { return };
+    clear(&pool, &CRASH_NAMES).await;
+
+    // Postgres refuses NUL bytes in text columns, so the second league
+    // fails after the first has already been upserted.
+    let mut bad = league(CRASH_NAMES[1]);
+    bad.category = "soc\0cer".to_string();
+    let leagues = vec![league(CRASH_NAMES[0]), bad, league(CRASH_NAMES[2])];
+
+    assert!(seed_tracked_leagues(pool.clone(), leagues).await.is_err());
+    assert_eq!(stored_count(&pool, &CRASH_NAMES).await, 0);
+}
+
+#[tokio::test]
+async fn test_reseeding_matching_rows_is_a_no_op() {
+    let Some(pool) = skip_unless_db().await else { return };
+    clear(&pool, &NOOP_NAMES).await;
+
+    let leagues: Vec<LeagueConfig> = NOOP_NAMES.iter().copied().map(league).collect();
+    assert_eq!(seed_tracked_leagues(pool.clone(), leagues.clone()).await.unwrap(), 3);
+    assert_eq!(seed_tracked_leagues(pool.clone(), leagues.clone()).await.unwrap(), 0);
+
+    let mut changed = leagues;
+    changed[2].priority = 5;
+    assert_eq!(seed_tracked_leagues(pool.clone(), changed).await.unwrap(), 3);
+    assert_eq!(stored_count(&pool, &NOOP_NAMES).await, 3);
+
+    clear(&pool, &NOOP_NAMES).await;
+}
@@ -19,12 +19,13 @@ export function StandingsView({ league }: StandingsViewProps) {
   const { sorted, cutoff, userTeamKey } = useMemo(() => {
     if (!league?.standings) return { sorted: [], cutoff: 0, userTeamKey: null };
     const items = [...league.standings].sort((a, b) => {
-      const ra = a.rank ?? 999;
-      const rb = b.rank ?? 999;
+      const ra = a.rank ?? a.computed_rank ?? 999;
+      const rb = b.rank ?? b.computed_rank ?? 999;
       if (ra !== rb) return ra - rb;
       if (b.wins !== a.wins) return b.wins - a.wins;
       return (
-        parseFloat(String(b.points_for || 0)) - parseFloat(String(a.points_for || 0))
+        (b.points_for_value ?? parseFloat(String(b.points_for || 0))) -
+        (a.points_for_value ?? parseFloat(String(a.points_for || 0)))
       );
     });
     return {
@@ -88,13 +88,19 @@ export interface StandingsEntry {
   team_logo: string;
   manager_name: string;
   rank: number | null;
+  /** 1..n by win percentage, then points for; always set, unlike `rank`. */
+  computed_rank?: number;
   wins: number;
   losses: number;
   ties: number;
   percentage?: string;
+  percentage_value?: number;
   games_back?: string;
+  games_back_value?: number;
   points_for: number | string;
+  points_for_value?: number;
   points_against?: string;
+  points_against_value?: number;
   streak_type: string;
   streak_value: number;
   playoff_seed: number | null;
@@ -46,6 +46,11 @@ export interface Game {
   timer?: string;
   venue?: string;
   season?: string;
+  venue_city?: string;
+  venue_state?: string;
+  series_summary?: string;
+  /** Broad sport for grouping (basketball, football, hockey, baseball, soccer). */
+  sport_group?: string;
   created_at?: string;
   updated_at?: string;
 }
@@ -61,6 +66,7 @@ export interface RssItem {
   description: string;
   source_name: string;
   published_at: string | null;
+  lang?: string | null;
   created_at: string;
   updated_at: string;
 }